@@ -0,0 +1,46 @@
+//! Whole-graph sweeps on a large brain. Run twice to compare:
+//! `cargo bench --bench sweeps` and `cargo bench --bench sweeps --features parallel`.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use loom_db::{ContextFormat, LoomGraph, NodeId};
+
+const NODES: usize = 200_000;
+
+fn build_graph() -> (LoomGraph, Vec<NodeId>) {
+    let mut graph = LoomGraph::new(0.95);
+    let ids: Vec<NodeId> = (0..NODES)
+        .map(|i| graph.add_concept(format!("concept{}", i), format!("definition number {}", i % 1000)))
+        .collect();
+    for _ in 0..25 {
+        graph.tick();
+    }
+    // Ativações variadas: a maioria decaída, uma fração reforçada
+    graph.settle_decay();
+    let recalled: Vec<NodeId> = ids.iter().step_by(50).copied().collect();
+    graph.boost_batch(&recalled, 0.5);
+    (graph, ids)
+}
+
+fn sweeps(c: &mut Criterion) {
+    let (graph, ids) = build_graph();
+    let batch: Vec<NodeId> = ids.iter().step_by(4).copied().collect();
+
+    c.bench_function("settle_decay", |b| {
+        b.iter_batched(|| graph.clone(), |mut g| black_box(g.settle_decay()), BatchSize::LargeInput)
+    });
+
+    c.bench_function("context_ranking", |b| {
+        b.iter(|| black_box(graph.get_context_as(ContextFormat::Json, 0.5).len()))
+    });
+
+    c.bench_function("boost_batch", |b| {
+        b.iter_batched(|| graph.clone(), |mut g| black_box(g.boost_batch(&batch, 0.3)), BatchSize::LargeInput)
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = sweeps
+}
+criterion_main!(benches);
@@ -0,0 +1,4 @@
+fn main() {
+    // O addon Node precisa de flags de link por plataforma (ex.: macOS)
+    napi_build::setup();
+}
@@ -0,0 +1,416 @@
+//! # Node.js API
+//! Native Node addon (the `loom_db_node` cdylib), for server-side JS where WASM is too
+//! slow on big graphs. The class is exported as `LoomGraph` and method names
+//! match the WASM API, but results are real JS objects and arrays (same shapes
+//! as the WASM JSON) instead of JSON strings, and binary backups are `Buffer`s.
+//!
+//! Enum arguments are strings: context formats `"xml"`, `"json"`,
+//! `"markdown"`, `"openai"`; message grouping `"aggregated"`, `"per_memory"`.
+//! Bad ids behave as in the WASM API (`false`/`null`/empty results); parse
+//! and I/O failures throw.
+
+use loom_db::{merge_json, ContextFormat, DotOptions, EdgeKind, LoomConfig, LoomGraph, MessageGrouping, Node, NodeId};
+use napi::bindgen_prelude::Buffer;
+use napi::{Error, Result};
+use napi_derive::napi;
+use serde_json::{json, Value};
+
+fn parse_id(id_str: &str) -> Option<NodeId> {
+    id_str.parse().ok()
+}
+
+fn to_error(e: impl std::fmt::Display) -> Error {
+    Error::from_reason(e.to_string())
+}
+
+fn parse_format(format: &str) -> Result<ContextFormat> {
+    match format {
+        "xml" => Ok(ContextFormat::Xml),
+        "json" => Ok(ContextFormat::Json),
+        "markdown" => Ok(ContextFormat::Markdown),
+        "openai" => Ok(ContextFormat::OpenAiMessages),
+        other => Err(Error::from_reason(format!("unknown context format '{}'", other))),
+    }
+}
+
+fn parse_grouping(grouping: &str) -> Result<MessageGrouping> {
+    match grouping {
+        "aggregated" => Ok(MessageGrouping::Aggregated),
+        "per_memory" => Ok(MessageGrouping::PerMemory),
+        other => Err(Error::from_reason(format!("unknown message grouping '{}'", other))),
+    }
+}
+
+fn to_value(value: impl serde::Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+#[napi(js_name = "LoomGraph")]
+pub struct NodeLoom {
+    graph: LoomGraph,
+}
+
+impl NodeLoom {
+    pub fn graph(&self) -> &LoomGraph {
+        &self.graph
+    }
+
+    pub fn into_graph(self) -> LoomGraph {
+        self.graph
+    }
+}
+
+impl From<LoomGraph> for NodeLoom {
+    fn from(graph: LoomGraph) -> Self {
+        Self { graph }
+    }
+}
+
+#[napi]
+impl NodeLoom {
+    #[napi(constructor)]
+    pub fn new(decay_rate: f64) -> Self {
+        LoomGraph::new(decay_rate as f32).into()
+    }
+
+    #[napi(getter, js_name = "current_tick")]
+    pub fn current_tick(&self) -> i64 {
+        self.graph.current_tick as i64
+    }
+
+    #[napi(getter, js_name = "live_tick")]
+    pub fn live_tick(&self) -> i64 {
+        self.graph.live_tick() as i64
+    }
+
+    #[napi(getter, js_name = "decay_rate")]
+    pub fn decay_rate(&self) -> f64 {
+        self.graph.decay_rate as f64
+    }
+
+    /// Throws outside `0 < rate <= 1`.
+    #[napi(setter, js_name = "decay_rate")]
+    pub fn set_decay_rate(&mut self, rate: f64) -> Result<()> {
+        self.graph.set_decay_rate(rate as f32).map_err(to_error)
+    }
+
+    #[napi(getter, js_name = "node_count")]
+    pub fn node_count(&self) -> u32 {
+        self.graph.node_count() as u32
+    }
+
+    #[napi(getter, js_name = "edge_count")]
+    pub fn edge_count(&self) -> u32 {
+        self.graph.edge_count() as u32
+    }
+
+    // --- INGESTÃO DE DADOS ---
+
+    #[napi(js_name = "add_concept")]
+    pub fn add_concept(&mut self, name: String, definition: String) -> String {
+        self.graph.add_concept(name, definition).to_string()
+    }
+
+    #[napi(js_name = "upsert_concept")]
+    pub fn upsert_concept(&mut self, name: String, definition: String) -> String {
+        self.graph.upsert_concept(name, definition).to_string()
+    }
+
+    #[napi(js_name = "add_alias")]
+    pub fn add_alias(&mut self, id_str: String, alias: String) -> bool {
+        parse_id(&id_str).is_some_and(|id| self.graph.add_alias(id, &alias))
+    }
+
+    #[napi(js_name = "find_concept_by_name")]
+    pub fn find_concept_by_name(&self, name: String) -> Option<String> {
+        self.graph.find_concept_by_name(&name).map(|id| id.to_string())
+    }
+
+    #[napi(js_name = "add_episode")]
+    pub fn add_episode(&mut self, summary: String) -> String {
+        self.graph.add_episode(summary).to_string()
+    }
+
+    /// Throws if `iso_datetime` is unparseable or in the future.
+    #[napi(js_name = "add_episode_at")]
+    pub fn add_episode_at(&mut self, summary: String, iso_datetime: String) -> Result<String> {
+        self.graph.add_episode_at(summary, &iso_datetime).map(|id| id.to_string()).map_err(to_error)
+    }
+
+    #[napi(js_name = "add_episodes_batch")]
+    pub fn add_episodes_batch(&mut self, summaries: Vec<String>) -> Vec<String> {
+        self.graph.add_episodes_batch(summaries).iter().map(|id| id.to_string()).collect()
+    }
+
+    #[napi(js_name = "add_state")]
+    pub fn add_state(&mut self, valence: f64, arousal: f64) -> String {
+        self.graph.add_state(valence as f32, arousal as f32).to_string()
+    }
+
+    #[napi(js_name = "add_observation")]
+    pub fn add_observation(&mut self, tool: String, input: String, output: String) -> String {
+        self.graph.add_observation(tool, input, output).to_string()
+    }
+
+    // --- CONEXÕES ---
+
+    #[napi(js_name = "connect")]
+    pub fn connect(&mut self, source_id: String, target_id: String, weight: f64) -> bool {
+        match (parse_id(&source_id), parse_id(&target_id)) {
+            (Some(source), Some(target)) => self.graph.connect(source, target, weight as f32),
+            _ => false,
+        }
+    }
+
+    // --- BUSCA & RECUPERAÇÃO ---
+
+    /// `[[id, activation], ...]`, most active first.
+    #[napi(js_name = "search")]
+    pub fn search(&self, query: String) -> Value {
+        to_value(self.graph.search_native(&query))
+    }
+
+    #[napi(js_name = "search_in_namespace")]
+    pub fn search_in_namespace(&self, namespace: String, query: String) -> Value {
+        to_value(self.graph.search_in_namespace(&namespace, &query))
+    }
+
+    /// Like `search`, but counts every hit as an access.
+    #[napi(js_name = "recall")]
+    pub fn recall(&mut self, query: String) -> Value {
+        to_value(self.graph.recall(&query))
+    }
+
+    #[napi(js_name = "peek_activation")]
+    pub fn peek_activation(&self, id_str: String) -> Option<f64> {
+        parse_id(&id_str).and_then(|id| self.graph.peek_activation(id)).map(f64::from)
+    }
+
+    /// Node object plus `pinned`, `community` and, for States, the emotion `label`; `null` for unknown ids.
+    #[napi(js_name = "get_node_info")]
+    pub fn get_node_info(&self, id_str: String) -> Option<Value> {
+        let id = parse_id(&id_str)?;
+        let node = self.graph.get_node(id)?;
+        let mut value = to_value(node);
+        if let Some(object) = value.as_object_mut() {
+            object.insert("pinned".to_string(), json!(node.meta().is_pinned()));
+            object.insert("community".to_string(), json!(self.graph.community_of(id)));
+            if let Node::State(_, data) = node {
+                object.insert("label".to_string(), json!(self.graph.state_label(data)));
+            }
+        }
+        Some(value)
+    }
+
+    /// `{outgoing: [{id, kind, weight}], incoming: [...], in_degree, out_degree}`.
+    #[napi(js_name = "get_neighbors")]
+    pub fn get_neighbors(&self, id_str: String) -> Option<Value> {
+        let id = parse_id(&id_str)?;
+        let edges = |list: Vec<(NodeId, EdgeKind, f32)>| -> Vec<Value> {
+            list.into_iter()
+                .map(|(other, kind, weight)| json!({ "id": other, "kind": kind, "weight": weight }))
+                .collect()
+        };
+        let (outgoing, incoming) = (self.graph.neighbors(id), self.graph.incoming(id));
+        Some(json!({
+            "out_degree": outgoing.len(),
+            "in_degree": incoming.len(),
+            "outgoing": edges(outgoing),
+            "incoming": edges(incoming),
+        }))
+    }
+
+    // --- CONTEXTO ---
+
+    #[napi(js_name = "get_context")]
+    pub fn get_context(&self, min_activation: f64) -> String {
+        self.graph.get_context_prompt(min_activation as f32)
+    }
+
+    #[napi(js_name = "get_context_as")]
+    pub fn get_context_as(&self, format: String, min_activation: f64) -> Result<String> {
+        Ok(self.graph.get_context_as(parse_format(&format)?, min_activation as f32))
+    }
+
+    /// `[{role: "system", content}, ...]`
+    #[napi(js_name = "get_context_messages")]
+    pub fn get_context_messages(&self, min_activation: f64, grouping: Option<String>) -> Result<Value> {
+        let grouping = grouping.as_deref().map(parse_grouping).transpose()?.unwrap_or_default();
+        Ok(to_value(self.graph.get_context_messages_with(min_activation as f32, grouping)))
+    }
+
+    #[napi(js_name = "get_context_budgeted")]
+    pub fn get_context_budgeted(&self, min_activation: f64, max_tokens: u32) -> String {
+        self.graph.get_context_budgeted(min_activation as f32, max_tokens as usize)
+    }
+
+    // --- SIMULAÇÃO & TEMPO ---
+
+    #[napi(js_name = "tick")]
+    pub fn tick(&mut self) {
+        self.graph.tick();
+    }
+
+    /// Advances `ticks` at once, running due scheduled events; returns how many ran.
+    #[napi(js_name = "fast_forward")]
+    pub fn fast_forward(&mut self, ticks: u32) -> u32 {
+        self.graph.fast_forward(ticks as u64) as u32
+    }
+
+    #[napi(js_name = "stimulate")]
+    pub fn stimulate(&mut self, id_str: String, force: f64) -> bool {
+        parse_id(&id_str).is_some_and(|id| self.graph.stimulate(id, force as f32))
+    }
+
+    #[napi(js_name = "wake_up")]
+    pub fn wake_up(&mut self) -> Value {
+        to_value(self.graph.wake_up())
+    }
+
+    /// Real-time clock catch-up; returns the ticks advanced.
+    #[napi(js_name = "catch_up_clock")]
+    pub fn catch_up_clock(&mut self) -> i64 {
+        self.graph.catch_up_clock() as i64
+    }
+
+    #[napi(js_name = "dream")]
+    pub fn dream(&mut self) -> String {
+        self.graph.dream()
+    }
+
+    #[napi(js_name = "prune_low_stability")]
+    pub fn prune_low_stability(&mut self, threshold: f64) -> u32 {
+        self.graph.prune_low_stability(threshold as f32) as u32
+    }
+
+    #[napi(js_name = "forget")]
+    pub fn forget(&mut self, id_str: String) -> bool {
+        parse_id(&id_str).is_some_and(|id| self.graph.forget(id))
+    }
+
+    #[napi(js_name = "undo_forget")]
+    pub fn undo_forget(&mut self, id_str: String) -> bool {
+        parse_id(&id_str).is_some_and(|id| self.graph.undo_forget(id))
+    }
+
+    #[napi(js_name = "pin")]
+    pub fn pin(&mut self, id_str: String) -> bool {
+        parse_id(&id_str).is_some_and(|id| self.graph.pin(id))
+    }
+
+    #[napi(js_name = "unpin")]
+    pub fn unpin(&mut self, id_str: String) -> bool {
+        parse_id(&id_str).is_some_and(|id| self.graph.unpin(id))
+    }
+
+    #[napi(js_name = "is_pinned")]
+    pub fn is_pinned(&self, id_str: String) -> bool {
+        parse_id(&id_str).is_some_and(|id| self.graph.is_pinned(id))
+    }
+
+    #[napi(js_name = "set_always_in_context")]
+    pub fn set_always_in_context(&mut self, id_str: String, pinned: bool) -> bool {
+        parse_id(&id_str).is_some_and(|id| self.graph.set_always_in_context(id, pinned))
+    }
+
+    #[napi(js_name = "set_tag")]
+    pub fn set_tag(&mut self, id_str: String, key: String, value: String) -> bool {
+        parse_id(&id_str).is_some_and(|id| self.graph.set_tag(id, key, value))
+    }
+
+    #[napi(js_name = "get_tag")]
+    pub fn get_tag(&self, id_str: String, key: String) -> Option<String> {
+        parse_id(&id_str).and_then(|id| self.graph.get_tag(id, &key)).map(str::to_string)
+    }
+
+    // --- DIAGNÓSTICO & CONFIGURAÇÃO ---
+
+    #[napi(js_name = "health_report")]
+    pub fn health_report(&self) -> Value {
+        to_value(self.graph.health_report())
+    }
+
+    #[napi(js_name = "validate")]
+    pub fn validate(&self) -> Value {
+        to_value(self.graph.validate())
+    }
+
+    #[napi(js_name = "perf_stats")]
+    pub fn perf_stats(&self) -> Value {
+        to_value(self.graph.perf_stats())
+    }
+
+    /// Current `LoomConfig` (including the graph-wide `decay_rate`).
+    #[napi(js_name = "config")]
+    pub fn config(&self) -> Value {
+        to_value(self.graph.config())
+    }
+
+    /// Alias of `config()`, matching the WASM API.
+    #[napi(js_name = "get_config_json")]
+    pub fn get_config_json(&self) -> Value {
+        self.config()
+    }
+
+    /// Full or partial `LoomConfig`, merged over the current one. A
+    /// `decay_rate` key changes the decay rate too; changing a creation-time
+    /// field (`capacity`, `archive_tier`, `journal_capacity`,
+    /// `dynamics.clock`) throws.
+    #[napi(js_name = "set_config")]
+    pub fn set_config(&mut self, patch: Value) -> Result<()> {
+        let mut merged = to_value(self.graph.config());
+        merge_json(&mut merged, &patch);
+        let config: LoomConfig = serde_json::from_value(merged).map_err(to_error)?;
+        self.graph.set_config(config).map_err(to_error)
+    }
+
+    // --- EXPORT/IMPORT ---
+
+    #[napi(js_name = "export_backup")]
+    pub fn export_backup(&self) -> String {
+        self.graph.export_backup()
+    }
+
+    #[napi(factory, js_name = "import_backup")]
+    pub fn import_backup(json: String) -> Result<Self> {
+        LoomGraph::from_backup_json(&json).map(Self::from).map_err(to_error)
+    }
+
+    #[napi(js_name = "export_backup_binary")]
+    pub fn export_backup_binary(&self) -> Result<Buffer> {
+        self.graph.to_binary().map(Buffer::from).map_err(to_error)
+    }
+
+    #[napi(factory, js_name = "import_backup_binary")]
+    pub fn import_backup_binary(bytes: Buffer) -> Result<Self> {
+        LoomGraph::from_binary(&bytes).map(Self::from).map_err(to_error)
+    }
+
+    #[napi(js_name = "save_binary")]
+    pub fn save_binary(&mut self, filepath: String) -> Result<()> {
+        self.graph.save_binary(&filepath).map_err(to_error)
+    }
+
+    #[napi(factory, js_name = "load_binary")]
+    pub fn load_binary(filepath: String) -> Result<Self> {
+        LoomGraph::load_binary(&filepath).map(Self::from).map_err(to_error)
+    }
+
+    /// `options`: partial `DotOptions` (`{}` for defaults).
+    #[napi(js_name = "export_dot")]
+    pub fn export_dot(&self, options: Value) -> Result<String> {
+        let options: DotOptions = serde_json::from_value(options).map_err(to_error)?;
+        Ok(self.graph.export_dot(&options))
+    }
+
+    /// `{nodes: [{id, label, group, value}], edges: [{from, to, weight, kind}]}`.
+    #[napi(js_name = "export_vis_json")]
+    pub fn export_vis_json(&self) -> Value {
+        serde_json::from_str(&self.graph.export_vis_json()).unwrap_or_default()
+    }
+
+    #[napi(js_name = "export_mermaid")]
+    pub fn export_mermaid(&self, center_id: String, radius: u32) -> Option<String> {
+        parse_id(&center_id).and_then(|center| self.graph.export_mermaid(center, radius as usize))
+    }
+}
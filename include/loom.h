@@ -0,0 +1,57 @@
+/*
+ * LoomDB C API (build with `--features ffi`). See src/ffi.rs for the
+ * ownership rules: every returned char* is freed with loom_string_free,
+ * byte buffers with loom_bytes_free, graphs with loom_free. Failures
+ * return NULL/false/-1; loom_last_error() explains why.
+ */
+#ifndef LOOM_H
+#define LOOM_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct LoomGraph LoomGraph;
+
+enum {
+    LOOM_CONTEXT_XML = 0,
+    LOOM_CONTEXT_JSON = 1,
+    LOOM_CONTEXT_MARKDOWN = 2,
+    LOOM_CONTEXT_OPENAI = 3,
+};
+
+const char *loom_last_error(void);
+void loom_string_free(char *text);
+void loom_bytes_free(uint8_t *bytes, size_t len);
+
+LoomGraph *loom_new(float decay_rate);
+void loom_free(LoomGraph *graph);
+LoomGraph *loom_load_binary(const char *path);
+bool loom_save_binary(LoomGraph *graph, const char *path);
+uint8_t *loom_to_bytes(const LoomGraph *graph, size_t *out_len);
+LoomGraph *loom_from_bytes(const uint8_t *bytes, size_t len);
+
+char *loom_add_concept(LoomGraph *graph, const char *name, const char *definition);
+char *loom_add_episode(LoomGraph *graph, const char *summary);
+char *loom_add_episode_at(LoomGraph *graph, const char *summary, const char *iso_datetime);
+bool loom_connect(LoomGraph *graph, const char *source_id, const char *target_id, float weight);
+
+char *loom_search(const LoomGraph *graph, const char *query);
+char *loom_get_node(const LoomGraph *graph, const char *id);
+char *loom_get_context(const LoomGraph *graph, float min_activation, uint32_t format);
+
+bool loom_stimulate(LoomGraph *graph, const char *id, float force);
+bool loom_tick(LoomGraph *graph, uint64_t ticks);
+char *loom_wake_up(LoomGraph *graph);
+int64_t loom_prune(LoomGraph *graph, float threshold);
+size_t loom_node_count(const LoomGraph *graph);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* LOOM_H */
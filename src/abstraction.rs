@@ -0,0 +1,127 @@
+//! # Episode Abstraction
+//! Turns clusters of related episodes into new concepts during
+//! consolidation. LoomDB finds the clusters (recent episodes linked to each
+//! other or mentioning the same concepts) and wires the result; the text
+//! comes from a user-supplied `Summarizer` (typically an LLM call).
+//!
+//! The new concept gets `Evoked` edges to its source episodes. Episodes that
+//! already evoked a concept are not abstracted again.
+
+use crate::{Connection, ConsolidationConfig, ConsolidationReport, LoomGraph, Node, NodeId, MENTIONED_EDGE};
+use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub const EVOKED_EDGE: &str = "Evoked";
+
+pub trait Summarizer {
+    /// Returns `(name, definition)` for a concept abstracting `episodes`
+    /// (their summaries, oldest first), or None to skip this cluster.
+    fn summarize(&mut self, episodes: &[&str]) -> Option<(String, String)>;
+}
+
+impl<F: FnMut(&[&str]) -> Option<(String, String)>> Summarizer for F {
+    fn summarize(&mut self, episodes: &[&str]) -> Option<(String, String)> {
+        self(episodes)
+    }
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+impl LoomGraph {
+    /// `consolidate`, then abstraction of episode clusters through `summarizer`.
+    pub fn consolidate_with(&mut self, config: &ConsolidationConfig, summarizer: &mut dyn Summarizer) -> ConsolidationReport {
+        let mut report = self.consolidate(config);
+        report.abstracted = self.abstract_episodes(config, summarizer);
+        report
+    }
+
+    /// Clusters of recent, not yet abstracted episodes (at least
+    /// `config.min_cluster_size`), each oldest first; biggest clusters first.
+    pub fn episode_clusters(&self, config: &ConsolidationConfig) -> Vec<Vec<NodeId>> {
+        let abstracted: HashSet<NodeId> = self.adjacency.values().flatten()
+            .filter(|e| e.edge_type == EVOKED_EDGE)
+            .map(|e| e.target)
+            .collect();
+        let mut episodes: Vec<(DateTime<Utc>, u64, NodeId)> = self.nodes.values()
+            .filter_map(|node| match node {
+                Node::Episode(meta, data) => Some((data.timestamp, meta.seq, meta.id)),
+                _ => None,
+            })
+            .filter(|(_, _, id)| !abstracted.contains(id))
+            .filter(|(_, _, id)| self.current_tick.saturating_sub(self.nodes[id].meta().last_tick) <= config.recent_ticks)
+            .collect();
+        episodes.sort();
+        let position: HashMap<NodeId, usize> = episodes.iter().enumerate().map(|(i, (_, _, id))| (*id, i)).collect();
+
+        // Union-find: episódios ligados entre si ou ao mesmo conceito
+        let mut parent: Vec<usize> = (0..episodes.len()).collect();
+        let mut concept_owner: BTreeMap<NodeId, usize> = BTreeMap::new();
+        for (i, (_, _, id)) in episodes.iter().enumerate() {
+            for edge in self.adjacency.get(id).into_iter().flatten() {
+                let other = if let Some(j) = position.get(&edge.target) {
+                    *j
+                } else if edge.edge_type == MENTIONED_EDGE {
+                    *concept_owner.entry(edge.target).or_insert(i)
+                } else {
+                    continue;
+                };
+                let (a, b) = (find(&mut parent, i), find(&mut parent, other));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+
+        let mut groups: BTreeMap<usize, Vec<NodeId>> = BTreeMap::new();
+        for (i, (_, _, id)) in episodes.iter().enumerate() {
+            let root = find(&mut parent, i);
+            groups.entry(root).or_default().push(*id);
+        }
+        let mut clusters: Vec<Vec<NodeId>> = groups.into_values()
+            .filter(|group| group.len() >= config.min_cluster_size.max(2))
+            .collect();
+        clusters.sort_by_key(|group| Reverse(group.len()));
+        clusters
+    }
+
+    /// Asks `summarizer` for a concept per cluster (up to `config.max_abstractions`)
+    /// and links it to the source episodes. Returns the new concept ids.
+    pub fn abstract_episodes(&mut self, config: &ConsolidationConfig, summarizer: &mut dyn Summarizer) -> Vec<NodeId> {
+        let mut created = Vec::new();
+        for cluster in self.episode_clusters(config).into_iter().take(config.max_abstractions) {
+            let summaries: Vec<&str> = cluster.iter()
+                .filter_map(|id| match self.nodes.get(id) {
+                    Some(Node::Episode(_, data)) => Some(data.summary.as_str()),
+                    _ => None,
+                })
+                .collect();
+            let Some((name, definition)) = summarizer.summarize(&summaries) else { continue };
+            created.push(self.add_abstraction(name, definition, &cluster, config.evoked_weight));
+        }
+        created
+    }
+
+    /// Creates a concept with `Evoked` edges to `episodes`. For callers that
+    /// summarize asynchronously: take `episode_clusters`, generate the text,
+    /// then wire it here.
+    pub fn add_abstraction(&mut self, name: String, definition: String, episodes: &[NodeId], weight: f32) -> NodeId {
+        let concept = self.add_concept(name, definition);
+        for episode in episodes {
+            if !self.nodes.contains_key(episode) {
+                continue;
+            }
+            self.insert_edge(concept, Connection {
+                target: *episode,
+                weight,
+                edge_type: EVOKED_EDGE.to_string(),
+                inferred: false,
+            });
+        }
+        concept
+    }
+}
@@ -0,0 +1,60 @@
+//! # Access Statistics
+//! Counts how often each memory is actually used (search hits via `recall`,
+//! direct boosts) and when it was last touched, in wall-clock time. Ripple
+//! spill-over doesn't count as access. Drives LRU-style eviction and the
+//! `access` term of `importance`.
+
+use crate::{GraphEvent, LoomGraph, NodeId};
+use chrono::{DateTime, Utc};
+
+impl LoomGraph {
+    /// Bumps `access_count` and `last_accessed` for the given nodes.
+    pub fn record_access(&mut self, ids: &[NodeId]) {
+        let ids: Vec<NodeId> = ids.iter().filter(|id| self.nodes.contains_key(id)).copied().collect();
+        if ids.is_empty() {
+            return;
+        }
+        // O horário vai no evento: o replay não usa o relógio de agora
+        let at = self.now();
+        self.apply_access(&ids, at);
+        self.record(GraphEvent::Accessed { ids, at });
+    }
+
+    pub(crate) fn apply_access(&mut self, ids: &[NodeId], at: DateTime<Utc>) {
+        for id in ids {
+            if let Some(node) = self.nodes.get_mut(id) {
+                let meta = node.meta_mut();
+                meta.access_count += 1;
+                meta.last_accessed = Some(at);
+            }
+        }
+    }
+
+    /// `search_native` that also counts every hit as an access.
+    pub fn recall(&mut self, query: &str) -> Vec<(NodeId, f32)> {
+        let hits = self.search_native(query);
+        let ids: Vec<NodeId> = hits.iter().map(|(id, _)| *id).collect();
+        self.record_access(&ids);
+        self.maybe_autosave();
+        hits
+    }
+
+    /// The `k` most accessed nodes as `(id, access_count)`, highest first.
+    pub fn most_accessed(&self, k: usize) -> Vec<(NodeId, u64)> {
+        let mut ranked: Vec<(NodeId, u64)> = self.nodes.values()
+            .map(|node| (node.meta().id, node.meta().access_count))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked.truncate(k);
+        ranked
+    }
+
+    /// The `k` least recently used nodes, stalest first. Never-accessed
+    /// nodes come first, oldest inserted first.
+    pub fn least_recently_used(&self, k: usize) -> Vec<NodeId> {
+        let mut nodes: Vec<_> = self.nodes.values().map(|node| node.meta()).collect();
+        nodes.sort_by_key(|meta| (meta.last_accessed, meta.seq));
+        nodes.into_iter().take(k).map(|meta| meta.id).collect()
+    }
+}
@@ -0,0 +1,42 @@
+//! # Aliases
+//! Alternative surface forms for a concept ("JS" for "JavaScript"). Aliases
+//! are part of the concept's indexed text, match during auto-linking and
+//! resolve through `find_concept_by_name` / `upsert_concept`.
+
+use crate::{tokenize, LoomGraph, Node, NodeId};
+
+impl LoomGraph {
+    /// Adds an alias to a concept. Returns false if `id` isn't a concept or
+    /// the alias is empty or already one of its names.
+    pub fn add_alias(&mut self, id: NodeId, alias: &str) -> bool {
+        let wanted = tokenize(alias);
+        let Some(Node::Concept(_, data)) = self.nodes.get(&id) else { return false };
+        if wanted.is_empty() || std::iter::once(&data.name).chain(&data.aliases).any(|form| tokenize(form) == wanted) {
+            return false;
+        }
+        let alias = alias.trim().to_string();
+        self.rewrite_node(id, |node| {
+            if let Node::Concept(_, data) = node {
+                data.aliases.push(alias);
+            }
+        });
+        self.maybe_autosave();
+        true
+    }
+
+    /// Removes an alias (normalized comparison). Returns false if it wasn't there.
+    pub fn remove_alias(&mut self, id: NodeId, alias: &str) -> bool {
+        let wanted = tokenize(alias);
+        let Some(Node::Concept(_, data)) = self.nodes.get(&id) else { return false };
+        if !data.aliases.iter().any(|a| tokenize(a) == wanted) {
+            return false;
+        }
+        self.rewrite_node(id, |node| {
+            if let Node::Concept(_, data) = node {
+                data.aliases.retain(|a| tokenize(a) != wanted);
+            }
+        });
+        self.maybe_autosave();
+        true
+    }
+}
@@ -0,0 +1,151 @@
+//! # Anonymized Export
+//! `export_anonymized(salt)` produces a backup that is safe to share for
+//! debugging or research: concept names (the graph's named entities) become
+//! pseudonyms, and every occurrence of those names in other memories is
+//! rewritten to match. Emails, URLs and `config.anonymize.patterns` matches
+//! become `redacted_<hash>` tokens. Ids, edges, activation, stability and
+//! timestamps are kept, so the structure behaves exactly like the original.
+//!
+//! Pseudonyms are a salted SHA-256 of the lowercased original: the same salt
+//! gives the same pseudonym across exports, a different salt unlinks them.
+//! The event log is dropped (it holds the raw text).
+
+use crate::{ConfigError, EventLog, LoomGraph, Node};
+use regex::{Regex, RegexBuilder};
+use sha2::{Digest, Sha256};
+
+const EMAIL_PATTERN: &str = r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}";
+const URL_PATTERN: &str = r"\b(?:https?://|www\.)[^\s<>]+";
+
+fn pseudonym(prefix: &str, salt: &str, value: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(salt.as_bytes())
+        .chain_update(b":")
+        .chain_update(value.to_lowercase().as_bytes())
+        .finalize();
+    let hex: String = digest.iter().take(4).map(|b| format!("{:02x}", b)).collect();
+    format!("{}_{}", prefix, hex)
+}
+
+struct Anonymizer {
+    salt: String,
+    /// Nomes de conceitos (mais longos primeiro) -> pseudônimo
+    names: Option<(Regex, Vec<(String, String)>)>,
+    redactions: Vec<Regex>,
+}
+
+impl Anonymizer {
+    fn rewrite(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for pattern in &self.redactions {
+            out = pattern.replace_all(&out, |caps: &regex::Captures| pseudonym("redacted", &self.salt, &caps[0])).into_owned();
+        }
+        if let Some((pattern, names)) = &self.names {
+            out = pattern.replace_all(&out, |caps: &regex::Captures| {
+                let found = caps[0].to_lowercase();
+                names.iter().find(|(name, _)| *name == found).map(|(_, alias)| alias.clone()).unwrap_or(found)
+            }).into_owned();
+        }
+        out
+    }
+}
+
+impl LoomGraph {
+    /// Serialized backup (same format as `export_backup`) with personal content
+    /// replaced by consistent pseudonyms. Fails only if a configured pattern is invalid.
+    pub fn export_anonymized(&self, salt: &str) -> Result<String, ConfigError> {
+        let cfg = &self.config.anonymize;
+        let mut redactions = Vec::new();
+        if cfg.redact_emails {
+            redactions.push(EMAIL_PATTERN.to_string());
+        }
+        if cfg.redact_urls {
+            redactions.push(URL_PATTERN.to_string());
+        }
+        redactions.extend(cfg.patterns.iter().cloned());
+        let redactions = redactions.into_iter()
+            .map(|pattern| Regex::new(&pattern).map_err(|e| ConfigError::InvalidPattern {
+                field: "anonymize.patterns",
+                reason: e.to_string(),
+                pattern,
+            }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Apelidos recebem o mesmo pseudônimo do nome do conceito
+        let mut names: Vec<(String, String)> = self.nodes.values()
+            .filter_map(|node| match node {
+                Node::Concept(_, d) if !d.name.trim().is_empty() => {
+                    let name = d.name.trim().to_lowercase();
+                    let pseudo = pseudonym("concept", salt, &name);
+                    let forms = std::iter::once(name).chain(d.aliases.iter().map(|a| a.trim().to_lowercase()));
+                    Some(forms.filter(|f| !f.is_empty()).map(|f| (f, pseudo.clone())).collect::<Vec<_>>())
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        names.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.0.cmp(&b.0)));
+        names.dedup();
+        let names = if names.is_empty() {
+            None
+        } else {
+            let alternation: Vec<String> = names.iter().map(|(name, _)| regex::escape(name)).collect();
+            let pattern = RegexBuilder::new(&format!(r"\b(?:{})\b", alternation.join("|")))
+                .case_insensitive(true)
+                .build()
+                .expect("escaped names always form a valid pattern");
+            Some((pattern, names))
+        };
+        let anonymizer = Anonymizer { salt: salt.to_string(), names, redactions };
+
+        let mut anon = self.clone();
+        for node in anon.nodes.values_mut() {
+            match node {
+                Node::Concept(_, d) => {
+                    d.name = anonymizer.rewrite(&d.name);
+                    d.definition = anonymizer.rewrite(&d.definition);
+                    for alias in &mut d.aliases {
+                        *alias = anonymizer.rewrite(alias);
+                    }
+                }
+                Node::Episode(_, d) => {
+                    d.summary = anonymizer.rewrite(&d.summary);
+                    for attachment in &mut d.attachments {
+                        attachment.uri = anonymizer.rewrite(&attachment.uri);
+                    }
+                }
+                Node::Observation(_, d) => {
+                    d.input = anonymizer.rewrite(&d.input);
+                    d.output = anonymizer.rewrite(&d.output);
+                }
+                Node::Goal(_, d) => d.description = anonymizer.rewrite(&d.description),
+                Node::Entity(_, d) => {
+                    d.name = anonymizer.rewrite(&d.name);
+                    for value in d.attributes.values_mut() {
+                        *value = anonymizer.rewrite(value);
+                    }
+                }
+                Node::Custom(_, d) => d.map_strings(&|s| anonymizer.rewrite(s)),
+                Node::Skill(_, d) => {
+                    d.name = anonymizer.rewrite(&d.name);
+                    d.description = anonymizer.rewrite(&d.description);
+                    for step in &mut d.steps {
+                        *step = anonymizer.rewrite(step);
+                    }
+                }
+                Node::State(..) => {}
+            }
+            for value in node.meta_mut().tags.values_mut() {
+                *value = anonymizer.rewrite(value);
+            }
+            if let Some(source) = &mut node.meta_mut().source {
+                source.reference = anonymizer.rewrite(&source.reference);
+            }
+        }
+
+        // O índice é derivado do texto: reconstrói a partir dos pseudônimos
+        anon.rebuild_index();
+        anon.event_log = EventLog::default();
+        Ok(anon.export_backup())
+    }
+}
@@ -0,0 +1,317 @@
+//! # Cold Archive
+//! Append-only file for memories moved out of RAM. "Forgotten" nodes stay
+//! recoverable on explicit deep-recall requests without costing memory.
+//!
+//! Format: one JSON `ArchivedNode` per line. A sidecar `<path>.idx` holds
+//! `<id> <byte offset>` lines (the offset index), so single records can be
+//! fetched with one seek. Re-archiving a node appends a new record; the index
+//! always points at the newest. `retrieve_from_archive` streams the file line
+//! by line and never loads it whole. Native only.
+//!
+//! Pruning goes through the archive tier set in `config.archive_tier`:
+//! `Off` deletes as before, `Memory` keeps the records on the graph itself
+//! (persisted with it), `File { path }` appends them to a `ColdArchive`.
+//! `search_archive` and `restore` work across both stores.
+
+use crate::{Connection, GraphEvent, Inverse, LoomGraph, Node, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedNode {
+    pub node: Node,
+    pub outgoing: Vec<Connection>,
+    /// Edges from other nodes into this one, as `(source, edge)`.
+    pub incoming: Vec<(NodeId, Connection)>,
+    pub archived_at_tick: u64,
+}
+
+/// Where pruned memories go instead of being destroyed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ArchiveTier {
+    /// Pruning deletes permanently.
+    #[default]
+    Off,
+    /// Records are kept in `LoomGraph::archived`.
+    Memory,
+    /// Records are appended to the cold archive at `path`. If the file can't
+    /// be written they fall back to the in-memory archive.
+    File { path: String },
+}
+
+pub struct ColdArchive {
+    path: String,
+    index: HashMap<NodeId, u64>,
+}
+
+fn index_path(path: &str) -> String {
+    format!("{}.idx", path)
+}
+
+fn invalid(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+impl ColdArchive {
+    /// Opens (or creates) an archive. The offset index is read from the
+    /// sidecar, or rebuilt by scanning the archive if the sidecar is missing.
+    pub fn open(path: &str) -> io::Result<Self> {
+        OpenOptions::new().create(true).append(true).open(path)?;
+        let mut archive = Self { path: path.to_string(), index: HashMap::new() };
+
+        match File::open(index_path(path)) {
+            Ok(file) => {
+                for line in BufReader::new(file).lines() {
+                    let line = line?;
+                    let Some((id, offset)) = line.split_once(' ') else { continue };
+                    let id: NodeId = id.parse().map_err(invalid)?;
+                    archive.index.insert(id, offset.trim().parse().map_err(invalid)?);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => archive.rebuild_index()?,
+            Err(e) => return Err(e),
+        }
+        Ok(archive)
+    }
+
+    fn rebuild_index(&mut self) -> io::Result<()> {
+        self.index.clear();
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        let mut offset = 0u64;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                break;
+            }
+            if let Ok(record) = serde_json::from_str::<ArchivedNode>(&line) {
+                self.index.insert(record.node.meta().id, offset);
+            }
+            offset += read as u64;
+        }
+
+        let mut sidecar = BufWriter::new(File::create(index_path(&self.path))?);
+        for (id, offset) in &self.index {
+            writeln!(sidecar, "{} {}", id, offset)?;
+        }
+        sidecar.flush()
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.index.contains_key(&id)
+    }
+
+    pub fn append(&mut self, record: &ArchivedNode) -> io::Result<()> {
+        let mut file = OpenOptions::new().append(true).open(&self.path)?;
+        let offset = file.metadata()?.len();
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+
+        let id = record.node.meta().id;
+        let mut sidecar = OpenOptions::new().create(true).append(true).open(index_path(&self.path))?;
+        writeln!(sidecar, "{} {}", id, offset)?;
+        self.index.insert(id, offset);
+        Ok(())
+    }
+
+    /// Fetches the newest record for `id` with a single seek.
+    pub fn get(&self, id: NodeId) -> io::Result<Option<ArchivedNode>> {
+        let Some(offset) = self.index.get(&id) else { return Ok(None) };
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        reader.seek(SeekFrom::Start(*offset))?;
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        serde_json::from_str(&line).map(Some).map_err(invalid)
+    }
+
+    /// Streams the current (newest) record of every archived node.
+    pub fn scan(&self) -> io::Result<impl Iterator<Item = io::Result<ArchivedNode>> + '_> {
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        let mut offset = 0u64;
+        Ok(std::iter::from_fn(move || loop {
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(read) => {
+                    let at = offset;
+                    offset += read as u64;
+                    let record: ArchivedNode = match serde_json::from_str(&line) {
+                        Ok(record) => record,
+                        Err(e) => return Some(Err(invalid(e))),
+                    };
+                    // Registros antigos (superados por um re-arquivamento) são pulados
+                    if self.index.get(&record.node.meta().id) == Some(&at) {
+                        return Some(Ok(record));
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }))
+    }
+}
+
+impl LoomGraph {
+    /// Moves nodes (with their incoming and outgoing edges) into the archive
+    /// and removes them from the graph. Returns how many were archived.
+    pub fn archive_nodes(&mut self, archive: &mut ColdArchive, ids: &[NodeId]) -> io::Result<usize> {
+        let mut archived = Vec::new();
+        let mut records = Vec::new();
+        for record in self.archive_records(ids) {
+            archive.append(&record)?;
+            archived.push(record.node.meta().id);
+            records.push(record);
+        }
+
+        if !archived.is_empty() {
+            self.record(GraphEvent::Pruned { ids: archived.clone() });
+            self.journal_push(vec![GraphEvent::Pruned { ids: archived.clone() }], Inverse::Reinsert(records));
+            self.remove_nodes(&archived);
+        }
+        Ok(archived.len())
+    }
+
+    /// Snapshot of each existing node in `ids` with its edges. Incoming
+    /// edges from other nodes in `ids` are left out (they travel as outgoing).
+    fn archive_records(&self, ids: &[NodeId]) -> Vec<ArchivedNode> {
+        let leaving: HashSet<NodeId> = ids.iter().copied().collect();
+        ids.iter()
+            .filter_map(|id| {
+                let node = self.nodes.get(id)?;
+                let incoming = self.adjacency.iter()
+                    .filter(|(source, _)| !leaving.contains(source))
+                    .flat_map(|(source, edges)| {
+                        edges.iter().filter(|e| e.target == *id).map(|e| (*source, e.clone()))
+                    })
+                    .collect();
+                Some(ArchivedNode {
+                    node: node.clone(),
+                    outgoing: self.adjacency.get(id).cloned().unwrap_or_default(),
+                    incoming,
+                    archived_at_tick: self.current_tick,
+                })
+            })
+            .collect()
+    }
+
+    /// Prunes `ids`, first moving them into the configured archive tier.
+    pub(crate) fn discard_nodes(&mut self, ids: &[NodeId]) {
+        let tier = self.config.archive_tier.clone();
+        let journaling = self.journaling();
+        let snapshot = if tier != ArchiveTier::Off || journaling { self.archive_records(ids) } else { Vec::new() };
+        if journaling {
+            self.journal_push(vec![GraphEvent::Pruned { ids: ids.to_vec() }], Inverse::Reinsert(snapshot.clone()));
+        }
+        if tier != ArchiveTier::Off {
+            let mut records = snapshot;
+            if let ArchiveTier::File { path } = tier {
+                // Falha de escrita: o que sobrou vai para o arquivo em memória
+                if let Ok(mut archive) = ColdArchive::open(&path) {
+                    while let Some(record) = records.first() {
+                        if archive.append(record).is_err() {
+                            break;
+                        }
+                        records.remove(0);
+                    }
+                }
+            }
+            for record in records {
+                self.archived.insert(record.node.meta().id, record);
+            }
+        }
+        self.record(GraphEvent::Pruned { ids: ids.to_vec() });
+        self.remove_nodes(ids);
+    }
+
+    /// Searches archived memories (in memory and, for the `File` tier, on
+    /// disk) whose text contains `query`.
+    pub fn search_archive(&self, query: &str) -> io::Result<Vec<ArchivedNode>> {
+        let clean = query.trim().to_lowercase();
+        if clean.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut found: Vec<ArchivedNode> = self.archived.values()
+            .filter(|record| record.node.tokens().iter().any(|t| t.contains(&clean)))
+            .cloned()
+            .collect();
+        if let ArchiveTier::File { path } = &self.config.archive_tier {
+            let seen: HashSet<NodeId> = found.iter().map(|r| r.node.meta().id).collect();
+            let on_disk = self.retrieve_from_archive(&ColdArchive::open(path)?, query)?;
+            found.extend(on_disk.into_iter().filter(|r| !seen.contains(&r.node.meta().id)));
+        }
+        found.sort_by_key(|record| record.node.meta().id);
+        Ok(found)
+    }
+
+    /// Brings an archived memory back from whichever tier holds it. Returns
+    /// false if no archive has it.
+    pub fn restore(&mut self, id: NodeId) -> io::Result<bool> {
+        if let Some(record) = self.archived.remove(&id) {
+            self.restore_record(record);
+            return Ok(true);
+        }
+        if let ArchiveTier::File { path } = &self.config.archive_tier {
+            let archive = ColdArchive::open(&path.clone())?;
+            return self.restore_from_archive(&archive, id);
+        }
+        Ok(self.nodes.contains_key(&id))
+    }
+
+    /// Deep recall: lazily scans the archive for nodes whose text contains
+    /// `query` (same matching as search). Nodes already back in the graph are skipped.
+    pub fn retrieve_from_archive(&self, archive: &ColdArchive, query: &str) -> io::Result<Vec<ArchivedNode>> {
+        let clean = query.trim().to_lowercase();
+        if clean.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut found = Vec::new();
+        for record in archive.scan()? {
+            let record = record?;
+            if self.nodes.contains_key(&record.node.meta().id) {
+                continue;
+            }
+            if record.node.tokens().iter().any(|t| t.contains(&clean)) {
+                found.push(record);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Brings an archived node back, reconnecting edges whose other end is
+    /// present. Returns false if the archive has no such node.
+    pub fn restore_from_archive(&mut self, archive: &ColdArchive, id: NodeId) -> io::Result<bool> {
+        if self.nodes.contains_key(&id) {
+            return Ok(true);
+        }
+        let Some(record) = archive.get(id)? else { return Ok(false) };
+        self.restore_record(record);
+        Ok(true)
+    }
+
+    fn restore_record(&mut self, record: ArchivedNode) {
+        let id = record.node.meta().id;
+        self.add_node_internal(record.node);
+        for edge in record.outgoing {
+            if self.nodes.contains_key(&edge.target) {
+                self.insert_edge(id, edge);
+            }
+        }
+        for (source, edge) in record.incoming {
+            if self.nodes.contains_key(&source) {
+                self.insert_edge(source, edge);
+            }
+        }
+        self.enforce_capacity(&[id]);
+    }
+}
@@ -0,0 +1,142 @@
+//! # Async API
+//! `AsyncLoom` (feature `tokio`) wraps a `SharedLoom` for async servers:
+//! every call runs on tokio's blocking pool (`spawn_blocking`) and returns a
+//! future, so saves, consolidation passes, index rebuilds and searches over
+//! big graphs never stall the runtime's worker threads.
+//!
+//! Locking is the same as `SharedLoom`: queries share the read lock,
+//! mutations (including saves, which mark the graph clean) take the write
+//! lock. A panic inside an operation resumes on the awaiting task.
+
+use crate::{ConsolidationConfig, ConsolidationReport, ContextFormat, LoomGraph, Node, NodeId, SharedLoom};
+use std::io;
+
+#[derive(Clone)]
+pub struct AsyncLoom {
+    shared: SharedLoom,
+}
+
+impl From<LoomGraph> for AsyncLoom {
+    fn from(graph: LoomGraph) -> Self {
+        Self::new(graph)
+    }
+}
+
+impl From<SharedLoom> for AsyncLoom {
+    fn from(shared: SharedLoom) -> Self {
+        Self { shared }
+    }
+}
+
+impl AsyncLoom {
+    pub fn new(graph: LoomGraph) -> Self {
+        Self { shared: SharedLoom::new(graph) }
+    }
+
+    /// The underlying handle, for cheap synchronous calls from blocking code.
+    pub fn shared(&self) -> &SharedLoom {
+        &self.shared
+    }
+
+    /// Runs `f` under the read lock on a blocking thread.
+    pub async fn read<T: Send + 'static>(&self, f: impl FnOnce(&LoomGraph) -> T + Send + 'static) -> T {
+        let shared = self.shared.clone();
+        blocking(move || shared.with_read(f)).await
+    }
+
+    /// Runs `f` under the write lock on a blocking thread.
+    pub async fn write<T: Send + 'static>(&self, f: impl FnOnce(&mut LoomGraph) -> T + Send + 'static) -> T {
+        let shared = self.shared.clone();
+        blocking(move || shared.with_write(f)).await
+    }
+
+    // --- Persistência ---
+
+    /// `load_binary` followed by `wake_up`, off the runtime.
+    pub async fn load_binary(path: impl Into<String>) -> io::Result<Self> {
+        let path = path.into();
+        blocking(move || {
+            let mut graph = LoomGraph::load_binary(&path)?;
+            graph.wake_up();
+            Ok(Self::new(graph))
+        }).await
+    }
+
+    pub async fn load_from_file(path: impl Into<String>) -> io::Result<Self> {
+        let path = path.into();
+        blocking(move || LoomGraph::load_from_file(&path).map(Self::new)).await
+    }
+
+    pub async fn save_binary(&self, path: impl Into<String>) -> io::Result<()> {
+        let path = path.into();
+        self.write(move |graph| graph.save_binary(&path)).await
+    }
+
+    pub async fn save_to_file(&self, path: impl Into<String>) -> io::Result<()> {
+        let path = path.into();
+        self.write(move |graph| graph.save_to_file(&path)).await
+    }
+
+    // --- Operações pesadas ---
+
+    pub async fn consolidate(&self, config: ConsolidationConfig) -> ConsolidationReport {
+        self.write(move |graph| graph.consolidate(&config)).await
+    }
+
+    pub async fn rebuild_index(&self) {
+        self.write(LoomGraph::rebuild_index).await
+    }
+
+    pub async fn dream(&self) -> String {
+        self.write(LoomGraph::dream).await
+    }
+
+    /// Advances `ticks` at once; returns how many scheduled events ran.
+    pub async fn fast_forward(&self, ticks: u64) -> usize {
+        self.write(move |graph| graph.fast_forward(ticks)).await
+    }
+
+    pub async fn search(&self, query: impl Into<String>) -> Vec<(NodeId, f32)> {
+        let query = query.into();
+        self.read(move |graph| graph.search_native(&query)).await
+    }
+
+    /// Search that counts hits as accesses (takes the write lock).
+    pub async fn recall(&self, query: impl Into<String>) -> Vec<(NodeId, f32)> {
+        let query = query.into();
+        self.write(move |graph| graph.recall(&query)).await
+    }
+
+    pub async fn get_context_as(&self, format: ContextFormat, min_activation: f32) -> String {
+        self.read(move |graph| graph.get_context_as(format, min_activation)).await
+    }
+
+    // --- Mutações simples ---
+
+    pub async fn add_concept(&self, name: String, definition: String) -> NodeId {
+        self.write(move |graph| graph.add_concept(name, definition)).await
+    }
+
+    pub async fn add_episode(&self, summary: String) -> NodeId {
+        self.write(move |graph| graph.add_episode(summary)).await
+    }
+
+    pub async fn stimulate(&self, id: NodeId, force: f32) -> bool {
+        self.write(move |graph| graph.stimulate(id, force)).await
+    }
+
+    pub async fn tick(&self) {
+        self.write(LoomGraph::tick).await
+    }
+
+    pub async fn get_node(&self, id: NodeId) -> Option<Node> {
+        self.read(move |graph| graph.get_node(id).cloned()).await
+    }
+}
+
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(value) => value,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
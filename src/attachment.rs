@@ -0,0 +1,76 @@
+//! # Attachments
+//! References from episodes to externally stored media (images, audio
+//! clips, documents). LoomDB keeps only the URI, mime type and an optional
+//! content hash; the blob itself lives wherever the URI points.
+
+use crate::{LoomGraph, Node, NodeId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub uri: String,
+    /// e.g. `image/png`, `audio/ogg`.
+    pub mime_type: String,
+    /// Content hash (e.g. `sha256:...`) to detect a changed or missing blob.
+    #[serde(default)]
+    pub hash: Option<String>,
+}
+
+impl Attachment {
+    pub fn new(uri: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        Self { uri: uri.into(), mime_type: mime_type.into(), hash: None }
+    }
+
+    pub fn with_hash(mut self, hash: impl Into<String>) -> Self {
+        self.hash = Some(hash.into());
+        self
+    }
+}
+
+impl LoomGraph {
+    /// Adds `attachment` to an episode (ignored if the same URI is already
+    /// attached). Returns false if `episode` isn't an episode.
+    pub fn attach(&mut self, episode: NodeId, attachment: Attachment) -> bool {
+        let Some(Node::Episode(_, data)) = self.nodes.get_mut(&episode) else { return false };
+        if !data.attachments.iter().any(|a| a.uri == attachment.uri) {
+            data.attachments.push(attachment);
+            self.record_rewrite(episode);
+        }
+        true
+    }
+
+    /// Removes the attachment with `uri`. Returns false if there was none.
+    pub fn detach(&mut self, episode: NodeId, uri: &str) -> bool {
+        let Some(Node::Episode(_, data)) = self.nodes.get_mut(&episode) else { return false };
+        let before = data.attachments.len();
+        data.attachments.retain(|a| a.uri != uri);
+        let removed = data.attachments.len() != before;
+        if removed {
+            self.record_rewrite(episode);
+        }
+        removed
+    }
+
+    pub fn attachments(&self, episode: NodeId) -> &[Attachment] {
+        match self.nodes.get(&episode) {
+            Some(Node::Episode(_, data)) => &data.attachments,
+            _ => &[],
+        }
+    }
+
+    /// Episodes with at least one attachment whose mime type starts with
+    /// `mime_prefix` (`"image/"`, `"audio/ogg"`; `""` matches any), newest first.
+    pub fn episodes_with_attachments(&self, mime_prefix: &str) -> Vec<NodeId> {
+        let mut found: Vec<(NodeId, _)> = self.nodes.iter()
+            .filter(|(id, _)| !self.is_forgotten(**id))
+            .filter_map(|(id, node)| match node {
+                Node::Episode(meta, d) if d.attachments.iter().any(|a| a.mime_type.starts_with(mime_prefix)) => {
+                    Some((*id, (d.timestamp, meta.seq)))
+                }
+                _ => None,
+            })
+            .collect();
+        found.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        found.into_iter().map(|(id, _)| id).collect()
+    }
+}
@@ -0,0 +1,105 @@
+//! # Entity Linking
+//! New episodes get a `Mentioned` edge to every concept or entity whose name
+//! (or concept alias) appears in their text (whole-word, multi-word names matched as phrases),
+//! and optionally give those nodes a small boost. When a concept and an
+//! entity share a name, a capitalized mention ("Apple") links the entity and
+//! a lowercase one ("apple") links the concept.
+
+use crate::{tokenize, Connection, LoomGraph, Node, NodeId};
+
+pub const MENTIONED_EDGE: &str = "Mentioned";
+
+impl LoomGraph {
+    /// Concepts and entities named in `text`, matched on normalized tokens.
+    pub fn mentioned_concepts(&self, text: &str) -> Vec<NodeId> {
+        let words = tokenize(text);
+        if words.is_empty() {
+            return Vec::new();
+        }
+        // Mesma segmentação de `tokenize`, guardando se o token original começa com maiúscula.
+        let proper: Vec<bool> = text.split_whitespace()
+            .map(|token| token.trim_matches(|c: char| !c.is_alphanumeric()))
+            .filter(|clean| !clean.is_empty())
+            .map(|clean| clean.chars().next().is_some_and(char::is_uppercase))
+            .collect();
+
+        // (id, forma, é entidade, casou como nome próprio, casou em minúsculas)
+        let mut matches: Vec<(NodeId, Vec<String>, bool, bool, bool)> = Vec::new();
+        let forms = self.nodes.iter().flat_map(|(id, node)| {
+            let forms: Vec<(Vec<String>, bool)> = match node {
+                Node::Concept(_, data) => std::iter::once(&data.name).chain(&data.aliases)
+                    .map(|form| (tokenize(form), false))
+                    .collect(),
+                Node::Entity(_, data) => vec![(tokenize(&data.name), true)],
+                _ => Vec::new(),
+            };
+            forms.into_iter().map(move |(form, is_entity)| (*id, form, is_entity))
+        });
+        for (id, name, is_entity) in forms {
+            if name.is_empty() || name.len() > words.len() {
+                continue;
+            }
+            let (mut as_proper, mut as_common) = (false, false);
+            for start in 0..=words.len() - name.len() {
+                if words[start..start + name.len()] == name[..] {
+                    if proper.get(start).copied().unwrap_or(false) {
+                        as_proper = true;
+                    } else {
+                        as_common = true;
+                    }
+                }
+            }
+            if as_proper || as_common {
+                matches.push((id, name, is_entity, as_proper, as_common));
+            }
+        }
+
+        let mut found: Vec<NodeId> = matches.iter()
+            .filter(|(_, name, is_entity, as_proper, as_common)| {
+                let rival = matches.iter().any(|(_, other, other_entity, _, _)| other == name && other_entity != is_entity);
+                match (rival, is_entity) {
+                    (false, _) => true,
+                    (true, true) => *as_proper,
+                    (true, false) => *as_common,
+                }
+            })
+            .map(|(id, ..)| *id)
+            .collect();
+        found.sort();
+        found.dedup();
+        found
+    }
+
+    /// `add_episode` that always links mentioned concepts, regardless of `config.auto_link.enabled`.
+    pub fn add_episode_linked(&mut self, summary: String) -> (NodeId, Vec<NodeId>) {
+        let enabled = std::mem::replace(&mut self.config.auto_link.enabled, false);
+        let id = self.add_episode(summary);
+        self.config.auto_link.enabled = enabled;
+        let linked = self.link_mentions(id);
+        (id, linked)
+    }
+
+    /// Creates `Mentioned` edges from `episode` to the concepts and entities its text names.
+    pub(crate) fn link_mentions(&mut self, episode: NodeId) -> Vec<NodeId> {
+        let Some(Node::Episode(_, data)) = self.nodes.get(&episode) else { return Vec::new() };
+        // Sem contaminação entre namespaces: só liga nós do mesmo namespace do episódio
+        let namespace = self.nodes[&episode].meta().namespace.clone();
+        let mentioned: Vec<NodeId> = self.mentioned_concepts(&data.summary).into_iter()
+            .filter(|id| self.nodes[id].meta().namespace == namespace)
+            .collect();
+        let (weight, boost) = (self.config.auto_link.weight, self.config.auto_link.boost);
+
+        for target in &mentioned {
+            self.insert_edge(episode, Connection {
+                target: *target,
+                weight,
+                edge_type: MENTIONED_EDGE.to_string(),
+                inferred: false,
+            });
+            if boost > 0.0 {
+                self.boost_node(*target, boost, 1);
+            }
+        }
+        mentioned
+    }
+}
@@ -0,0 +1,139 @@
+//! # Autosave
+//! Every mutation bumps a dirty counter; an attached `AutosavePolicy` decides
+//! when enough has changed to persist again, bounding how much memory a
+//! long-running agent can lose to a crash.
+//!
+//! Policies are checked after mutations and on `tick`. The save itself goes
+//! through a sink callback, so native code can write a file while WASM hosts
+//! hand the snapshot to IndexedDB or similar.
+
+use crate::LoomGraph;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::sync::Arc;
+
+pub type AutosaveSink = Arc<dyn Fn(&LoomGraph) -> io::Result<()> + Send + Sync>;
+
+/// Seconds from an arbitrary, monotonic origin (host-provided, so it also works in WASM).
+pub type AutosaveClock = Arc<dyn Fn() -> u64 + Send + Sync>;
+
+#[derive(Clone)]
+pub enum AutosavePolicy {
+    /// Save once this many mutations accumulated since the last save.
+    EveryMutations(u64),
+    /// Save (if dirty) once this many ticks passed since the last save.
+    EveryTicks(u64),
+    /// Save (if dirty) once `clock` reports at least `seconds` since the last save.
+    Elapsed { seconds: u64, clock: AutosaveClock },
+}
+
+struct AutosaveState {
+    policy: AutosavePolicy,
+    sink: AutosaveSink,
+    saved_at_tick: u64,
+    saved_at_clock: u64,
+    last_error: Option<io::Error>,
+}
+
+/// Attached autosave. Like the WAL handle, cloning a graph yields a detached
+/// handle so a copy never overwrites the original's snapshot.
+#[derive(Default)]
+pub struct AutosaveHandle(Option<AutosaveState>);
+
+impl Clone for AutosaveHandle {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl AutosaveHandle {
+    pub fn is_attached(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+impl AutosavePolicy {
+    fn clock_now(&self) -> u64 {
+        match self {
+            AutosavePolicy::Elapsed { clock, .. } => clock(),
+            _ => 0,
+        }
+    }
+}
+
+impl LoomGraph {
+    /// Mutations since the last save (or since load).
+    pub fn dirty_count(&self) -> u64 {
+        self.dirty
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty > 0
+    }
+
+    pub(crate) fn mark_clean(&mut self) {
+        self.dirty = 0;
+    }
+
+    /// Persists through `sink` whenever `policy` says so.
+    pub fn autosave(&mut self, policy: AutosavePolicy, sink: impl Fn(&LoomGraph) -> io::Result<()> + Send + Sync + 'static) {
+        self.autosave = AutosaveHandle(Some(AutosaveState {
+            saved_at_tick: self.current_tick,
+            saved_at_clock: policy.clock_now(),
+            policy,
+            sink: Arc::new(sink),
+            last_error: None,
+        }));
+    }
+
+    /// `autosave` writing the same JSON snapshot as `save_to_file`.
+    pub fn autosave_to_file(&mut self, filepath: &str, policy: AutosavePolicy) {
+        let path = filepath.to_string();
+        self.autosave(policy, move |graph| {
+            let writer = BufWriter::new(File::create(&path)?);
+            serde_json::to_writer_pretty(writer, graph)?;
+            Ok(())
+        });
+    }
+
+    pub fn disable_autosave(&mut self) {
+        self.autosave = AutosaveHandle::default();
+    }
+
+    /// Returns (and clears) the last autosave failure, if any. A failed save
+    /// keeps the graph dirty and is retried on the next check.
+    pub fn take_autosave_error(&mut self) -> Option<io::Error> {
+        self.autosave.0.as_mut()?.last_error.take()
+    }
+
+    /// Saves now if the policy is due. Returns true when a save happened.
+    pub fn maybe_autosave(&mut self) -> bool {
+        let Some(mut state) = self.autosave.0.take() else { return false };
+        let now = state.policy.clock_now();
+        let due = self.dirty > 0 && match &state.policy {
+            AutosavePolicy::EveryMutations(n) => self.dirty >= *n,
+            AutosavePolicy::EveryTicks(n) => self.current_tick.saturating_sub(state.saved_at_tick) >= *n,
+            AutosavePolicy::Elapsed { seconds, .. } => now.saturating_sub(state.saved_at_clock) >= *seconds,
+        };
+
+        let mut saved = false;
+        if due {
+            let previous = self.last_saved;
+            self.last_saved = Some(self.now());
+            match (state.sink)(self) {
+                Ok(()) => {
+                    self.mark_clean();
+                    state.saved_at_tick = self.current_tick;
+                    state.saved_at_clock = now;
+                    saved = true;
+                }
+                Err(e) => {
+                    self.last_saved = previous;
+                    state.last_error = Some(e);
+                }
+            }
+        }
+        self.autosave = AutosaveHandle(Some(state));
+        saved
+    }
+}
@@ -0,0 +1,83 @@
+//! # Batch Operations
+//! Bulk ingestion in one call: nodes are tokenized first and merged into the
+//! index in a single pass, and the autosave policy is checked once at the
+//! end instead of after every node. Mainly for crossing the WASM boundary
+//! once per chat history instead of once per message.
+
+use crate::{ConceptData, Connection, EpisodeData, LoomGraph, Node, NodeId, NodeKind, ASSOCIATED_EDGE};
+use chrono::Duration;
+use std::collections::HashMap;
+
+impl LoomGraph {
+    /// Adds episodes in order. Timestamps increase by 1µs per item so the
+    /// batch keeps its order under time-based sorting.
+    pub fn add_episodes_batch(&mut self, summaries: Vec<String>) -> Vec<NodeId> {
+        let now = self.now();
+        let nodes = summaries.into_iter().enumerate()
+            .map(|(i, summary)| Node::Episode(self.episode_meta(), EpisodeData {
+                summary,
+                timestamp: now + Duration::microseconds(i as i64),
+                confidence: None,
+                attachments: Vec::new(),
+            }))
+            .collect();
+        let ids = self.add_nodes_batch(nodes);
+        self.chain_session(&ids);
+        if self.config.auto_link.enabled {
+            for id in &ids {
+                self.link_mentions(*id);
+            }
+        }
+        ids
+    }
+
+    /// Adds `(name, definition)` pairs.
+    pub fn add_concepts_batch(&mut self, concepts: Vec<(String, String)>) -> Vec<NodeId> {
+        let nodes = concepts.into_iter()
+            .map(|(name, definition)| Node::Concept(self.new_meta(NodeKind::Concept), ConceptData { name, definition, aliases: Vec::new(), confidence: None }))
+            .collect();
+        self.add_nodes_batch(nodes)
+    }
+
+    /// Creates "Associated" edges for every `(source, target, weight)` whose
+    /// endpoints exist. Returns how many edges were created.
+    pub fn connect_batch(&mut self, edges: &[(NodeId, NodeId, f32)]) -> usize {
+        let mut created = 0;
+        for (source, target, weight) in edges {
+            if self.nodes.contains_key(source) && self.nodes.contains_key(target) {
+                self.store_edge(*source, Connection {
+                    target: *target,
+                    weight: *weight,
+                    edge_type: ASSOCIATED_EDGE.to_string(),
+                    inferred: false,
+                });
+                created += 1;
+            }
+        }
+        self.maybe_autosave();
+        created
+    }
+
+    pub(crate) fn add_nodes_batch(&mut self, nodes: Vec<Node>) -> Vec<NodeId> {
+        // Uma única passada no índice: agrupa os tokens do lote antes de mesclar
+        let mut tokens: HashMap<String, Vec<NodeId>> = HashMap::new();
+        for node in &nodes {
+            let id = node.meta().id;
+            for token in node.tokens() {
+                tokens.entry(token).or_default().push(id);
+            }
+        }
+        for (token, ids) in tokens {
+            self.index.entry(token).or_default().extend(ids);
+        }
+
+        self.nodes.reserve(nodes.len());
+        let ids: Vec<NodeId> = nodes.iter().map(|n| n.meta().id).collect();
+        for node in nodes {
+            self.store_node(node);
+        }
+        self.enforce_capacity(&ids);
+        self.maybe_autosave();
+        ids
+    }
+}
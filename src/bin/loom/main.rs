@@ -0,0 +1,312 @@
+mod repl;
+
+use loom_db::{ContextFormat, DotOptions, LoomGraph, NodeKind};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+loom: command-line access to a LoomDB brain file
+
+Usage: loom [--brain PATH] <command> [args]
+
+Commands:
+  init [--decay RATE] [--force]       Create a new, empty brain
+  add-concept <name> <definition>     Add a concept, print its id
+  add-episode <summary> [--at TIME]   Add an episode (TIME in RFC 3339), print its id
+  search <query> [--limit N]          Matching memories, most active first
+  context [--min A] [--format F]      Prompt context (F: xml, json, markdown, openai)
+  stats                               Node/edge counts and health score
+  prune [--threshold S]               Delete memories below stability S (default 0.2)
+  tick [N]                            Advance the clock N ticks (default 1)
+  export [--format F] [--out FILE]    Dump the graph (F: dot, graphml, gexf, vis, jsonl, json, binary)
+         [--min A]                    DOT only: skip memories below activation A
+  import <file> --format F            Load records (F: jsonl, concepts-csv, episodes-csv,
+                                      vault with the `obsidian` feature)
+  repl                                Interactive explorer (search, inspect, stimulate, tick)
+  serve-mcp                           MCP server on stdio (with the `mcp` feature)
+  serve [--addr ADDR]                 REST API (with the `server` feature, default 127.0.0.1:7700)
+
+The brain defaults to ./brain.loom (or $LOOM_BRAIN). Paths ending in
+.json are read and written as JSON, anything else as binary.";
+
+/// Erro de uso ou de IO, já formatado para o usuário
+type CliResult = Result<(), String>;
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let brain_path = take_option(&mut args, "--brain")
+        .or_else(|| std::env::var("LOOM_BRAIN").ok())
+        .unwrap_or_else(|| "brain.loom".to_string());
+
+    if args.is_empty() || args[0] == "help" || args[0] == "--help" || args[0] == "-h" {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let command = args.remove(0);
+    match run(&command, args, &brain_path) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("loom: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: &str, mut args: Vec<String>, path: &str) -> CliResult {
+    match command {
+        "init" => {
+            let force = take_flag(&mut args, "--force");
+            let decay = parse_option(&mut args, "--decay", 0.9f32)?;
+            no_extra(&args)?;
+            if Path::new(path).exists() && !force {
+                return Err(format!("{} already exists (use --force to overwrite)", path));
+            }
+            let mut brain = LoomGraph::new(decay);
+            save(&mut brain, path)?;
+            println!("Created {}", path);
+            Ok(())
+        }
+        "add-concept" => {
+            let [name, definition] = positional::<2>(args, "add-concept <name> <definition>")?;
+            let mut brain = load(path)?;
+            let id = brain.add_concept(name, definition);
+            save(&mut brain, path)?;
+            println!("{}", id);
+            Ok(())
+        }
+        "add-episode" => {
+            let at = take_option(&mut args, "--at");
+            let [summary] = positional::<1>(args, "add-episode <summary> [--at TIME]")?;
+            let mut brain = load(path)?;
+            let id = match at {
+                Some(time) => brain.add_episode_at(summary, &time).map_err(|e| e.to_string())?,
+                None => brain.add_episode(summary),
+            };
+            save(&mut brain, path)?;
+            println!("{}", id);
+            Ok(())
+        }
+        "search" => {
+            let limit = parse_option(&mut args, "--limit", 10usize)?;
+            let query = args.join(" ");
+            if query.trim().is_empty() {
+                return Err("usage: search <query> [--limit N]".to_string());
+            }
+            let brain = load(path)?;
+            for (id, activation) in brain.search_native(&query).into_iter().take(limit) {
+                if let Some(node) = brain.get_node(id) {
+                    println!("{:.3}  {}  {:?}  {}", activation, id, node.kind(), node.label());
+                }
+            }
+            Ok(())
+        }
+        "context" => {
+            let min = parse_option(&mut args, "--min", 0.3f32)?;
+            let format = match take_option(&mut args, "--format").as_deref() {
+                None | Some("xml") => ContextFormat::Xml,
+                Some("json") => ContextFormat::Json,
+                Some("markdown") | Some("md") => ContextFormat::Markdown,
+                Some("openai") => ContextFormat::OpenAiMessages,
+                Some(other) => return Err(format!("unknown context format '{}'", other)),
+            };
+            no_extra(&args)?;
+            println!("{}", load(path)?.get_context_as(format, min));
+            Ok(())
+        }
+        "stats" => {
+            no_extra(&args)?;
+            let brain = load(path)?;
+            let mut kinds: BTreeMap<String, usize> = BTreeMap::new();
+            for node in brain.nodes.values() {
+                *kinds.entry(kind_name(node.kind()).to_string()).or_default() += 1;
+            }
+            let health = brain.health_report();
+            println!("tick:   {}", brain.current_tick);
+            println!("nodes:  {}", health.node_count);
+            for (kind, count) in &kinds {
+                println!("  {:<12}{}", kind, count);
+            }
+            println!("edges:  {}", health.edge_count);
+            println!("health: {:.0}/100", health.score);
+            for recommendation in &health.recommendations {
+                println!("  - {}", recommendation.describe());
+            }
+            Ok(())
+        }
+        "prune" => {
+            let threshold = parse_option(&mut args, "--threshold", 0.2f32)?;
+            no_extra(&args)?;
+            let mut brain = load(path)?;
+            let removed = brain.prune_low_stability(threshold);
+            save(&mut brain, path)?;
+            println!("Pruned {} memories", removed);
+            Ok(())
+        }
+        "tick" => {
+            let ticks = match args.pop() {
+                Some(n) => n.parse::<u64>().map_err(|_| format!("invalid tick count '{}'", n))?,
+                None => 1,
+            };
+            no_extra(&args)?;
+            let mut brain = load(path)?;
+            brain.fast_forward(ticks);
+            save(&mut brain, path)?;
+            println!("tick {}", brain.current_tick);
+            Ok(())
+        }
+        "export" => {
+            let format = take_option(&mut args, "--format").unwrap_or_else(|| "dot".to_string());
+            let out = take_option(&mut args, "--out");
+            let dot = DotOptions { min_activation: parse_option(&mut args, "--min", 0.0f32)?, ..DotOptions::default() };
+            no_extra(&args)?;
+            let brain = load(path)?;
+            let bytes = match format.as_str() {
+                "dot" => brain.export_dot(&dot).into_bytes(),
+                "graphml" => brain.export_graphml().into_bytes(),
+                "gexf" => brain.export_gexf().into_bytes(),
+                "vis" => brain.export_vis_json().into_bytes(),
+                "jsonl" => {
+                    let mut lines = Vec::new();
+                    brain.export_nodes_jsonl(&mut lines).map_err(|e| e.to_string())?;
+                    lines
+                }
+                "json" => brain.export_backup().into_bytes(),
+                "binary" => brain.to_binary().map_err(|e| e.to_string())?,
+                other => return Err(format!("unknown export format '{}'", other)),
+            };
+            match out {
+                Some(file) => std::fs::write(&file, bytes).map_err(|e| format!("{}: {}", file, e)),
+                None if format == "binary" => Err("binary export needs --out FILE".to_string()),
+                None => {
+                    print!("{}", String::from_utf8_lossy(&bytes));
+                    Ok(())
+                }
+            }
+        }
+        "import" => {
+            let format = take_option(&mut args, "--format").ok_or("usage: import <file> --format F")?;
+            let [file] = positional::<1>(args, "import <file> --format F")?;
+            #[cfg(feature = "obsidian")]
+            if format == "vault" {
+                let mut brain = load(path)?;
+                let report = brain.import_markdown_vault(&file).map_err(|e| format!("{}: {}", file, e))?;
+                save(&mut brain, path)?;
+                println!("Imported {} notes, {} links ({} unresolved), {} dated episodes",
+                    report.notes.len(), report.links, report.unresolved_links, report.episodes);
+                return Ok(());
+            }
+            let reader = std::fs::File::open(&file).map_err(|e| format!("{}: {}", file, e))?;
+            let mut brain = load(path)?;
+            let imported = match format.as_str() {
+                "jsonl" => brain.import_nodes_jsonl(std::io::BufReader::new(reader)).map(|report| report.inserted),
+                "concepts-csv" => brain.import_concepts_csv(reader).map(|ids| ids.len()),
+                "episodes-csv" => brain.import_episodes_csv(reader).map(|ids| ids.len()),
+                other => return Err(format!("unknown import format '{}'", other)),
+            }
+            .map_err(|e| format!("{}: {}", file, e))?;
+            save(&mut brain, path)?;
+            println!("Imported {} nodes", imported);
+            Ok(())
+        }
+        "repl" => {
+            no_extra(&args)?;
+            repl::run(load(path)?, path)
+        }
+        #[cfg(feature = "mcp")]
+        "serve-mcp" => {
+            no_extra(&args)?;
+            let mut server = loom_db::McpServer::new(load(path)?);
+            let stdin = std::io::stdin();
+            // Salva após cada mensagem que alterou o cérebro
+            let persist = |brain: &mut LoomGraph| save(brain, path).map_err(std::io::Error::other);
+            server.serve(stdin.lock(), std::io::stdout(), persist).map_err(|e| e.to_string())
+        }
+        #[cfg(feature = "server")]
+        "serve" => {
+            let addr = take_option(&mut args, "--addr").unwrap_or_else(|| "127.0.0.1:7700".to_string());
+            no_extra(&args)?;
+            let mut brain = load(path)?;
+            let file = path.to_string();
+            // Cada mutação vai direto para o arquivo
+            brain.autosave(loom_db::AutosavePolicy::EveryMutations(1), move |graph| {
+                let bytes = if is_json(&file) { serde_json::to_vec_pretty(graph)? } else { graph.to_binary()? };
+                std::fs::write(&file, bytes)
+            });
+            let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+            eprintln!("Serving {} on http://{}", path, addr);
+            runtime.block_on(loom_db::serve_http(brain.into(), addr)).map_err(|e| e.to_string())
+        }
+        other => Err(format!("unknown command '{}' (see `loom help`)", other)),
+    }
+}
+
+fn load(path: &str) -> Result<LoomGraph, String> {
+    if !Path::new(path).exists() {
+        return Err(format!("{} not found (run `loom init` first)", path));
+    }
+    let mut brain = if is_json(path) { LoomGraph::load_from_file(path) } else { LoomGraph::load_binary(path) }
+        .map_err(|e| format!("{}: {}", path, e))?;
+    // Recupera o tempo que passou desde o último save
+    brain.wake_up();
+    Ok(brain)
+}
+
+fn save(brain: &mut LoomGraph, path: &str) -> CliResult {
+    if is_json(path) { brain.save_to_file(path) } else { brain.save_binary(path) }
+        .map_err(|e| format!("{}: {}", path, e))
+}
+
+fn is_json(path: &str) -> bool {
+    path.ends_with(".json")
+}
+
+fn kind_name(kind: NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Episode => "episode",
+        NodeKind::Concept => "concept",
+        NodeKind::State => "state",
+        NodeKind::Observation => "observation",
+        NodeKind::Goal => "goal",
+        NodeKind::Skill => "skill",
+        NodeKind::Entity => "entity",
+        NodeKind::Custom => "custom",
+    }
+}
+
+/// Remove `--flag` dos argumentos; diz se estava presente
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|a| a != flag);
+    args.len() != before
+}
+
+/// Remove `--name VALUE` dos argumentos e devolve VALUE
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let at = args.iter().position(|a| a == name)?;
+    if at + 1 >= args.len() {
+        args.remove(at);
+        return None;
+    }
+    let value = args.remove(at + 1);
+    args.remove(at);
+    Some(value)
+}
+
+fn parse_option<T: std::str::FromStr>(args: &mut Vec<String>, name: &str, default: T) -> Result<T, String> {
+    match take_option(args, name) {
+        Some(raw) => raw.parse().map_err(|_| format!("invalid value '{}' for {}", raw, name)),
+        None => Ok(default),
+    }
+}
+
+fn positional<const N: usize>(args: Vec<String>, usage: &str) -> Result<[String; N], String> {
+    args.try_into().map_err(|_| format!("usage: {}", usage))
+}
+
+fn no_extra(args: &[String]) -> CliResult {
+    match args.first() {
+        Some(extra) => Err(format!("unexpected argument '{}'", extra)),
+        None => Ok(()),
+    }
+}
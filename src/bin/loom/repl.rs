@@ -0,0 +1,285 @@
+//! Interactive explorer (`loom repl`): search, inspect, walk edges,
+//! stimulate and tick a brain while watching activations change. Handy for
+//! finding out why an agent "forgot" something.
+
+use crate::{kind_name, save};
+use loom_db::{ContextFormat, LoomGraph, NodeId};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+  search <query>          Matching memories (results become #1, #2, ...)
+  top [N]                 Most active memories
+  show <ref>              Node details, decay forecast and why it may be fading
+  neighbors <ref>         Outgoing and incoming edges
+  stimulate <ref> [F]     Boost a node (force F, default 0.5) and show the ripple
+  tick [N]                Advance the clock and show what faded
+  pin <ref> / unpin <ref> Keep a node above config.pin_floor and safe from pruning
+  watch <ref>             Print this node's activation after every command
+  unwatch <ref>
+  context [MIN]           Prompt context (XML) above activation MIN
+  save                    Write the brain back to its file
+  quit                    Leave (asks to confirm if there are unsaved changes)
+<ref> is an id, a unique id prefix, or #N from the last listing.";
+
+/// Mudanças menores que isso não aparecem nos relatórios
+const CHANGE_EPSILON: f32 = 0.005;
+/// Quantas mudanças de ativação listar depois de `stimulate`/`tick`
+const MAX_CHANGES_SHOWN: usize = 10;
+
+struct Session {
+    brain: LoomGraph,
+    path: String,
+    /// Resultado da última listagem, para referências `#N`
+    last: Vec<NodeId>,
+    watched: Vec<NodeId>,
+    confirm_quit: bool,
+}
+
+pub fn run(brain: LoomGraph, path: &str) -> Result<(), String> {
+    let mut session = Session { brain, path: path.to_string(), last: Vec::new(), watched: Vec::new(), confirm_quit: false };
+    println!("loom repl on {} (tick {}, {} nodes). Type `help`.", path, session.brain.current_tick, session.brain.nodes.len());
+
+    let stdin = io::stdin();
+    let mut line = String::new();
+    loop {
+        print!("loom> ");
+        io::stdout().flush().map_err(|e| e.to_string())?;
+        line.clear();
+        if stdin.lock().read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+            println!();
+            return Ok(()); // EOF
+        }
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else { continue };
+        let args: Vec<&str> = words.collect();
+        match session.execute(command, &args) {
+            Ok(true) => return Ok(()),
+            Ok(false) => session.print_watched(),
+            Err(message) => println!("error: {}", message),
+        }
+    }
+}
+
+impl Session {
+    /// Returns true when the session should end.
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<bool, String> {
+        if command != "quit" && command != "exit" {
+            self.confirm_quit = false;
+        }
+        match command {
+            "help" | "?" => println!("{}", HELP),
+            "search" => {
+                let query = args.join(" ");
+                let hits: Vec<(NodeId, f32)> = self.brain.search_native(&query).into_iter().take(20).collect();
+                self.list(hits);
+            }
+            "top" => {
+                let limit = parse_arg(args.first(), 10usize)?;
+                let tick = self.brain.current_tick;
+                let mut nodes: Vec<(NodeId, f32)> = self.brain.nodes.iter()
+                    .map(|(id, node)| (*id, self.brain.projected_activation(node.meta(), tick)))
+                    .collect();
+                nodes.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+                nodes.truncate(limit);
+                self.list(nodes);
+            }
+            "show" => {
+                let id = self.resolve(args.first())?;
+                self.show(id);
+            }
+            "neighbors" | "edges" => {
+                let id = self.resolve(args.first())?;
+                let mut listed = Vec::new();
+                println!("outgoing:");
+                for (target, kind, weight) in self.brain.neighbors(id) {
+                    listed.push(target);
+                    println!("  #{:<3} -{}({:.2})-> {}", listed.len(), kind, weight, self.describe(target));
+                }
+                println!("incoming:");
+                for (source, kind, weight) in self.brain.incoming(id) {
+                    listed.push(source);
+                    println!("  #{:<3} <-{}({:.2})- {}", listed.len(), kind, weight, self.describe(source));
+                }
+                if !listed.is_empty() {
+                    self.last = listed;
+                }
+            }
+            "stimulate" | "boost" => {
+                let id = self.resolve(args.first())?;
+                let force = parse_arg(args.get(1), 0.5f32)?;
+                let before = self.snapshot();
+                let report = self.brain.stimulate_with_report(id, force).ok_or("node not found")?;
+                println!("touched {} nodes{}", report.touched, if report.truncated { " (ripple truncated)" } else { "" });
+                self.print_changes(&before);
+            }
+            "tick" => {
+                let ticks = parse_arg(args.first(), 1u64)?;
+                let before = self.snapshot();
+                self.brain.fast_forward(ticks);
+                println!("tick {}", self.brain.current_tick);
+                self.print_changes(&before);
+            }
+            "pin" => {
+                let id = self.resolve(args.first())?;
+                self.brain.pin(id);
+                println!("pinned {}", self.describe(id));
+            }
+            "unpin" => {
+                let id = self.resolve(args.first())?;
+                self.brain.unpin(id);
+                println!("unpinned {}", self.describe(id));
+            }
+            "watch" => {
+                let id = self.resolve(args.first())?;
+                if !self.watched.contains(&id) {
+                    self.watched.push(id);
+                }
+            }
+            "unwatch" => {
+                let id = self.resolve(args.first())?;
+                self.watched.retain(|w| *w != id);
+            }
+            "context" => {
+                let min = parse_arg(args.first(), 0.3f32)?;
+                println!("{}", self.brain.get_context_as(ContextFormat::Xml, min));
+            }
+            "save" => {
+                save(&mut self.brain, &self.path)?;
+                println!("saved {}", self.path);
+            }
+            "quit" | "exit" => {
+                if self.brain.is_dirty() && !self.confirm_quit {
+                    self.confirm_quit = true;
+                    println!("unsaved changes: `save` first, or `quit` again to discard them");
+                    return Ok(false);
+                }
+                return Ok(true);
+            }
+            other => return Err(format!("unknown command '{}' (try `help`)", other)),
+        }
+        Ok(false)
+    }
+
+    fn list(&mut self, hits: Vec<(NodeId, f32)>) {
+        if hits.is_empty() {
+            println!("(nothing)");
+        }
+        for (n, (id, activation)) in hits.iter().enumerate() {
+            println!("  #{:<3} {:.3}  {}", n + 1, activation, self.describe(*id));
+        }
+        self.last = hits.into_iter().map(|(id, _)| id).collect();
+    }
+
+    fn show(&self, id: NodeId) {
+        let Some(node) = self.brain.get_node(id) else { return };
+        let meta = node.meta();
+        let tick = self.brain.current_tick;
+        let activation = self.brain.projected_activation(meta, tick);
+        println!("{}  {}", kind_name(node.kind()), id);
+        println!("  text:        {}", node.extract_text());
+        println!("  activation:  {:.3} (stored {:.3} at tick {}, now tick {})", activation, meta.activation, meta.last_tick, tick);
+        println!("  stability:   {:.3}", meta.stability);
+        println!("  accessed:    {} times", meta.access_count);
+        if let Some(rate) = meta.decay_override {
+            println!("  decay:       {:?}", rate);
+        }
+        if let Some(floor) = meta.pin_floor {
+            println!("  pinned:      never below {:.2}, never pruned", floor);
+        }
+        if meta.always_in_context {
+            println!("  in context:  always");
+        }
+        if let Some(namespace) = &meta.namespace {
+            println!("  namespace:   {}", namespace);
+        }
+        if let Some(source) = &meta.source {
+            println!("  source:      {} {}", source.origin, source.reference);
+        }
+        for (key, value) in &meta.tags {
+            println!("  tag:         {}={}", key, value);
+        }
+        println!("  edges:       {} out, {} in", self.brain.out_degree(id), self.brain.in_degree(id));
+        let forecast: Vec<String> = self.brain.forecast(id, 5).iter().map(|a| format!("{:.3}", a)).collect();
+        println!("  next ticks:  {}", forecast.join(" "));
+
+        // Pistas do porquê a memória está sumindo
+        if self.brain.is_forgotten(id) {
+            println!("  ! forgotten (tombstoned); it is hidden from search and context");
+        }
+        if activation < 0.05 {
+            println!("  ! activation has decayed below 0.05; it won't make most contexts");
+        }
+        if self.brain.out_degree(id) + self.brain.in_degree(id) == 0 {
+            println!("  ! no edges: nothing else can reactivate it through ripples");
+        }
+    }
+
+    /// Resolves an id, a unique id prefix or `#N` from the last listing.
+    fn resolve(&self, reference: Option<&&str>) -> Result<NodeId, String> {
+        let reference = reference.ok_or("missing node reference")?;
+        if let Some(n) = reference.strip_prefix('#') {
+            let n: usize = n.parse().map_err(|_| format!("invalid reference '{}'", reference))?;
+            return n.checked_sub(1).and_then(|i| self.last.get(i)).copied()
+                .ok_or_else(|| format!("no #{} in the last listing", n));
+        }
+        let matches: Vec<NodeId> = self.brain.nodes.keys()
+            .filter(|id| id.to_string().starts_with(&reference.to_lowercase()))
+            .copied()
+            .collect();
+        match matches.as_slice() {
+            [id] => Ok(*id),
+            [] => Err(format!("no node matches '{}'", reference)),
+            _ => Err(format!("'{}' matches {} nodes; use a longer prefix", reference, matches.len())),
+        }
+    }
+
+    fn describe(&self, id: NodeId) -> String {
+        match self.brain.get_node(id) {
+            Some(node) => format!("{} [{}] {}", short_id(id), kind_name(node.kind()), node.label()),
+            None => format!("{} (missing)", short_id(id)),
+        }
+    }
+
+    fn snapshot(&self) -> HashMap<NodeId, f32> {
+        let tick = self.brain.current_tick;
+        self.brain.nodes.iter()
+            .map(|(id, node)| (*id, self.brain.projected_activation(node.meta(), tick)))
+            .collect()
+    }
+
+    fn print_changes(&self, before: &HashMap<NodeId, f32>) {
+        let mut changes: Vec<(NodeId, f32, f32)> = self.snapshot().into_iter()
+            .filter_map(|(id, now)| {
+                let was = before.get(&id).copied().unwrap_or(0.0);
+                ((now - was).abs() >= CHANGE_EPSILON).then_some((id, was, now))
+            })
+            .collect();
+        changes.sort_by(|a, b| (b.2 - b.1).abs().total_cmp(&(a.2 - a.1).abs()).then(a.0.cmp(&b.0)));
+        for (id, was, now) in changes.iter().take(MAX_CHANGES_SHOWN) {
+            println!("  {:.3} -> {:.3}  {}", was, now, self.describe(*id));
+        }
+        if changes.len() > MAX_CHANGES_SHOWN {
+            println!("  ... and {} more", changes.len() - MAX_CHANGES_SHOWN);
+        }
+    }
+
+    fn print_watched(&self) {
+        for id in &self.watched {
+            if let Some(activation) = self.brain.peek_activation(*id) {
+                println!("  [watch] {:.3}  {}", activation, self.describe(*id));
+            }
+        }
+    }
+}
+
+fn short_id(id: NodeId) -> String {
+    id.to_string()[..8].to_string()
+}
+
+fn parse_arg<T: std::str::FromStr>(arg: Option<&&str>, default: T) -> Result<T, String> {
+    match arg {
+        Some(raw) => raw.parse().map_err(|_| format!("invalid argument '{}'", raw)),
+        None => Ok(default),
+    }
+}
@@ -0,0 +1,182 @@
+//! # Bulk Import & Export
+//! Moves whole knowledge bases in and out without custom code:
+//!
+//! - **JSONL**: one `{"node": ..., "edges": [...]}` record per line, in the
+//!   same node format as backups. Round-trips ids, metadata and outgoing edges.
+//! - **CSV**: concepts as `name,definition` and episodes as
+//!   `timestamp,summary` (RFC 3339). A header row with those names is
+//!   optional; fields may be quoted (`"a, b"`, `""` for a literal quote).
+//!
+//! Imports validate every line before touching the graph, so a bad line
+//! leaves it unchanged. Errors are `InvalidData` naming the line.
+
+use crate::{parse_timestamp, Connection, GraftReport, LoomGraph, Node, NodeId, TimeError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{self, BufRead, Read, Write};
+
+#[derive(Serialize, Deserialize)]
+struct NodeRecord {
+    node: Node,
+    #[serde(default)]
+    edges: Vec<Connection>,
+}
+
+fn line_error(line: usize, message: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", line, message))
+}
+
+impl LoomGraph {
+    /// Writes every live node with its outgoing edges, one JSON record per
+    /// line, ordered by id. Returns how many nodes were written.
+    pub fn export_nodes_jsonl(&self, mut writer: impl Write) -> io::Result<usize> {
+        let mut ids: Vec<NodeId> = self.nodes.keys().copied().filter(|id| !self.is_forgotten(*id)).collect();
+        ids.sort();
+        for id in &ids {
+            let record = NodeRecord {
+                node: self.nodes[id].clone(),
+                edges: self.adjacency.get(id).cloned().unwrap_or_default(),
+            };
+            serde_json::to_writer(&mut writer, &record)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(ids.len())
+    }
+
+    /// Reads records written by `export_nodes_jsonl`. Nodes whose id already
+    /// exists are skipped (listed in `collisions`); edges are added once all
+    /// nodes are in, when their target exists.
+    pub fn import_nodes_jsonl(&mut self, reader: impl BufRead) -> io::Result<GraftReport> {
+        let mut records = Vec::new();
+        for (n, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: NodeRecord = serde_json::from_str(&line).map_err(|e| line_error(n + 1, e))?;
+            records.push(record);
+        }
+
+        let mut report = GraftReport::default();
+        let mut seen = HashSet::new();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        for record in records {
+            let id = record.node.meta().id;
+            if self.nodes.contains_key(&id) || !seen.insert(id) {
+                report.collisions.push(id);
+                continue;
+            }
+            report.id_map.insert(id, id);
+            nodes.push(record.node);
+            edges.extend(record.edges.into_iter().map(|edge| (id, edge)));
+        }
+        report.inserted = nodes.len();
+        self.add_nodes_batch(nodes);
+
+        for (source, edge) in edges {
+            if self.nodes.contains_key(&edge.target) {
+                self.store_edge(source, edge);
+                report.edges_inserted += 1;
+            }
+        }
+        self.maybe_autosave();
+        Ok(report)
+    }
+
+    /// Imports `name,definition` rows as concepts. Returns their ids in row order.
+    pub fn import_concepts_csv(&mut self, reader: impl Read) -> io::Result<Vec<NodeId>> {
+        let rows = read_csv(reader, &["name", "definition"])?;
+        let mut concepts = Vec::with_capacity(rows.len());
+        for (line, mut fields) in rows {
+            if fields[0].trim().is_empty() {
+                return Err(line_error(line, "empty concept name"));
+            }
+            let definition = fields.pop().unwrap_or_default();
+            let name = fields.pop().unwrap_or_default();
+            concepts.push((name, definition));
+        }
+        Ok(self.add_concepts_batch(concepts))
+    }
+
+    /// Imports `timestamp,summary` rows as episodes (RFC 3339 timestamps).
+    /// Returns their ids in row order.
+    pub fn import_episodes_csv(&mut self, reader: impl Read) -> io::Result<Vec<NodeId>> {
+        let rows = read_csv(reader, &["timestamp", "summary"])?;
+        let mut episodes = Vec::with_capacity(rows.len());
+        for (line, mut fields) in rows {
+            let summary = fields.pop().unwrap_or_default();
+            let timestamp = parse_timestamp(&fields[0]).map_err(|e| line_error(line, e))?;
+            let tolerance_secs = self.config.future_tolerance_secs;
+            if timestamp > self.now() + chrono::Duration::seconds(tolerance_secs as i64) {
+                return Err(line_error(line, TimeError::InFuture { timestamp, tolerance_secs }));
+            }
+            episodes.push((summary, timestamp));
+        }
+        let mut ids = Vec::with_capacity(episodes.len());
+        for (summary, timestamp) in episodes {
+            ids.push(self.add_episode_with_time(summary, timestamp).map_err(io::Error::other)?);
+        }
+        Ok(ids)
+    }
+}
+
+/// Parses CSV into `(line number, fields)` rows with exactly `header.len()`
+/// fields, skipping blank lines and a leading header row.
+fn read_csv(mut reader: impl Read, header: &[&str]) -> io::Result<Vec<(usize, Vec<String>)>> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+
+    let mut rows = Vec::new();
+    let (mut fields, mut field) = (Vec::new(), String::new());
+    let (mut line, mut row_line) = (1, 1);
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                fields.push(std::mem::take(&mut field));
+                rows.push((row_line, std::mem::take(&mut fields)));
+                line += 1;
+                row_line = line;
+            }
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if quoted {
+        return Err(line_error(row_line, "unterminated quoted field"));
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        rows.push((row_line, fields));
+    }
+
+    // Linhas em branco e cabeçalho opcional
+    rows.retain(|(_, fields)| !(fields.len() == 1 && fields[0].trim().is_empty()));
+    let is_header = |fields: &[String]| {
+        fields.len() == header.len() && fields.iter().zip(header).all(|(f, h)| f.trim().eq_ignore_ascii_case(h))
+    };
+    if rows.first().is_some_and(|(_, fields)| is_header(fields)) {
+        rows.remove(0);
+    }
+    for (line, fields) in &rows {
+        if fields.len() != header.len() {
+            return Err(line_error(*line, format!("expected {} fields ({}), found {}", header.len(), header.join(","), fields.len())));
+        }
+    }
+    Ok(rows)
+}
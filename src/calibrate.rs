@@ -0,0 +1,79 @@
+//! # Stability Recalibration
+//! Imported graphs usually arrive with a flat `stability = 1.0`, which makes
+//! every memory decay at the same pace and the whole brain fade together.
+//! `recalibrate_stability` derives stability from structure (degree) or age
+//! so a migrated graph behaves sensibly from the first tick.
+
+use crate::{LoomGraph, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StabilityPolicy {
+    /// Well-connected nodes are hubs: `base + per_edge * (in + out degree)`.
+    Degree { base: f32, per_edge: f32 },
+    /// Old memories that survived are consolidated: `base + per_day * age_days`.
+    /// Age is measured against the newest timestamp in the graph; untimed
+    /// nodes (concepts, states) get `base`.
+    Age { base: f32, per_day: f32 },
+    /// Sum of the degree and age bonuses over a shared `base`.
+    Combined { base: f32, per_edge: f32, per_day: f32 },
+}
+
+/// Stability bounds applied after every policy.
+const MIN_STABILITY: f32 = 0.1;
+const MAX_STABILITY: f32 = 50.0;
+
+impl LoomGraph {
+    /// Rewrites the stability of every node according to `policy`.
+    /// Current activations are settled first so past decay is not re-applied
+    /// with the new stability. Returns how many nodes changed.
+    pub fn recalibrate_stability(&mut self, policy: StabilityPolicy) -> usize {
+        let degree = self.degrees();
+        let newest = self.nodes.values().filter_map(|n| n.timestamp()).max();
+        let tick = self.current_tick;
+
+        let targets: Vec<(NodeId, f32, f32)> = self.nodes.iter()
+            .map(|(id, node)| {
+                let edges = degree.get(id).copied().unwrap_or(0) as f32;
+                let age_days = match (newest, node.timestamp()) {
+                    (Some(newest), Some(ts)) => (newest - ts).num_seconds().max(0) as f32 / 86_400.0,
+                    _ => 0.0,
+                };
+                let stability = match policy {
+                    StabilityPolicy::Degree { base, per_edge } => base + per_edge * edges,
+                    StabilityPolicy::Age { base, per_day } => base + per_day * age_days,
+                    StabilityPolicy::Combined { base, per_edge, per_day } => base + per_edge * edges + per_day * age_days,
+                };
+                (*id, stability.clamp(MIN_STABILITY, MAX_STABILITY), self.projected_activation(node.meta(), tick))
+            })
+            .collect();
+
+        let mut changed = 0;
+        for (id, stability, settled) in targets {
+            if let Some(node) = self.nodes.get_mut(&id) {
+                let meta = node.meta_mut();
+                if (meta.stability - stability).abs() > f32::EPSILON {
+                    changed += 1;
+                }
+                meta.activation = settled;
+                meta.last_tick = tick;
+                meta.stability = stability;
+            }
+        }
+        self.dirty += changed as u64;
+        changed
+    }
+
+    /// In + out degree per node.
+    fn degrees(&self) -> HashMap<NodeId, usize> {
+        let mut degree: HashMap<NodeId, usize> = HashMap::new();
+        for (source, edges) in &self.adjacency {
+            *degree.entry(*source).or_default() += edges.len();
+            for edge in edges {
+                *degree.entry(edge.target).or_default() += 1;
+            }
+        }
+        degree
+    }
+}
@@ -0,0 +1,94 @@
+//! # Capacity Bounds
+//! Caps the number of live nodes so embedded and browser deployments can't
+//! grow without limit. Whenever an insertion pushes the graph past
+//! `config.capacity.max_nodes`, the configured `EvictionPolicy` picks victims
+//! until it fits again. Nodes pinned (with `pin` or `always_in_context`) and
+//! the nodes that triggered the eviction (a whole batch, merge, graft or
+//! applied diff) are never chosen.
+//!
+//! Eviction only runs on the public insertion paths, never while replaying
+//! the event log or the WAL (the recorded `Pruned` events cover that).
+
+use crate::{CapacityConfig, ColdArchive, ConfigError, LoomConfig, LoomGraph, Node, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum EvictionPolicy {
+    /// Drops the nodes with the lowest `importance`. Like every other
+    /// policy except `Archive`, drops go through `config.archive_tier`.
+    #[default]
+    LowestImportance,
+    /// Drops the least recently accessed nodes.
+    Lru,
+    /// Drops episodes oldest first; once none are left, falls back to lowest importance.
+    OldestEpisodeFirst,
+    /// Moves the least important nodes to the cold archive at `path`
+    /// (regardless of `config.archive_tier`). If the archive can't be
+    /// written nothing is evicted.
+    Archive { path: String },
+}
+
+impl LoomGraph {
+    /// A new graph bounded to `max_nodes` live nodes, evicting with `policy`.
+    /// Goes through `LoomConfig::validate`, so `max_nodes == 0` is rejected.
+    pub fn with_capacity(max_nodes: usize, policy: EvictionPolicy) -> Result<Self, ConfigError> {
+        Self::with_config(LoomConfig {
+            capacity: CapacityConfig { max_nodes: Some(max_nodes), eviction: policy },
+            ..LoomConfig::default()
+        })
+    }
+
+    /// Evicts until the graph fits `config.capacity.max_nodes`, sparing
+    /// `protected`. Returns how many nodes left the graph.
+    pub fn evict_to_capacity(&mut self, protected: &[NodeId]) -> io::Result<usize> {
+        let Some(max) = self.config.capacity.max_nodes else { return Ok(0) };
+        let excess = self.nodes.len().saturating_sub(max);
+        if excess == 0 {
+            return Ok(0);
+        }
+
+        let protected: HashSet<&NodeId> = protected.iter().collect();
+        let victims: Vec<NodeId> = self.eviction_order().into_iter()
+            .filter(|id| !protected.contains(id) && !self.nodes[id].meta().always_in_context && !self.nodes[id].meta().is_pinned())
+            .take(excess)
+            .collect();
+        if victims.is_empty() {
+            return Ok(0);
+        }
+
+        if let EvictionPolicy::Archive { path } = &self.config.capacity.eviction {
+            let mut archive = ColdArchive::open(&path.clone())?;
+            return self.archive_nodes(&mut archive, &victims);
+        }
+        self.discard_nodes(&victims);
+        Ok(victims.len())
+    }
+
+    /// Automatic eviction after an insertion. An archive failure leaves the
+    /// graph over capacity rather than losing memories.
+    pub(crate) fn enforce_capacity(&mut self, protected: &[NodeId]) {
+        if self.config.capacity.max_nodes.is_some() {
+            let _ = self.evict_to_capacity(protected);
+        }
+    }
+
+    /// Every node, first to evict first.
+    fn eviction_order(&self) -> Vec<NodeId> {
+        let by_importance = || {
+            let mut ranked: Vec<(NodeId, f32)> = self.importance_scores().into_iter().collect();
+            ranked.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+            ranked.into_iter().map(|(id, _)| id).collect::<Vec<_>>()
+        };
+        match self.config.capacity.eviction {
+            EvictionPolicy::LowestImportance | EvictionPolicy::Archive { .. } => by_importance(),
+            EvictionPolicy::Lru => self.least_recently_used(self.nodes.len()),
+            EvictionPolicy::OldestEpisodeFirst => {
+                let mut order = self.episodes_chronological();
+                order.extend(by_importance().into_iter().filter(|id| !matches!(self.nodes[id], Node::Episode(..))));
+                order
+            }
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! # Centrality
+//! Finds the "hub" concepts that anchor the agent's knowledge. Both measures
+//! run over positive `Associated` edges, following their direction:
+//!
+//! - **PageRank**: rank flows along edges in proportion to their weight;
+//!   dangling nodes spread theirs uniformly. Scores sum to 1.
+//! - **Betweenness** (Brandes): how many shortest paths pass through a node,
+//!   with edge length `1 / weight` so strong associations are "close".
+//!   Costs O(V·E·log V), so it's meant for on-demand analysis.
+//!
+//! Results are cached (runtime only) per tick and graph shape, so repeated
+//! calls within a tick are free.
+
+use crate::{LoomGraph, NodeId, ASSOCIATED_EDGE};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Mutex, PoisonError};
+
+// Caminhos com comprimentos dentro desta tolerância contam como empatados
+const PATH_EPSILON: f32 = 1e-6;
+
+/// Identifies the graph state a cached result was computed for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CacheKey {
+    tick: u64,
+    nodes: usize,
+    edges: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct CentralityBuffers {
+    pagerank: Option<(CacheKey, f32, usize, HashMap<NodeId, f32>)>,
+    betweenness: Option<(CacheKey, HashMap<NodeId, f32>)>,
+}
+
+/// Runtime-only cache for centrality scores (never persisted).
+#[derive(Debug, Default)]
+pub struct CentralityCache(Mutex<CentralityBuffers>);
+
+impl Clone for CentralityCache {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.buffers().clone()))
+    }
+}
+
+impl CentralityCache {
+    fn buffers(&self) -> std::sync::MutexGuard<'_, CentralityBuffers> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Min-heap entry for Dijkstra.
+struct Frontier(f32, usize);
+
+impl PartialEq for Frontier {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Frontier {}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0).then(other.1.cmp(&self.1))
+    }
+}
+
+impl LoomGraph {
+    fn centrality_key(&self) -> CacheKey {
+        CacheKey {
+            tick: self.current_tick,
+            nodes: self.nodes.len(),
+            edges: self.adjacency.values().map(Vec::len).sum(),
+        }
+    }
+
+    /// Nodes sorted by id plus weighted `Associated` out-edges by position.
+    fn association_lists(&self) -> (Vec<NodeId>, Vec<Vec<(usize, f32)>>) {
+        let mut ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+        ids.sort();
+        let position: HashMap<NodeId, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let mut out: Vec<Vec<(usize, f32)>> = vec![Vec::new(); ids.len()];
+        for (source, edges) in &self.adjacency {
+            let Some(&a) = position.get(source) else { continue };
+            for edge in edges.iter().filter(|e| e.edge_type == ASSOCIATED_EDGE && e.weight > 0.0) {
+                let Some(&b) = position.get(&edge.target) else { continue };
+                if a != b {
+                    out[a].push((b, edge.weight));
+                }
+            }
+        }
+        (ids, out)
+    }
+
+    /// Weighted PageRank of every node (scores sum to 1).
+    pub fn compute_pagerank(&self, damping: f32, iterations: usize) -> HashMap<NodeId, f32> {
+        let key = self.centrality_key();
+        if let Some((k, d, i, scores)) = &self.centrality.buffers().pagerank {
+            if *k == key && *d == damping && *i == iterations {
+                return scores.clone();
+            }
+        }
+
+        let (ids, out) = self.association_lists();
+        let n = ids.len();
+        if n == 0 {
+            return HashMap::new();
+        }
+        let damping = damping.clamp(0.0, 1.0);
+        let totals: Vec<f32> = out.iter().map(|edges| edges.iter().map(|(_, w)| w).sum()).collect();
+
+        let mut rank = vec![1.0 / n as f32; n];
+        for _ in 0..iterations {
+            let dangling: f32 = (0..n).filter(|i| out[*i].is_empty()).map(|i| rank[i]).sum();
+            let base = (1.0 - damping) / n as f32 + damping * dangling / n as f32;
+            let mut next = vec![base; n];
+            for (i, edges) in out.iter().enumerate() {
+                for (j, weight) in edges {
+                    next[*j] += damping * rank[i] * weight / totals[i];
+                }
+            }
+            rank = next;
+        }
+
+        let scores: HashMap<NodeId, f32> = ids.into_iter().zip(rank).collect();
+        self.centrality.buffers().pagerank = Some((key, damping, iterations, scores.clone()));
+        scores
+    }
+
+    /// The `k` nodes with the highest betweenness centrality, highest first.
+    pub fn betweenness_top_k(&self, k: usize) -> Vec<(NodeId, f32)> {
+        let mut ranked: Vec<(NodeId, f32)> = self.betweenness().into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked.truncate(k);
+        ranked
+    }
+
+    fn betweenness(&self) -> HashMap<NodeId, f32> {
+        let key = self.centrality_key();
+        if let Some((k, scores)) = &self.centrality.buffers().betweenness {
+            if *k == key {
+                return scores.clone();
+            }
+        }
+
+        let (ids, out) = self.association_lists();
+        let n = ids.len();
+        let mut centrality = vec![0.0f32; n];
+
+        for source in 0..n {
+            // Brandes: Dijkstra a partir de `source`, depois acumula dependências em ordem reversa
+            let mut order = Vec::with_capacity(n);
+            let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+            let mut paths = vec![0.0f32; n];
+            let mut dist = vec![f32::INFINITY; n];
+            let mut settled = vec![false; n];
+            paths[source] = 1.0;
+            dist[source] = 0.0;
+
+            let mut heap = BinaryHeap::from([Frontier(0.0, source)]);
+            while let Some(Frontier(d, v)) = heap.pop() {
+                if settled[v] {
+                    continue;
+                }
+                settled[v] = true;
+                order.push(v);
+                for (w, weight) in &out[v] {
+                    let candidate = d + 1.0 / weight;
+                    if candidate < dist[*w] - PATH_EPSILON {
+                        dist[*w] = candidate;
+                        paths[*w] = paths[v];
+                        preds[*w] = vec![v];
+                        heap.push(Frontier(candidate, *w));
+                    } else if (candidate - dist[*w]).abs() <= PATH_EPSILON && !settled[*w] {
+                        paths[*w] += paths[v];
+                        preds[*w].push(v);
+                    }
+                }
+            }
+
+            let mut dependency = vec![0.0f32; n];
+            for w in order.into_iter().rev() {
+                for v in &preds[w] {
+                    dependency[*v] += paths[*v] / paths[w] * (1.0 + dependency[w]);
+                }
+                if w != source {
+                    centrality[w] += dependency[w];
+                }
+            }
+        }
+
+        let scores: HashMap<NodeId, f32> = ids.into_iter().zip(centrality).collect();
+        self.centrality.buffers().betweenness = Some((key, scores.clone()));
+        scores
+    }
+}
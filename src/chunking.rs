@@ -0,0 +1,119 @@
+//! # Long-Text Episodes
+//! A long passage (a meeting transcript, a document section) is stored as a
+//! parent "scene" episode plus chunk episodes of at most
+//! `config.chunking.max_chunk_chars`. Chunks point at the scene with `PartOf`
+//! edges and at each other with `Preceded` edges, so a matching chunk can be
+//! stitched back into its surrounding passage (see `search_chunks`).
+
+use crate::{Connection, EpisodeData, LoomGraph, Node, NodeId, PRECEDED_EDGE};
+use chrono::Duration;
+
+pub const PART_OF_EDGE: &str = "PartOf";
+
+/// Splits `text` into chunks of at most `max_chars` characters, breaking at
+/// paragraph and sentence boundaries when possible.
+pub fn split_into_chunks(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut pieces: Vec<String> = Vec::new();
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        let mut sentence = String::new();
+        for word in paragraph.split_whitespace() {
+            if !sentence.is_empty() {
+                sentence.push(' ');
+            }
+            sentence.push_str(word);
+            if word.ends_with(['.', '!', '?']) {
+                pieces.push(std::mem::take(&mut sentence));
+            }
+        }
+        if !sentence.is_empty() {
+            pieces.push(sentence);
+        }
+        // Marcador de fim de parágrafo: força um novo chunk
+        pieces.push(String::new());
+    }
+
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let flush = |current: &mut String, chunks: &mut Vec<String>| {
+        if !current.is_empty() {
+            chunks.push(std::mem::take(current));
+        }
+    };
+    for piece in pieces {
+        if piece.is_empty() {
+            if current.chars().count() * 2 >= max_chars {
+                flush(&mut current, &mut chunks);
+            }
+            continue;
+        }
+        let joined = current.chars().count() + piece.chars().count() + 1;
+        if !current.is_empty() && joined > max_chars {
+            flush(&mut current, &mut chunks);
+        }
+        if piece.chars().count() > max_chars {
+            // Frase maior que o limite: quebra por palavras
+            for word in piece.split_whitespace() {
+                if !current.is_empty() && current.chars().count() + word.chars().count() + 1 > max_chars {
+                    flush(&mut current, &mut chunks);
+                }
+                if !current.is_empty() {
+                    current.push(' ');
+                }
+                current.push_str(word);
+            }
+            continue;
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&piece);
+    }
+    flush(&mut current, &mut chunks);
+    chunks
+}
+
+impl LoomGraph {
+    /// Stores `text` as a scene episode titled `title` plus its chunks.
+    /// Returns `(scene, chunks in order)`.
+    pub fn add_long_episode(&mut self, title: String, text: &str) -> (NodeId, Vec<NodeId>) {
+        let now = self.now();
+        let scene = Node::Episode(self.episode_meta(), EpisodeData { summary: title, timestamp: now, confidence: None, attachments: Vec::new() });
+        let chunks = split_into_chunks(text, self.config.chunking.max_chunk_chars);
+        let mut nodes = vec![scene];
+        nodes.extend(chunks.into_iter().enumerate().map(|(i, summary)| {
+            Node::Episode(self.episode_meta(), EpisodeData {
+                summary,
+                timestamp: now + Duration::microseconds(i as i64 + 1),
+                confidence: None,
+                attachments: Vec::new(),
+            })
+        }));
+
+        let mut ids = self.add_nodes_batch(nodes);
+        let scene = ids.remove(0);
+        let weight = self.config.chunking.link_weight;
+        for chunk in &ids {
+            self.store_edge(*chunk, Connection { target: scene, weight, edge_type: PART_OF_EDGE.to_string(), inferred: false });
+        }
+        for pair in ids.windows(2) {
+            self.store_edge(pair[0], Connection { target: pair[1], weight, edge_type: PRECEDED_EDGE.to_string(), inferred: false });
+        }
+        self.chain_session(&[scene]);
+        self.chain_session(&ids);
+        if self.config.auto_link.enabled {
+            for id in &ids {
+                self.link_mentions(*id);
+            }
+        }
+        self.maybe_autosave();
+        (scene, ids)
+    }
+
+    /// Scene a chunk belongs to (target of its `PartOf` edge).
+    pub fn chunk_parent(&self, id: NodeId) -> Option<NodeId> {
+        self.adjacency.get(&id)?.iter()
+            .find(|e| e.edge_type == PART_OF_EDGE && self.nodes.contains_key(&e.target))
+            .map(|e| e.target)
+    }
+}
@@ -0,0 +1,151 @@
+//! # Time & Id Sources
+//! Every wall-clock read and every fresh id the graph makes goes through a
+//! `TimeSource` and an `IdSource`, defaulting to the real clock and random
+//! v4 UUIDs. Swapping them in makes tests and simulations reproducible:
+//! `with_seed` installs seeded ids and a clock frozen at the Unix epoch;
+//! share a `ManualClock` via `set_time_source` to move time by hand.
+//!
+//! Latency measurements (`perf_stats`) always use the real clock.
+
+use crate::{LoomGraph, NodeId, NodeKind, NodeMetadata};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex, PoisonError};
+use uuid::Uuid;
+
+pub trait TimeSource: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub trait IdSource: Send + Sync {
+    fn next_uuid(&self) -> Uuid;
+}
+
+/// The real clock (`Utc::now`).
+pub struct SystemClock;
+
+impl TimeSource for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Random v4 UUIDs.
+pub struct RandomIds;
+
+impl IdSource for RandomIds {
+    fn next_uuid(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+/// A clock that only moves when told to.
+pub struct ManualClock(Mutex<DateTime<Utc>>);
+
+impl ManualClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self(Mutex::new(start))
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = now;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        *now += by;
+    }
+}
+
+impl TimeSource for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Reproducible v4-shaped UUIDs from a seed (SplitMix64).
+pub struct SeededIds(Mutex<u64>);
+
+impl SeededIds {
+    pub fn new(seed: u64) -> Self {
+        Self(Mutex::new(seed))
+    }
+
+    fn next_u64(state: &mut u64) -> u64 {
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl IdSource for SeededIds {
+    fn next_uuid(&self) -> Uuid {
+        let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let high = Self::next_u64(&mut state);
+        let low = Self::next_u64(&mut state);
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&high.to_be_bytes());
+        bytes[8..].copy_from_slice(&low.to_be_bytes());
+        uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+}
+
+/// The sources in use by a graph (runtime only; a reloaded graph gets the real ones).
+#[derive(Clone)]
+pub struct Sources {
+    pub time: Arc<dyn TimeSource>,
+    pub ids: Arc<dyn IdSource>,
+}
+
+impl Default for Sources {
+    fn default() -> Self {
+        Self { time: Arc::new(SystemClock), ids: Arc::new(RandomIds) }
+    }
+}
+
+impl LoomGraph {
+    /// Deterministic mode: ids from `SeededIds::new(seed)` and a clock frozen
+    /// at the Unix epoch.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.sources = Sources {
+            time: Arc::new(ManualClock::new(DateTime::UNIX_EPOCH)),
+            ids: Arc::new(SeededIds::new(seed)),
+        };
+        self
+    }
+
+    pub fn set_time_source(&mut self, time: Arc<dyn TimeSource>) {
+        self.sources.time = time;
+    }
+
+    pub fn set_id_source(&mut self, ids: Arc<dyn IdSource>) {
+        self.sources.ids = ids;
+    }
+
+    /// Current time according to the graph's `TimeSource`.
+    pub fn now(&self) -> DateTime<Utc> {
+        self.sources.time.now()
+    }
+
+    pub(crate) fn new_uuid(&self) -> Uuid {
+        self.sources.ids.next_uuid()
+    }
+
+    pub(crate) fn new_node_id(&self) -> NodeId {
+        NodeId::from_uuid(self.new_uuid())
+    }
+
+    /// Fresh metadata for a `kind` node: an id from the graph's `IdSource`,
+    /// the active namespace and the kind's configured defaults.
+    pub(crate) fn new_meta(&self, kind: NodeKind) -> NodeMetadata {
+        let defaults = self.config.node_defaults(kind);
+        NodeMetadata {
+            id: self.new_node_id(),
+            namespace: self.active_namespace.clone(),
+            activation: defaults.initial_activation,
+            stability: defaults.initial_stability,
+            ..NodeMetadata::new()
+        }
+    }
+}
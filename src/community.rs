@@ -0,0 +1,92 @@
+//! # Communities
+//! Groups memories into topics by label propagation over positive
+//! `Associated` edges (treated as undirected, weighted). Updates run in id
+//! order with ties going to the smallest label, so the result is
+//! deterministic. Community ids are renumbered by size: 0 is the largest.
+//!
+//! The last result is cached on the graph (runtime only) so node info and
+//! topic queries don't recompute it; call `detect_communities` again after
+//! the graph changes.
+
+use crate::{LoomGraph, NodeId, ASSOCIATED_EDGE};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+const MAX_ROUNDS: usize = 20;
+
+impl LoomGraph {
+    /// Assigns every node a community id and caches the assignment.
+    pub fn detect_communities(&mut self) -> HashMap<NodeId, usize> {
+        let mut ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+        ids.sort();
+        let position: HashMap<NodeId, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let mut neighbours: Vec<Vec<(usize, f32)>> = vec![Vec::new(); ids.len()];
+        for (source, edges) in &self.adjacency {
+            let Some(&a) = position.get(source) else { continue };
+            for edge in edges.iter().filter(|e| e.edge_type == ASSOCIATED_EDGE && e.weight > 0.0) {
+                let Some(&b) = position.get(&edge.target) else { continue };
+                if a != b {
+                    neighbours[a].push((b, edge.weight));
+                    neighbours[b].push((a, edge.weight));
+                }
+            }
+        }
+
+        let mut labels: Vec<usize> = (0..ids.len()).collect();
+        for _ in 0..MAX_ROUNDS {
+            let mut changed = false;
+            for i in 0..ids.len() {
+                if neighbours[i].is_empty() {
+                    continue;
+                }
+                let mut votes: BTreeMap<usize, f32> = BTreeMap::new();
+                for (j, weight) in &neighbours[i] {
+                    *votes.entry(labels[*j]).or_default() += weight;
+                }
+                // Maior peso vence; empate fica com o menor rótulo (BTreeMap já ordena)
+                let best = votes.iter()
+                    .fold(None, |best: Option<(usize, f32)>, (label, weight)| match best {
+                        Some((_, w)) if w >= *weight => best,
+                        _ => Some((*label, *weight)),
+                    })
+                    .map(|(label, _)| label)
+                    .unwrap_or(labels[i]);
+                if best != labels[i] {
+                    labels[i] = best;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut sizes: BTreeMap<usize, usize> = BTreeMap::new();
+        for label in &labels {
+            *sizes.entry(*label).or_default() += 1;
+        }
+        let mut order: Vec<(usize, usize)> = sizes.into_iter().collect();
+        order.sort_by_key(|(label, size)| (Reverse(*size), *label));
+        let renumber: HashMap<usize, usize> = order.iter().enumerate().map(|(new, (label, _))| (*label, new)).collect();
+
+        let assignment: HashMap<NodeId, usize> = ids.iter().zip(&labels).map(|(id, label)| (*id, renumber[label])).collect();
+        self.communities = assignment.clone();
+        assignment
+    }
+
+    /// Community of `id` from the last `detect_communities` run.
+    pub fn community_of(&self, id: NodeId) -> Option<usize> {
+        self.communities.get(&id).copied()
+    }
+
+    /// Members of a community from the last run, sorted by id.
+    pub fn community_members(&self, community: usize) -> Vec<NodeId> {
+        let mut members: Vec<NodeId> = self.communities.iter()
+            .filter(|(id, c)| **c == community && self.nodes.contains_key(id))
+            .map(|(id, _)| *id)
+            .collect();
+        members.sort();
+        members
+    }
+}
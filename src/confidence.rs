@@ -0,0 +1,167 @@
+//! # Confidence & Contradictions
+//! Concepts and episodes carry an optional trust score, nudged by feedback
+//! with `raise_confidence` / `lower_confidence`. `find_contradictions` links
+//! conflicting concepts with `Inhibited` edges: a boost on one suppresses
+//! the other instead of spreading to it.
+//!
+//! Two concepts conflict when they share a name but neither definition
+//! covers the other, or when their names form a pair registered with
+//! `mark_exclusive` ("vegetarian" / "eats meat").
+
+use crate::{tokenize, Connection, GraphEvent, LoomGraph, Node, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+pub const INHIBITED_EDGE: &str = "Inhibited";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfidenceConfig {
+    /// Reported for concepts and episodes that were never rated.
+    pub initial: f32,
+    /// Share of the remaining distance to 1 (or 0) moved per feedback.
+    pub feedback_step: f32,
+    /// Concept names that can't both hold (see `mark_exclusive`).
+    pub exclusive_pairs: Vec<(String, String)>,
+}
+
+impl Default for ConfidenceConfig {
+    fn default() -> Self {
+        Self { initial: 0.5, feedback_step: 0.2, exclusive_pairs: Vec::new() }
+    }
+}
+
+impl LoomGraph {
+    /// Trust score of a concept or episode; `None` for other kinds or missing nodes.
+    pub fn confidence(&self, id: NodeId) -> Option<f32> {
+        match self.nodes.get(&id)? {
+            Node::Concept(_, d) => Some(d.confidence.unwrap_or(self.config.confidence.initial)),
+            Node::Episode(_, d) => Some(d.confidence.unwrap_or(self.config.confidence.initial)),
+            _ => None,
+        }
+    }
+
+    /// Sets the score (clamped to 0..=1). Returns false if `id` isn't a concept or episode.
+    pub fn set_confidence(&mut self, id: NodeId, value: f32) -> bool {
+        let slot = match self.nodes.get_mut(&id) {
+            Some(Node::Concept(_, d)) => &mut d.confidence,
+            Some(Node::Episode(_, d)) => &mut d.confidence,
+            _ => return false,
+        };
+        let value = value.clamp(0.0, 1.0);
+        *slot = Some(value);
+        self.record(GraphEvent::ConfidenceSet { id, value });
+        true
+    }
+
+    /// Positive feedback: moves the score `feedback_step` of the way to 1. Returns the new score.
+    pub fn raise_confidence(&mut self, id: NodeId) -> Option<f32> {
+        let current = self.confidence(id)?;
+        let updated = current + (1.0 - current) * self.config.confidence.feedback_step;
+        self.set_confidence(id, updated);
+        Some(updated)
+    }
+
+    /// Negative feedback: moves the score `feedback_step` of the way to 0. Returns the new score.
+    pub fn lower_confidence(&mut self, id: NodeId) -> Option<f32> {
+        let current = self.confidence(id)?;
+        let updated = current - current * self.config.confidence.feedback_step;
+        self.set_confidence(id, updated);
+        Some(updated)
+    }
+
+    /// Registers two concept names as mutually exclusive. Returns false if already registered.
+    pub fn mark_exclusive(&mut self, a: &str, b: &str) -> bool {
+        let (a_key, b_key) = (tokenize(a), tokenize(b));
+        if a_key.is_empty() || b_key.is_empty() || a_key == b_key {
+            return false;
+        }
+        let known = self.config.confidence.exclusive_pairs.iter().any(|(x, y)| {
+            let (x, y) = (tokenize(x), tokenize(y));
+            (x == a_key && y == b_key) || (x == b_key && y == a_key)
+        });
+        if known {
+            return false;
+        }
+        self.config.confidence.exclusive_pairs.push((a.to_string(), b.to_string()));
+        self.record_config();
+        true
+    }
+
+    /// Pairs of conflicting concepts (older first in each pair), linking each
+    /// pair with `Inhibited` edges both ways if they aren't already.
+    pub fn find_contradictions(&mut self) -> Vec<(NodeId, NodeId)> {
+        let mut by_name: BTreeMap<String, Vec<(u64, NodeId)>> = BTreeMap::new();
+        for (id, node) in &self.nodes {
+            if let Node::Concept(meta, d) = node {
+                let name = tokenize(&d.name).join(" ");
+                if !name.is_empty() && !self.is_forgotten(*id) {
+                    by_name.entry(name).or_default().push((meta.seq, *id));
+                }
+            }
+        }
+        for group in by_name.values_mut() {
+            group.sort();
+        }
+
+        let mut pairs: Vec<(NodeId, NodeId)> = Vec::new();
+        // Mesmo nome, definições que não se cobrem
+        for group in by_name.values() {
+            for (i, (_, a)) in group.iter().enumerate() {
+                for (_, b) in &group[i + 1..] {
+                    if self.definitions_conflict(*a, *b) {
+                        pairs.push((*a, *b));
+                    }
+                }
+            }
+        }
+        // Pares registrados pelo usuário
+        let none = Vec::new();
+        for (x, y) in &self.config.confidence.exclusive_pairs {
+            let xs = by_name.get(&tokenize(x).join(" ")).unwrap_or(&none);
+            let ys = by_name.get(&tokenize(y).join(" ")).unwrap_or(&none);
+            for (x_seq, a) in xs {
+                for (y_seq, b) in ys.iter().filter(|(_, b)| b != a) {
+                    pairs.push(if x_seq <= y_seq { (*a, *b) } else { (*b, *a) });
+                }
+            }
+        }
+        let mut seen = HashSet::new();
+        pairs.retain(|pair| seen.insert(*pair));
+
+        for (a, b) in &pairs {
+            for (source, target) in [(*a, *b), (*b, *a)] {
+                let linked = self.adjacency.get(&source).into_iter().flatten()
+                    .any(|e| e.target == target && e.edge_type == INHIBITED_EDGE);
+                if !linked {
+                    self.insert_edge(source, Connection { target, weight: 1.0, edge_type: INHIBITED_EDGE.to_string(), inferred: false });
+                }
+            }
+        }
+        pairs
+    }
+
+    fn definitions_conflict(&self, a: NodeId, b: NodeId) -> bool {
+        let definition = |id: NodeId| match self.nodes.get(&id) {
+            Some(Node::Concept(_, d)) => tokenize(&d.definition).into_iter().collect::<HashSet<String>>(),
+            _ => HashSet::new(),
+        };
+        let (a, b) = (definition(a), definition(b));
+        !a.is_empty() && !b.is_empty() && !a.is_subset(&b) && !b.is_subset(&a)
+    }
+
+    /// Lowers `id`'s activation by `fraction` of its current value (no further spread).
+    pub(crate) fn suppress(&mut self, id: NodeId, fraction: f32) {
+        let tick = self.current_tick;
+        let Some(node) = self.nodes.get(&id) else { return };
+        let current = self.projected_activation(node.meta(), tick);
+        self.sample_activation([id]);
+        if let Some(node) = self.nodes.get_mut(&id) {
+            self.journal.remember(node.meta());
+            let meta = node.meta_mut();
+            meta.activation = meta.floored(current * (1.0 - fraction.clamp(0.0, 1.0)));
+            meta.last_tick = tick;
+        }
+        self.sample_activation([id]);
+    }
+}
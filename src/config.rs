@@ -0,0 +1,771 @@
+//! # Engine Configuration
+//! Tunables that shape decay, consolidation and pruning. Serialized with the
+//! graph so a reloaded brain keeps behaving the same way.
+
+use crate::{ArchiveTier, ConfidenceConfig, EmotionLabel, EvictionPolicy, GoalConfig, GraphEvent, InferenceRule, LoomGraph, NodeKind, SkillConfig, TaxonomyConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Decay rate used when neither the caller nor the config picks one.
+pub const DEFAULT_DECAY_RATE: f32 = 0.95;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoomConfig {
+    /// Graph-wide decay rate per tick. The live value is `LoomGraph::decay_rate`:
+    /// `config()` reports it here and `with_config`/`set_config` apply it
+    /// (`None` keeps the current rate).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decay_rate: Option<f32>,
+    pub dynamics: DynamicsConfig,
+    /// Creation defaults per node kind; kinds left out use `NodeDefaults::default()`.
+    pub kinds: BTreeMap<NodeKind, NodeDefaults>,
+    /// How strongly emotional linkage shields a node from pruning, in `[0, 1]`.
+    /// A node tied to a State with |valence| = 1 survives with a stability
+    /// threshold scaled by `1 - emotional_protection`.
+    pub emotional_protection: f32,
+    pub sanitizer: SanitizerConfig,
+    /// Activation floor given to nodes pinned with `pin`.
+    pub pin_floor: f32,
+    /// Upper bound on `always_in_context` nodes placed at the head of a context.
+    pub max_context_pinned: usize,
+    /// Adds each memory's `Source` (origin and reference) to rendered contexts.
+    pub cite_sources: bool,
+    pub perf: PerfConfig,
+    pub limits: LimitsConfig,
+    pub wake: WakeConfig,
+    pub auto_link: AutoLinkConfig,
+    pub conversation: ConversationConfig,
+    pub anonymize: AnonymizeConfig,
+    pub chunking: ChunkingConfig,
+    pub history: HistoryConfig,
+    pub rehearsal: RehearsalConfig,
+    pub importance: ImportanceConfig,
+    pub emotion: EmotionConfig,
+    pub goals: GoalConfig,
+    pub skills: SkillConfig,
+    pub taxonomy: TaxonomyConfig,
+    pub confidence: ConfidenceConfig,
+    /// Inference rules applied on every `consolidate` (see `register_rule`).
+    pub rules: Vec<InferenceRule>,
+    pub capacity: CapacityConfig,
+    /// Where pruned memories go; `Off` deletes them permanently.
+    pub archive_tier: ArchiveTier,
+    /// Ticks a `forget`-ed node stays recoverable before it is hard-deleted.
+    pub forget_grace_ticks: u64,
+    /// Operations kept for `undo`/`redo`; 0 disables the journal.
+    pub journal_capacity: usize,
+    /// Events kept for `reconstruct_at` once `enable_event_log` is on.
+    pub event_log_capacity: usize,
+    /// Boost given to an existing concept when `upsert_concept` hits it.
+    pub upsert_boost: f32,
+    /// How far in the future (seconds) an explicit episode timestamp may lie
+    /// before `add_episode_at` rejects it (clock skew allowance).
+    pub future_tolerance_secs: u64,
+    /// Activation below which observers get `on_decay_below`.
+    pub decay_alert_threshold: f32,
+}
+
+/// Core activation dynamics: consolidation ceiling, ripple shape, pruning
+/// and how ticks map onto wall-clock time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DynamicsConfig {
+    /// Ceiling for stability gained through dreams, consolidation and practice.
+    pub stability_cap: f32,
+    /// Fraction of a boost (times edge weight) passed on to each neighbor.
+    pub spread_factor: f32,
+    /// Ripple depth used by `stimulate`.
+    pub max_hops: u8,
+    /// Stability a faded node needs to survive a dream's pruning.
+    pub prune_stability: f32,
+    /// Activation below which a node counts as faded for pruning.
+    pub prune_activation: f32,
+    /// Wall-clock seconds one tick stands for (`wake_up`, dream length, `RealTime` clock).
+    pub seconds_per_tick: u64,
+    pub clock: ClockMode,
+}
+
+/// What moves the graph's clock forward.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClockMode {
+    /// Only `tick`, `fast_forward`, dreams and `wake_up`.
+    #[default]
+    Ticks,
+    /// Also wall-clock time: mutations first catch up to the time elapsed
+    /// since the last sync (see `catch_up_clock`), so hosts never call `tick`.
+    RealTime,
+}
+
+impl Default for DynamicsConfig {
+    fn default() -> Self {
+        Self {
+            stability_cap: 100.0,
+            spread_factor: 0.5,
+            max_hops: 3,
+            prune_stability: 1.2,
+            prune_activation: 0.1,
+            seconds_per_tick: 60,
+            clock: ClockMode::Ticks,
+        }
+    }
+}
+
+impl DynamicsConfig {
+    /// Ticks covering `secs` of wall-clock time (at least one).
+    pub fn ticks_for_secs(&self, secs: u64) -> u64 {
+        (secs / self.seconds_per_tick.max(1)).max(1)
+    }
+}
+
+/// What a freshly created node of one kind starts with, and how fast it fades.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodeDefaults {
+    pub initial_activation: f32,
+    pub initial_stability: f32,
+    /// Scales the ticks elapsed under the graph-wide decay rate: 2 fades
+    /// twice as fast. Nodes with a `DecayOverride` ignore it.
+    pub decay_multiplier: f32,
+}
+
+impl Default for NodeDefaults {
+    fn default() -> Self {
+        Self {
+            initial_activation: 1.0,
+            initial_stability: 1.0,
+            decay_multiplier: 1.0,
+        }
+    }
+}
+
+impl NodeDefaults {
+    /// Episodes fade faster than concepts, States faster still.
+    pub fn per_kind() -> BTreeMap<NodeKind, NodeDefaults> {
+        BTreeMap::from([
+            (NodeKind::Episode, NodeDefaults { decay_multiplier: 1.5, ..NodeDefaults::default() }),
+            (NodeKind::State, NodeDefaults { decay_multiplier: 2.0, ..NodeDefaults::default() }),
+        ])
+    }
+}
+
+/// Per-kind default decay rates, resolved once for bulk decay passes.
+#[derive(Debug, Clone)]
+pub(crate) struct DecayRates {
+    base: f32,
+    per_kind: BTreeMap<NodeKind, f32>,
+}
+
+impl DecayRates {
+    pub(crate) fn new(base: f32, kinds: &BTreeMap<NodeKind, NodeDefaults>) -> Self {
+        let per_kind = kinds.iter().map(|(kind, d)| (*kind, base.powf(d.decay_multiplier))).collect();
+        Self { base, per_kind }
+    }
+
+    pub(crate) fn of(&self, kind: NodeKind) -> f32 {
+        self.per_kind.get(&kind).copied().unwrap_or(self.base)
+    }
+}
+
+/// Text hygiene applied to every memory before it is rendered into a prompt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SanitizerConfig {
+    /// Drops control characters (except newline and tab) that could corrupt the prompt.
+    pub strip_control_chars: bool,
+    /// Truncates each memory's text fields to this many characters.
+    pub max_memory_chars: Option<usize>,
+}
+
+impl Default for SanitizerConfig {
+    fn default() -> Self {
+        Self {
+            strip_control_chars: true,
+            max_memory_chars: None,
+        }
+    }
+}
+
+/// Operation timing (see `LoomGraph::perf_stats`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerfConfig {
+    pub enabled: bool,
+    /// Samples per operation kept for the rolling percentiles.
+    pub window: usize,
+    /// Operations at least this slow are kept in the slow-op log (`None` disables it).
+    pub slow_op_threshold_ms: Option<f64>,
+    pub slow_log_capacity: usize,
+}
+
+impl Default for PerfConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window: 256,
+            slow_op_threshold_ms: Some(100.0),
+            slow_log_capacity: 100,
+        }
+    }
+}
+
+/// Work caps for single operations (see `search_bounded`, `stimulate_with_report`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LimitsConfig {
+    /// Maximum distinct nodes a search scores before returning partial results.
+    pub max_search_candidates: Option<usize>,
+    /// Maximum nodes a single stimulation may update through the ripple.
+    pub max_ripple_nodes: Option<usize>,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_search_candidates: Some(10_000),
+            max_ripple_nodes: Some(5_000),
+        }
+    }
+}
+
+/// What `wake_up` does after a long absence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WakeConfig {
+    /// Nodes whose activation fell from at or above this value to below it are reported as faded.
+    pub fade_threshold: f32,
+    /// Run one dream per 8h away, so long absences consolidate.
+    pub dream_on_wake: bool,
+    /// Upper bound on dreams per wake, to keep waking cheap.
+    pub max_dreams: u32,
+}
+
+impl Default for WakeConfig {
+    fn default() -> Self {
+        Self {
+            fade_threshold: 0.1,
+            dream_on_wake: false,
+            max_dreams: 3,
+        }
+    }
+}
+
+/// Automatic `Mentioned` edges from new episodes to the concepts they name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoLinkConfig {
+    /// Link on every `add_episode` (otherwise only via `add_episode_linked`).
+    pub enabled: bool,
+    pub weight: f32,
+    /// Activation boost given to each mentioned concept (0 disables it).
+    pub boost: f32,
+}
+
+impl Default for AutoLinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            weight: 0.5,
+            boost: 0.1,
+        }
+    }
+}
+
+/// How `ingest_conversation` turns a transcript into episodes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConversationConfig {
+    /// Weight of the `Preceded` edge between consecutive turns (and session episodes).
+    pub chain_weight: f32,
+    /// Create `Mentioned` edges to concepts named in each turn.
+    pub link_mentions: bool,
+    /// Attach a detected `State` to each segment with a clear emotional tone.
+    pub detect_emotion: bool,
+    /// Turns per emotion segment.
+    pub segment_turns: usize,
+    /// Minimum |valence| or arousal for a segment to get a `State`.
+    pub min_emotion_intensity: f32,
+}
+
+impl Default for ConversationConfig {
+    fn default() -> Self {
+        Self {
+            chain_weight: 0.6,
+            link_mentions: true,
+            detect_emotion: false,
+            segment_turns: 4,
+            min_emotion_intensity: 0.3,
+        }
+    }
+}
+
+/// What `export_anonymized` replaces besides concept names.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnonymizeConfig {
+    /// Extra regexes; every match becomes a consistent `redacted_<hash>` token.
+    pub patterns: Vec<String>,
+    pub redact_emails: bool,
+    pub redact_urls: bool,
+}
+
+impl Default for AnonymizeConfig {
+    fn default() -> Self {
+        Self {
+            patterns: Vec::new(),
+            redact_emails: true,
+            redact_urls: true,
+        }
+    }
+}
+
+/// How `add_long_episode` splits long passages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChunkingConfig {
+    pub max_chunk_chars: usize,
+    /// Weight of the `PartOf` and `Preceded` edges between chunks and scene.
+    pub link_weight: f32,
+}
+
+impl Default for ChunkingConfig {
+    fn default() -> Self {
+        Self {
+            max_chunk_chars: 800,
+            link_weight: 0.7,
+        }
+    }
+}
+
+/// Per-node activation history (see `LoomGraph::history`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    pub enabled: bool,
+    /// Samples kept per node; older ones are dropped.
+    pub max_samples: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_samples: 256,
+        }
+    }
+}
+
+/// Spaced-repetition scheduling (see `LoomGraph::due_for_rehearsal`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RehearsalConfig {
+    /// Activation a memory may fall to before it is due.
+    pub retention: f32,
+    /// Only memories at least this stable (or pinned) are scheduled.
+    pub min_stability: f32,
+    /// Boost applied by `rehearse`.
+    pub boost: f32,
+    /// Stability multiplier per rehearsal (SM-2 "ease").
+    pub ease: f32,
+    pub max_stability: f32,
+}
+
+impl Default for RehearsalConfig {
+    fn default() -> Self {
+        Self {
+            retention: 0.3,
+            min_stability: 2.0,
+            boost: 0.5,
+            ease: 1.3,
+            max_stability: 100.0,
+        }
+    }
+}
+
+/// How State nodes bias recall.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmotionConfig {
+    /// States below this activation don't count toward the current mood.
+    pub mood_min_activation: f32,
+    /// Strength of mood-congruent re-ranking; 0 disables it.
+    pub congruence_bias: f32,
+    /// Episodes linked to a State with at least this arousal are "flashbulb" memories.
+    pub flashbulb_arousal: f32,
+    /// Stability multiplier for flashbulb episodes, at creation and on every
+    /// consolidation gain; 1 disables the effect.
+    pub flashbulb_multiplier: f32,
+    /// Fraction of a State's valence/arousal lost per tick (regression to neutral); 0 disables drift.
+    pub state_drift_rate: f32,
+    /// A State this close to (0, 0) counts as neutral and, once faded, is pruned.
+    pub neutral_epsilon: f32,
+    /// Circumplex table for `LoomGraph::state_label` (nearest entry wins).
+    pub labels: Vec<EmotionLabel>,
+}
+
+impl Default for EmotionConfig {
+    fn default() -> Self {
+        Self {
+            mood_min_activation: 0.1,
+            congruence_bias: 0.5,
+            flashbulb_arousal: 0.7,
+            flashbulb_multiplier: 2.0,
+            state_drift_rate: 0.001,
+            neutral_epsilon: 0.05,
+            labels: EmotionLabel::defaults(),
+        }
+    }
+}
+
+/// Node cap enforced on insertion; see `LoomGraph::with_capacity`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CapacityConfig {
+    /// `None` means unbounded.
+    pub max_nodes: Option<usize>,
+    pub eviction: EvictionPolicy,
+}
+
+/// Blend weights for `LoomGraph::importance` (normalized by their sum).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportanceConfig {
+    pub activation: f32,
+    pub stability: f32,
+    pub centrality: f32,
+    /// Access frequency, log-scaled against the most accessed node.
+    pub access: f32,
+}
+
+impl Default for ImportanceConfig {
+    fn default() -> Self {
+        Self {
+            activation: 0.4,
+            stability: 0.25,
+            centrality: 0.2,
+            access: 0.15,
+        }
+    }
+}
+
+impl Default for LoomConfig {
+    fn default() -> Self {
+        Self {
+            decay_rate: None,
+            dynamics: DynamicsConfig::default(),
+            kinds: NodeDefaults::per_kind(),
+            emotional_protection: 0.5,
+            sanitizer: SanitizerConfig::default(),
+            pin_floor: 0.5,
+            max_context_pinned: 5,
+            cite_sources: false,
+            perf: PerfConfig::default(),
+            limits: LimitsConfig::default(),
+            wake: WakeConfig::default(),
+            auto_link: AutoLinkConfig::default(),
+            conversation: ConversationConfig::default(),
+            anonymize: AnonymizeConfig::default(),
+            chunking: ChunkingConfig::default(),
+            history: HistoryConfig::default(),
+            rehearsal: RehearsalConfig::default(),
+            importance: ImportanceConfig::default(),
+            emotion: EmotionConfig::default(),
+            goals: GoalConfig::default(),
+            skills: SkillConfig::default(),
+            taxonomy: TaxonomyConfig::default(),
+            confidence: ConfidenceConfig::default(),
+            rules: Vec::new(),
+            capacity: CapacityConfig::default(),
+            archive_tier: ArchiveTier::default(),
+            forget_grace_ticks: 1440,
+            journal_capacity: 128,
+            event_log_capacity: 10_000,
+            upsert_boost: 0.3,
+            future_tolerance_secs: 300,
+            decay_alert_threshold: 0.1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    OutOfRange { field: &'static str, value: f64, expected: &'static str },
+    InvalidPattern { field: &'static str, pattern: String, reason: String },
+    /// A field fixed at creation (`with_config`) that `set_config` can't change.
+    Immutable(&'static str),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::OutOfRange { field, value, expected } => {
+                write!(f, "{} = {} is out of range (expected {})", field, value, expected)
+            }
+            ConfigError::InvalidPattern { field, pattern, reason } => {
+                write!(f, "{} contains an invalid pattern '{}': {}", field, pattern, reason)
+            }
+            ConfigError::Immutable(field) => write!(f, "{} can only be set when the graph is created", field),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn check(field: &'static str, value: f64, ok: bool, expected: &'static str) -> Result<(), ConfigError> {
+    if ok { Ok(()) } else { Err(ConfigError::OutOfRange { field, value, expected }) }
+}
+
+impl LoomConfig {
+    pub fn builder() -> LoomConfigBuilder {
+        LoomConfigBuilder::default()
+    }
+
+    /// Creation defaults for `kind`.
+    pub fn node_defaults(&self, kind: NodeKind) -> NodeDefaults {
+        self.kinds.get(&kind).cloned().unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(rate) = self.decay_rate {
+            check("decay_rate", rate as f64, rate > 0.0 && rate <= 1.0, "0 < rate <= 1")?;
+        }
+        let dynamics = &self.dynamics;
+        check("dynamics.stability_cap", dynamics.stability_cap as f64, dynamics.stability_cap >= 1.0, ">= 1")?;
+        check("dynamics.spread_factor", dynamics.spread_factor as f64, (0.0..=1.0).contains(&dynamics.spread_factor), "0..=1")?;
+        check("dynamics.prune_stability", dynamics.prune_stability as f64, dynamics.prune_stability >= 0.0, ">= 0")?;
+        check("dynamics.prune_activation", dynamics.prune_activation as f64, (0.0..=1.0).contains(&dynamics.prune_activation), "0..=1")?;
+        check("dynamics.seconds_per_tick", dynamics.seconds_per_tick as f64, dynamics.seconds_per_tick > 0, "> 0")?;
+        for defaults in self.kinds.values() {
+            let initial = defaults.initial_activation;
+            check("kinds.initial_activation", initial as f64, (0.0..=1.0).contains(&initial), "0..=1")?;
+            let stability = defaults.initial_stability;
+            check("kinds.initial_stability", stability as f64, stability > 0.0, "> 0")?;
+            let multiplier = defaults.decay_multiplier;
+            check("kinds.decay_multiplier", multiplier as f64, multiplier >= 0.0, ">= 0")?;
+        }
+        check("pin_floor", self.pin_floor as f64, (0.0..=1.0).contains(&self.pin_floor), "0..=1")?;
+        check("event_log_capacity", self.event_log_capacity as f64, self.event_log_capacity > 0, "> 0")?;
+        let p = self.emotional_protection;
+        check("emotional_protection", p as f64, (0.0..=1.0).contains(&p), "0..=1")?;
+        if let Some(max) = self.sanitizer.max_memory_chars {
+            check("sanitizer.max_memory_chars", max as f64, max > 0, "> 0")?;
+        }
+        check("perf.window", self.perf.window as f64, self.perf.window > 0, "> 0")?;
+        if let Some(ms) = self.perf.slow_op_threshold_ms {
+            check("perf.slow_op_threshold_ms", ms, ms >= 0.0, ">= 0")?;
+        }
+        if let Some(max) = self.limits.max_search_candidates {
+            check("limits.max_search_candidates", max as f64, max > 0, "> 0")?;
+        }
+        let bias = self.emotion.congruence_bias;
+        check("emotion.congruence_bias", bias as f64, bias >= 0.0, ">= 0")?;
+        let flash = self.emotion.flashbulb_multiplier;
+        check("emotion.flashbulb_multiplier", flash as f64, flash >= 1.0, ">= 1")?;
+        check("upsert_boost", self.upsert_boost as f64, (0.0..=1.0).contains(&self.upsert_boost), "0..=1")?;
+        for rule in &self.rules {
+            check("rules.weight", rule.weight as f64, (0.0..=1.0).contains(&rule.weight), "0..=1")?;
+        }
+        let initial = self.confidence.initial;
+        check("confidence.initial", initial as f64, (0.0..=1.0).contains(&initial), "0..=1")?;
+        let step = self.confidence.feedback_step;
+        check("confidence.feedback_step", step as f64, step > 0.0 && step <= 1.0, "0 < x <= 1")?;
+        let leak = self.taxonomy.upward_leak;
+        check("taxonomy.upward_leak", leak as f64, (0.0..1.0).contains(&leak), "0 <= x < 1")?;
+        let skill_gain = self.skills.success_stability_gain;
+        check("skills.success_stability_gain", skill_gain as f64, (0.0..=1.0).contains(&skill_gain), "0..=1")?;
+        let drift = self.emotion.state_drift_rate;
+        check("emotion.state_drift_rate", drift as f64, (0.0..=1.0).contains(&drift), "0..=1")?;
+        if let Some(max) = self.capacity.max_nodes {
+            check("capacity.max_nodes", max as f64, max > 0, "> 0")?;
+        }
+        if let Some(max) = self.limits.max_ripple_nodes {
+            check("limits.max_ripple_nodes", max as f64, max > 0, "> 0")?;
+        }
+        let fade = self.wake.fade_threshold;
+        check("wake.fade_threshold", fade as f64, (0.0..=1.0).contains(&fade), "0..=1")?;
+        let boost = self.auto_link.boost;
+        check("auto_link.boost", boost as f64, (0.0..=1.0).contains(&boost), "0..=1")?;
+        let convo = &self.conversation;
+        check("conversation.segment_turns", convo.segment_turns as f64, convo.segment_turns > 0, "> 0")?;
+        let intensity = convo.min_emotion_intensity;
+        check("conversation.min_emotion_intensity", intensity as f64, (0.0..=1.0).contains(&intensity), "0..=1")?;
+        let alert = self.decay_alert_threshold;
+        check("decay_alert_threshold", alert as f64, (0.0..=1.0).contains(&alert), "0..=1")?;
+        check("history.max_samples", self.history.max_samples as f64, self.history.max_samples > 0, "> 0")?;
+        let retention = self.rehearsal.retention;
+        check("rehearsal.retention", retention as f64, retention > 0.0 && retention < 1.0, "0 < x < 1")?;
+        check("rehearsal.ease", self.rehearsal.ease as f64, self.rehearsal.ease >= 1.0, ">= 1")?;
+        let imp = &self.importance;
+        for (field, weight) in [("importance.activation", imp.activation), ("importance.stability", imp.stability), ("importance.centrality", imp.centrality), ("importance.access", imp.access)] {
+            check(field, weight as f64, weight >= 0.0, ">= 0")?;
+        }
+        let chunk = self.chunking.max_chunk_chars;
+        check("chunking.max_chunk_chars", chunk as f64, chunk > 0, "> 0")?;
+        for pattern in &self.anonymize.patterns {
+            regex::Regex::new(pattern).map_err(|e| ConfigError::InvalidPattern {
+                field: "anonymize.patterns",
+                pattern: pattern.clone(),
+                reason: e.to_string(),
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Step-by-step `LoomConfig`; `build` validates the result.
+#[derive(Debug, Clone, Default)]
+pub struct LoomConfigBuilder {
+    config: LoomConfig,
+}
+
+impl LoomConfigBuilder {
+    pub fn decay_rate(mut self, rate: f32) -> Self {
+        self.config.decay_rate = Some(rate);
+        self
+    }
+
+    pub fn stability_cap(mut self, cap: f32) -> Self {
+        self.config.dynamics.stability_cap = cap;
+        self
+    }
+
+    pub fn spread_factor(mut self, factor: f32) -> Self {
+        self.config.dynamics.spread_factor = factor;
+        self
+    }
+
+    pub fn max_hops(mut self, hops: u8) -> Self {
+        self.config.dynamics.max_hops = hops;
+        self
+    }
+
+    /// Faded nodes (activation below `activation`) with less than `stability` are pruned by dreams.
+    pub fn prune_thresholds(mut self, stability: f32, activation: f32) -> Self {
+        self.config.dynamics.prune_stability = stability;
+        self.config.dynamics.prune_activation = activation;
+        self
+    }
+
+    pub fn seconds_per_tick(mut self, secs: u64) -> Self {
+        self.config.dynamics.seconds_per_tick = secs;
+        self
+    }
+
+    /// `seconds_per_tick` from a duration (whole seconds, e.g. `Duration::from_secs(3600)` for hourly ticks).
+    pub fn tick_duration(self, duration: std::time::Duration) -> Self {
+        self.seconds_per_tick(duration.as_secs())
+    }
+
+    pub fn clock(mut self, mode: ClockMode) -> Self {
+        self.config.dynamics.clock = mode;
+        self
+    }
+
+    pub fn node_defaults(mut self, kind: NodeKind, defaults: NodeDefaults) -> Self {
+        self.config.kinds.insert(kind, defaults);
+        self
+    }
+
+    /// Any other tunable, e.g. `.with(|c| c.cite_sources = true)`.
+    pub fn with(mut self, f: impl FnOnce(&mut LoomConfig)) -> Self {
+        f(&mut self.config);
+        self
+    }
+
+    pub fn build(self) -> Result<LoomConfig, ConfigError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+/// Recursively overlays `patch` onto `base` (objects merge, everything else
+/// replaces). Used by the JS bindings' partial `set_config`.
+pub fn merge_json(base: &mut serde_json::Value, patch: &serde_json::Value) {
+    match (base, patch) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge_json(base.entry(key.clone()).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (base, patch) => *base = patch.clone(),
+    }
+}
+
+impl LoomGraph {
+    /// A fresh graph tuned by `config` (decay rate defaults to `DEFAULT_DECAY_RATE`).
+    pub fn with_config(config: LoomConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+        let mut graph = LoomGraph::new(config.decay_rate.unwrap_or(DEFAULT_DECAY_RATE));
+        graph.config = LoomConfig { decay_rate: None, ..config };
+        Ok(graph)
+    }
+
+    /// Graph-wide decay rate as it applies to `kind` (`decay_rate ^ decay_multiplier`).
+    pub fn kind_decay_rate(&self, kind: NodeKind) -> f32 {
+        match self.config.kinds.get(&kind) {
+            Some(defaults) => self.decay_rate.powf(defaults.decay_multiplier),
+            None => self.decay_rate,
+        }
+    }
+
+    pub(crate) fn decay_rates(&self) -> DecayRates {
+        DecayRates::new(self.decay_rate, &self.config.kinds)
+    }
+
+    /// Current configuration, with `decay_rate` filled in.
+    pub fn config(&self) -> LoomConfig {
+        LoomConfig { decay_rate: Some(self.decay_rate), ..self.config.clone() }
+    }
+
+    /// Replaces the configuration after validating it; the old one is kept on error.
+    /// A `decay_rate` is applied through `set_decay_rate`. Creation-time fields
+    /// (`capacity`, `archive_tier`, `journal_capacity`, `dynamics.clock`) must
+    /// stay as they are, or `ConfigError::Immutable` is returned.
+    pub fn set_config(&mut self, config: LoomConfig) -> Result<(), ConfigError> {
+        config.validate()?;
+        let current = &self.config;
+        if config.capacity != current.capacity {
+            return Err(ConfigError::Immutable("capacity"));
+        }
+        if config.archive_tier != current.archive_tier {
+            return Err(ConfigError::Immutable("archive_tier"));
+        }
+        if config.journal_capacity != current.journal_capacity {
+            return Err(ConfigError::Immutable("journal_capacity"));
+        }
+        if config.dynamics.clock != current.dynamics.clock {
+            return Err(ConfigError::Immutable("dynamics.clock"));
+        }
+        if config.kinds != self.config.kinds {
+            // Decaimento pendente fecha com os multiplicadores antigos
+            self.settle_decay();
+        }
+        if let Some(rate) = config.decay_rate {
+            self.set_decay_rate(rate)?;
+        }
+        // A taxa viva fica em `self.decay_rate`; não duplica no config salvo
+        self.config = LoomConfig { decay_rate: None, ..config };
+        self.record_config();
+        Ok(())
+    }
+
+    /// Logs the whole configuration as `ConfigSet` (or just marks the graph dirty).
+    pub(crate) fn record_config(&mut self) {
+        if self.is_recording() {
+            self.record(GraphEvent::ConfigSet { config: Box::new(self.config()) });
+        } else {
+            self.dirty += 1;
+        }
+    }
+
+    /// Changes the graph-wide decay rate at runtime. Pending decay is settled
+    /// at the old rate first, so the new rate only applies from now on.
+    pub fn set_decay_rate(&mut self, rate: f32) -> Result<(), ConfigError> {
+        check("decay_rate", rate as f64, rate > 0.0 && rate <= 1.0, "0 < rate <= 1")?;
+        self.settle_decay();
+        self.decay_rate = rate;
+        self.record(GraphEvent::DecayRateSet { rate });
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+//! # Consolidation (Sleep Replay)
+//! A richer sleep phase than `dream()`:
+//!
+//! 1. **Replay**: the most important recent episodes (activation weighted by
+//!    emotional charge) are re-boosted.
+//! 2. **Transfer**: each replayed episode hands part of its activation, and
+//!    some stability, to the concepts it `Mentioned` (episodic -> semantic).
+//! 3. **Hebbian strengthening**: edges whose endpoints are both active get
+//!    a little heavier.
+//! 4. **Dampening**: everything else loses an extra share of activation.
+//! 5. **Inference**: registered `InferenceRule`s add derived edges.
+//!
+//! The pass is recorded as one event and is deterministic, so the timeline
+//! can replay it.
+
+use crate::{DecayOverride, GraphEvent, LoomGraph, Node, NodeId, MENTIONED_EDGE};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConsolidationConfig {
+    /// How many episodes to replay.
+    pub replay_count: usize,
+    /// Only episodes touched within this many ticks count as recent.
+    pub recent_ticks: u64,
+    pub replay_boost: f32,
+    /// Share of a replayed episode's activation moved to each mentioned concept.
+    pub transfer_rate: f32,
+    /// Both endpoints need at least this activation for an edge to strengthen.
+    pub coactivation_threshold: f32,
+    /// Weight added to co-activated edges (capped at 1).
+    pub strengthen: f32,
+    /// Extra share of activation removed from untouched memories.
+    pub dampen: f32,
+    /// Smallest episode cluster handed to a `Summarizer` (see `consolidate_with`).
+    pub min_cluster_size: usize,
+    /// Most concepts created per pass.
+    pub max_abstractions: usize,
+    /// Weight of the `Evoked` edges from a new concept to its episodes.
+    pub evoked_weight: f32,
+}
+
+impl Default for ConsolidationConfig {
+    fn default() -> Self {
+        Self {
+            replay_count: 10,
+            recent_ticks: 480,
+            replay_boost: 0.3,
+            transfer_rate: 0.2,
+            coactivation_threshold: 0.5,
+            strengthen: 0.05,
+            dampen: 0.3,
+            min_cluster_size: 3,
+            max_abstractions: 5,
+            evoked_weight: 0.6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConsolidationReport {
+    pub replayed: Vec<NodeId>,
+    pub concepts_reinforced: usize,
+    pub edges_strengthened: usize,
+    pub dampened: usize,
+    /// Concepts created from episode clusters (only with `consolidate_with`).
+    pub abstracted: Vec<NodeId>,
+    /// Edges derived by inference rules.
+    pub edges_inferred: usize,
+}
+
+impl LoomGraph {
+    pub fn consolidate(&mut self, config: &ConsolidationConfig) -> ConsolidationReport {
+        self.catch_up_clock();
+        self.record(GraphEvent::Consolidated { config: config.clone() });
+        let report = self.consolidation_pass(config);
+        self.journal.clear();
+        self.maybe_autosave();
+        report
+    }
+
+    pub(crate) fn consolidation_pass(&mut self, config: &ConsolidationConfig) -> ConsolidationReport {
+        let (tick, rates, cap) = (self.current_tick, self.decay_rates(), self.config.dynamics.stability_cap);
+        let charge = self.emotional_charge();
+        let arousal = self.episode_arousal();
+        let mut report = ConsolidationReport::default();
+
+        // 1. Replay: episódios recentes mais importantes
+        let mut candidates: Vec<(NodeId, f32)> = self.nodes.values()
+            .filter(|node| matches!(node, Node::Episode(..)))
+            .map(|node| node.meta())
+            .filter(|meta| tick.saturating_sub(meta.last_tick) <= config.recent_ticks)
+            .map(|meta| {
+                let weight = 1.0 + charge.get(&meta.id).copied().unwrap_or(0.0);
+                (meta.id, self.projected_activation(meta, tick) * weight)
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        candidates.truncate(config.replay_count);
+
+        let mut touched: HashSet<NodeId> = HashSet::new();
+        for (id, _) in &candidates {
+            let factor = self.flashbulb_factor(arousal.get(id).copied().unwrap_or(0.0));
+            let Some(node) = self.nodes.get_mut(id) else { continue };
+            let rate = rates.of(node.kind());
+            let meta = node.meta_mut();
+            let stability = meta.stability;
+            Self::boost_meta(meta, config.replay_boost, tick, rate);
+            // Memórias flashbulb ganham estabilidade multiplicada
+            meta.stability = (stability + (meta.stability - stability) * factor).min(cap);
+            report.replayed.push(*id);
+            touched.insert(*id);
+        }
+
+        // 2. Transferência episódio -> conceito
+        let mut reinforced: HashSet<NodeId> = HashSet::new();
+        for id in &report.replayed {
+            let mut concepts: Vec<NodeId> = self.adjacency.get(id).into_iter().flatten()
+                .filter(|e| e.edge_type == MENTIONED_EDGE)
+                .map(|e| e.target)
+                .filter(|target| matches!(self.nodes.get(target), Some(Node::Concept(..))))
+                .collect();
+            concepts.sort();
+            concepts.dedup();
+            if concepts.is_empty() {
+                continue;
+            }
+            let (activation, stability) = match self.nodes.get(id) {
+                Some(node) => (node.meta().activation, node.meta().stability),
+                None => continue,
+            };
+            let share = activation * config.transfer_rate;
+            for concept in &concepts {
+                if let Some(node) = self.nodes.get_mut(concept) {
+                    let rate = rates.of(node.kind());
+                    let meta = node.meta_mut();
+                    Self::boost_meta(meta, share, tick, rate);
+                    meta.stability = (meta.stability + stability * config.transfer_rate).min(cap);
+                    reinforced.insert(*concept);
+                    touched.insert(*concept);
+                }
+            }
+            if let Some(node) = self.nodes.get_mut(id) {
+                let meta = node.meta_mut();
+                meta.activation = meta.floored((meta.activation - share).max(0.0));
+            }
+        }
+        report.concepts_reinforced = reinforced.len();
+
+        // 3. Hebbiano: arestas entre nós co-ativados ficam mais fortes
+        let active: HashSet<NodeId> = self.nodes.values()
+            .filter(|node| self.projected_activation(node.meta(), tick) >= config.coactivation_threshold)
+            .map(|node| node.meta().id)
+            .collect();
+        for (source, edges) in self.adjacency.iter_mut() {
+            if !active.contains(source) {
+                continue;
+            }
+            for edge in edges.iter_mut().filter(|e| e.weight > 0.0 && active.contains(&e.target)) {
+                edge.weight = (edge.weight + config.strengthen).min(1.0);
+                report.edges_strengthened += 1;
+            }
+        }
+
+        // 4. O resto decai mais forte
+        for node in self.nodes.values_mut() {
+            let rate = rates.of(node.kind());
+            let meta = node.meta_mut();
+            if touched.contains(&meta.id) || meta.always_in_context || meta.decay_override == Some(DecayOverride::Never) {
+                continue;
+            }
+            meta.activation = meta.floored(Self::decay_projection(meta, tick, rate) * (1.0 - config.dampen));
+            meta.last_tick = tick;
+            report.dampened += 1;
+        }
+
+        // 5. Regras de inferência
+        report.edges_inferred = self.apply_rules();
+
+        if self.config.history.enabled {
+            let ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+            self.sample_activation(ids);
+        }
+        self.dirty += 1;
+        report
+    }
+}
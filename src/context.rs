@@ -0,0 +1,471 @@
+//! # Context Generation
+//! Prompt assembly helpers layered on top of `get_context_prompt`.
+//!
+//! Token counting is pluggable: the default `CharEstimator` uses the usual
+//! "~4 chars per token" heuristic, and any `Fn(&str) -> usize` (e.g. a
+//! tiktoken binding) can be passed instead.
+
+use crate::{LoomGraph, Node, NodeKind};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+pub trait TokenEstimator {
+    fn estimate(&self, text: &str) -> usize;
+}
+
+impl<F: Fn(&str) -> usize> TokenEstimator for F {
+    fn estimate(&self, text: &str) -> usize {
+        self(text)
+    }
+}
+
+/// Heuristic estimator: one token per `chars_per_token` characters, rounded up.
+#[derive(Debug, Clone, Copy)]
+pub struct CharEstimator {
+    pub chars_per_token: usize,
+}
+
+impl Default for CharEstimator {
+    fn default() -> Self {
+        Self { chars_per_token: 4 }
+    }
+}
+
+impl TokenEstimator for CharEstimator {
+    fn estimate(&self, text: &str) -> usize {
+        text.chars().count().div_ceil(self.chars_per_token.max(1))
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContextFormat {
+    Xml,
+    Json,
+    Markdown,
+    OpenAiMessages,
+}
+
+/// How `get_context_messages_with` splits memories into chat messages.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MessageGrouping {
+    /// One system message holding the Markdown context.
+    #[default]
+    Aggregated,
+    /// One system message per memory, in context order.
+    PerMemory,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+    /// When the turn happened; only used by `ingest_conversation`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl ChatMessage {
+    pub fn new(role: impl Into<String>, content: impl Into<String>) -> Self {
+        Self { role: role.into(), content: content.into(), timestamp: None }
+    }
+}
+
+const OPEN_TAG: &str = "<active_memories>\n";
+const CLOSE_TAG: &str = "</active_memories>";
+const EMPTY_MEMORY: &str = "  <memory>No relevant active memories.</memory>\n";
+
+impl LoomGraph {
+    /// Same XML as `get_context_prompt`, but memories are added in activation
+    /// order only while the whole prompt stays within `max_tokens`. A budget
+    /// too small for the wrapper tags yields an empty string.
+    pub fn get_context_budgeted_with(
+        &self,
+        min_activation: f32,
+        max_tokens: usize,
+        estimator: &dyn TokenEstimator,
+    ) -> String {
+        let mut body = String::new();
+        let mut used = estimator.estimate(OPEN_TAG) + estimator.estimate(CLOSE_TAG);
+        if used > max_tokens {
+            return String::new();
+        }
+
+        for node in self.active_nodes(min_activation) {
+            let memory = self.render_memory_xml(node);
+            let cost = estimator.estimate(&memory);
+            if used + cost > max_tokens {
+                break; // Orçamento esgotado
+            }
+            used += cost;
+            body.push_str(&memory);
+        }
+
+        if body.is_empty() && used + estimator.estimate(EMPTY_MEMORY) <= max_tokens {
+            body.push_str(EMPTY_MEMORY);
+        }
+        format!("{}{}{}", OPEN_TAG, body, CLOSE_TAG)
+    }
+
+    pub fn get_context_as(&self, format: ContextFormat, min_activation: f32) -> String {
+        self.render_context(format, &self.active_nodes(min_activation))
+    }
+
+    /// Active memories as `system` chat messages, ready to splice into an
+    /// OpenAI/Anthropic `messages` array.
+    pub fn get_context_messages(&self, min_activation: f32) -> Vec<ChatMessage> {
+        self.get_context_messages_with(min_activation, MessageGrouping::Aggregated)
+    }
+
+    /// Like `get_context_messages`, with `PerMemory` emitting one message per
+    /// memory (no message at all when nothing is active).
+    pub fn get_context_messages_with(&self, min_activation: f32, grouping: MessageGrouping) -> Vec<ChatMessage> {
+        self.render_messages(&self.active_nodes(min_activation), grouping)
+    }
+
+    pub fn context_builder(&self) -> ContextBuilder<'_> {
+        ContextBuilder::new(self)
+    }
+
+    fn render_context(&self, format: ContextFormat, nodes: &[&Node]) -> String {
+        match format {
+            ContextFormat::Xml => {
+                let body: String = nodes.iter().map(|n| self.render_memory_xml(n)).collect();
+                let body = if body.is_empty() { EMPTY_MEMORY } else { &body };
+                format!("{}{}{}", OPEN_TAG, body, CLOSE_TAG)
+            }
+            ContextFormat::Json => {
+                let entries: Vec<_> = nodes.iter().map(|n| self.render_memory_json(n)).collect();
+                serde_json::to_string(&entries).unwrap_or("[]".to_string())
+            }
+            ContextFormat::Markdown => self.render_markdown(nodes),
+            ContextFormat::OpenAiMessages => {
+                let messages = self.render_messages(nodes, MessageGrouping::Aggregated);
+                serde_json::to_string(&messages).unwrap_or("[]".to_string())
+            }
+        }
+    }
+
+    fn render_messages(&self, nodes: &[&Node], grouping: MessageGrouping) -> Vec<ChatMessage> {
+        match grouping {
+            MessageGrouping::Aggregated => vec![ChatMessage::new("system", self.render_markdown(nodes))],
+            MessageGrouping::PerMemory => nodes.iter()
+                .map(|n| ChatMessage::new("system", self.render_memory_markdown(n)))
+                .collect(),
+        }
+    }
+
+    /// Applies the custom sanitizer hook (if any), then the configured
+    /// control-character stripping and per-memory length cap.
+    pub fn clean_text(&self, text: &str) -> String {
+        let cfg = &self.config.sanitizer;
+        let mut out = match &self.sanitizer_hook {
+            Some(hook) => hook(text),
+            None => text.to_string(),
+        };
+        if cfg.strip_control_chars {
+            out.retain(|c| !c.is_control() || c == '\n' || c == '\t');
+        }
+        if let Some(max) = cfg.max_memory_chars {
+            if out.chars().count() > max {
+                out = out.chars().take(max).collect::<String>() + "…";
+            }
+        }
+        out
+    }
+
+    pub fn set_sanitizer_hook(&mut self, hook: impl Fn(&str) -> String + Send + Sync + 'static) {
+        self.sanitizer_hook = Some(Arc::new(hook));
+    }
+
+    pub fn clear_sanitizer_hook(&mut self) {
+        self.sanitizer_hook = None;
+    }
+
+    fn render_memory_json(&self, node: &Node) -> serde_json::Value {
+        let meta = node.meta();
+        let mut entry = match node {
+            Node::Concept(_, d) if d.aliases.is_empty() => json!({ "type": "concept", "name": self.clean_text(&d.name), "definition": self.clean_text(&d.definition) }),
+            Node::Concept(_, d) => json!({
+                "type": "concept",
+                "name": self.clean_text(&d.name),
+                "aliases": d.aliases.iter().map(|a| self.clean_text(a)).collect::<Vec<_>>(),
+                "definition": self.clean_text(&d.definition),
+            }),
+            Node::Episode(_, d) if d.attachments.is_empty() => json!({ "type": "episode", "summary": self.clean_text(&d.summary), "time": d.timestamp.to_rfc3339() }),
+            Node::Episode(_, d) => json!({
+                "type": "episode",
+                "summary": self.clean_text(&d.summary),
+                "time": d.timestamp.to_rfc3339(),
+                "attachments": d.attachments.iter()
+                    .map(|a| json!({ "uri": self.clean_text(&a.uri), "mime_type": a.mime_type }))
+                    .collect::<Vec<_>>(),
+            }),
+            Node::State(_, d) => {
+                let (valence, arousal) = self.state_values(d);
+                json!({ "type": "state", "label": self.state_label(d), "valence": valence, "arousal": arousal })
+            }
+            Node::Goal(_, d) => json!({
+                "type": "goal",
+                "description": self.clean_text(&d.description),
+                "due": d.due.to_string(),
+                "priority": d.priority,
+                "completed": d.completed,
+            }),
+            Node::Entity(_, d) => json!({
+                "type": "entity",
+                "kind": d.kind.to_string(),
+                "name": self.clean_text(&d.name),
+                "attributes": d.attributes.iter()
+                    .map(|(k, v)| (k.clone(), json!(self.clean_text(v))))
+                    .collect::<serde_json::Map<_, _>>(),
+            }),
+            Node::Custom(_, d) => {
+                let mut payload = d.clone();
+                payload.map_strings(&|s| self.clean_text(s));
+                json!({ "type": "custom", "type_name": d.type_name, "payload": payload.payload })
+            }
+            Node::Skill(_, d) => json!({
+                "type": "skill",
+                "name": self.clean_text(&d.name),
+                "description": self.clean_text(&d.description),
+                "steps": d.steps.iter().map(|s| self.clean_text(s)).collect::<Vec<_>>(),
+                "attempts": d.attempts,
+                "success_rate": d.success_rate(),
+            }),
+            Node::Observation(_, d) => json!({
+                "type": "observation",
+                "tool": self.clean_text(&d.tool),
+                "input": self.clean_text(&d.input),
+                "output": self.clean_text(&d.output),
+                "time": d.timestamp.to_rfc3339(),
+            }),
+        };
+        entry["id"] = json!(meta.id);
+        entry["activation"] = json!(meta.activation);
+        entry["stability"] = json!(meta.stability);
+        if meta.always_in_context || meta.is_pinned() {
+            entry["pinned"] = json!(true);
+        }
+        if let Some(source) = meta.source.as_ref().filter(|_| self.config.cite_sources) {
+            entry["source"] = json!({ "origin": source.origin.to_string(), "reference": self.clean_text(&source.reference) });
+        }
+        entry
+    }
+
+    fn render_markdown(&self, nodes: &[&Node]) -> String {
+        let mut buffer = String::from("## Active Memories\n");
+        if nodes.is_empty() {
+            buffer.push_str("_No relevant active memories._\n");
+        }
+        for node in nodes {
+            buffer.push_str(&format!("- {}\n", self.render_memory_markdown(node)));
+        }
+        buffer
+    }
+
+    /// One memory as a Markdown list item body (no leading `- `).
+    fn render_memory_markdown(&self, node: &Node) -> String {
+        let meta = node.meta();
+        let line = match node {
+            Node::Concept(_, d) if d.aliases.is_empty() => format!("**{}**: {}", self.clean_text(&d.name), self.clean_text(&d.definition)),
+            Node::Concept(_, d) => format!(
+                "**{}** (aka {}): {}",
+                self.clean_text(&d.name), self.clean_text(&d.aliases.join(", ")), self.clean_text(&d.definition)
+            ),
+            Node::Episode(_, d) if d.attachments.is_empty() => format!("[{}] {}", d.timestamp.to_rfc3339(), self.clean_text(&d.summary)),
+            Node::Episode(_, d) => {
+                let links: Vec<String> = d.attachments.iter()
+                    .map(|a| format!("[{}]({})", a.mime_type, self.clean_text(&a.uri)))
+                    .collect();
+                format!("[{}] {} ({})", d.timestamp.to_rfc3339(), self.clean_text(&d.summary), links.join(", "))
+            }
+            Node::State(_, d) => {
+                let (valence, arousal) = self.state_values(d);
+                match self.state_label(d) {
+                    Some(label) => format!("Mood: {} (valence {:.2}, arousal {:.2})", self.clean_text(label), valence, arousal),
+                    None => format!("Mood: valence {:.2}, arousal {:.2}", valence, arousal),
+                }
+            }
+            Node::Goal(_, d) => format!(
+                "Goal{}: {} (due {}, priority {:.2})",
+                if d.completed { " (done)" } else { "" },
+                self.clean_text(&d.description), d.due, d.priority
+            ),
+            Node::Entity(_, d) => {
+                let attributes: Vec<String> = d.attributes.iter()
+                    .map(|(k, v)| format!("{}: {}", k, self.clean_text(v)))
+                    .collect();
+                if attributes.is_empty() {
+                    format!("{} **{}**", d.kind, self.clean_text(&d.name))
+                } else {
+                    format!("{} **{}** ({})", d.kind, self.clean_text(&d.name), attributes.join(", "))
+                }
+            }
+            Node::Custom(_, d) => format!(
+                "[{}] {}",
+                self.clean_text(&d.type_name), self.clean_text(&d.payload_text())
+            ),
+            Node::Skill(_, d) => format!(
+                "Skill **{}**: {} ({} attempts, {:.0}% success)",
+                self.clean_text(&d.name), self.clean_text(&d.description), d.attempts, d.success_rate() * 100.0
+            ),
+            Node::Observation(_, d) => format!(
+                "Tool `{}` ({}) → {}",
+                self.clean_text(&d.tool), self.clean_text(&d.input), self.clean_text(&d.output)
+            ),
+        };
+        let marker = if meta.always_in_context || meta.is_pinned() { " 📌" } else { "" };
+        let source = match &meta.source {
+            Some(source) if self.config.cite_sources => format!(" [source: {} {}]", source.origin, self.clean_text(&source.reference)),
+            _ => String::new(),
+        };
+        format!("{}{}{} _(activation {:.2})_", line, marker, source, meta.activation)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortStrategy {
+    Activation,
+    Stability,
+    /// Timestamped nodes (episodes, observations) newest first; untimed nodes follow by activation.
+    NewestFirst,
+    OldestFirst,
+    /// Composite importance (activation, stability, centrality, access); see `LoomGraph::importance`.
+    Importance,
+}
+
+/// Fine-grained context selection: per-kind quotas, time window, activation
+/// threshold and ordering, rendered in any `ContextFormat`.
+pub struct ContextBuilder<'a> {
+    graph: &'a LoomGraph,
+    quotas: HashMap<NodeKind, usize>,
+    min_activation: f32,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    sort: SortStrategy,
+    format: ContextFormat,
+    namespace: Option<String>,
+}
+
+impl<'a> ContextBuilder<'a> {
+    pub fn new(graph: &'a LoomGraph) -> Self {
+        Self {
+            graph,
+            quotas: HashMap::new(),
+            min_activation: 0.0,
+            since: None,
+            until: None,
+            sort: SortStrategy::Activation,
+            format: ContextFormat::Xml,
+            namespace: None,
+        }
+    }
+
+    /// Caps how many nodes of `kind` are included. Kinds without a quota are unlimited;
+    /// a quota of 0 excludes the kind entirely.
+    pub fn quota(mut self, kind: NodeKind, max: usize) -> Self {
+        self.quotas.insert(kind, max);
+        self
+    }
+
+    pub fn min_activation(mut self, threshold: f32) -> Self {
+        self.min_activation = threshold;
+        self
+    }
+
+    /// Restricts timestamped nodes to `[since, until]`. Untimed nodes are unaffected.
+    pub fn time_range(mut self, since: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>) -> Self {
+        self.since = since;
+        self.until = until;
+        self
+    }
+
+    pub fn sort_by(mut self, strategy: SortStrategy) -> Self {
+        self.sort = strategy;
+        self
+    }
+
+    pub fn format(mut self, format: ContextFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Only nodes in `namespace` (pinned nodes included).
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    pub fn select(&self) -> Vec<&'a Node> {
+        // Decaimento preguiçoso: filtra e ordena pela ativação projetada
+        let mut nodes: Vec<&Node> = self.graph.ranked_nodes(|n, activation| {
+            activation > self.min_activation && !n.meta().always_in_context
+                && self.in_time_range(n) && !self.graph.is_forgotten(n.meta().id)
+        });
+
+        if self.sort == SortStrategy::Importance {
+            let scores = self.graph.importance_scores();
+            let score = |n: &Node| scores.get(&n.meta().id).copied().unwrap_or(0.0);
+            nodes.sort_by(|a, b| score(b).total_cmp(&score(a)));
+        } else {
+            nodes.sort_by(|a, b| self.compare(a, b));
+        }
+
+        let mut pinned = self.graph.context_pinned_nodes();
+        pinned.append(&mut nodes);
+        let mut nodes = pinned;
+        if let Some(ns) = &self.namespace {
+            nodes.retain(|n| n.meta().namespace.as_ref() == Some(ns));
+        }
+
+        let mut taken: HashMap<NodeKind, usize> = HashMap::new();
+        nodes.retain(|n| {
+            let kind = n.kind();
+            let count = taken.entry(kind).or_insert(0);
+            match self.quotas.get(&kind) {
+                Some(max) if *count >= *max => false,
+                _ => {
+                    *count += 1;
+                    true
+                }
+            }
+        });
+        nodes
+    }
+
+    pub fn build(&self) -> String {
+        self.graph.render_context(self.format, &self.select())
+    }
+
+    fn in_time_range(&self, node: &Node) -> bool {
+        let Some(time) = node.timestamp() else { return true };
+        self.since.is_none_or(|since| time >= since)
+            && self.until.is_none_or(|until| time <= until)
+    }
+
+    fn compare(&self, a: &Node, b: &Node) -> Ordering {
+        // `select` já entrega os nós pela ativação projetada; o sort estável preserva essa ordem
+        let by_activation = || Ordering::Equal;
+        match self.sort {
+            SortStrategy::Activation | SortStrategy::Importance => by_activation(),
+            SortStrategy::Stability => b.meta().stability.partial_cmp(&a.meta().stability).unwrap_or(Ordering::Equal),
+            SortStrategy::NewestFirst | SortStrategy::OldestFirst => match (a.timestamp(), b.timestamp()) {
+                (Some(ta), Some(tb)) if self.sort == SortStrategy::NewestFirst => {
+                    tb.cmp(&ta).then(b.meta().seq.cmp(&a.meta().seq))
+                }
+                (Some(ta), Some(tb)) => ta.cmp(&tb).then(a.meta().seq.cmp(&b.meta().seq)),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => by_activation(),
+            },
+        }
+    }
+}
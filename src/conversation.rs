@@ -0,0 +1,108 @@
+//! # Conversation Ingestion
+//! Turns a chat transcript into memory: one episode per turn ("role: text"),
+//! `Preceded` edges from each turn to the next, `Mentioned` edges to the
+//! concepts each turn names and, optionally, a detected `State` per segment
+//! of `config.conversation.segment_turns` turns.
+//!
+//! Emotion detection is a small keyword lexicon: good enough to tag clearly
+//! upset or happy stretches, not a sentiment model.
+
+use crate::{tokenize, ChatMessage, Connection, EpisodeData, LoomGraph, Node, NodeId};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+pub const PRECEDED_EDGE: &str = "Preceded";
+pub const FELT_EDGE: &str = "Felt";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IngestReport {
+    /// One episode per message, in transcript order.
+    pub episodes: Vec<NodeId>,
+    /// Concepts that got at least one `Mentioned` edge.
+    pub mentioned: Vec<NodeId>,
+    pub states: Vec<NodeId>,
+}
+
+// (palavra, valência, excitação)
+const LEXICON: &[(&str, f32, f32)] = &[
+    ("love", 0.9, 0.6), ("great", 0.7, 0.5), ("thanks", 0.5, 0.2), ("thank", 0.5, 0.2),
+    ("happy", 0.8, 0.5), ("awesome", 0.8, 0.7), ("perfect", 0.7, 0.4), ("glad", 0.6, 0.3),
+    ("excited", 0.7, 0.9), ("nice", 0.5, 0.3), ("works", 0.4, 0.2), ("calm", 0.3, -0.5),
+    ("hate", -0.9, 0.7), ("angry", -0.8, 0.9), ("furious", -0.9, 1.0), ("annoyed", -0.6, 0.6),
+    ("frustrated", -0.7, 0.7), ("sad", -0.7, -0.3), ("sorry", -0.3, 0.1), ("worried", -0.5, 0.6),
+    ("afraid", -0.7, 0.8), ("broken", -0.5, 0.4), ("wrong", -0.4, 0.3), ("terrible", -0.8, 0.6),
+    ("urgent", -0.2, 0.9), ("tired", -0.4, -0.6), ("bored", -0.3, -0.7), ("bug", -0.3, 0.3),
+];
+
+/// Mean `(valence, arousal)` of the lexicon words in `text`, or None if none occur.
+pub fn detect_emotion(text: &str) -> Option<(f32, f32)> {
+    let hits: Vec<(f32, f32)> = tokenize(text).iter()
+        .filter_map(|word| LEXICON.iter().find(|(w, _, _)| w == word).map(|(_, v, a)| (*v, *a)))
+        .collect();
+    if hits.is_empty() {
+        return None;
+    }
+    let n = hits.len() as f32;
+    let (v, a) = hits.iter().fold((0.0, 0.0), |(v, a), (hv, ha)| (v + hv, a + ha));
+    Some((v / n, a / n))
+}
+
+impl LoomGraph {
+    pub fn ingest_conversation(&mut self, messages: &[ChatMessage]) -> IngestReport {
+        let cfg = self.config.conversation.clone();
+        let now = self.now();
+        let nodes = messages.iter().enumerate()
+            .map(|(i, msg)| Node::Episode(self.episode_meta(), EpisodeData {
+                summary: format!("{}: {}", msg.role, msg.content),
+                timestamp: msg.timestamp.unwrap_or(now + Duration::microseconds(i as i64)),
+                confidence: None,
+                attachments: Vec::new(),
+            }))
+            .collect();
+        let episodes = self.add_nodes_batch(nodes);
+
+        for pair in episodes.windows(2) {
+            self.store_edge(pair[0], Connection {
+                target: pair[1],
+                weight: cfg.chain_weight,
+                edge_type: PRECEDED_EDGE.to_string(),
+                inferred: false,
+            });
+        }
+        self.chain_session(&episodes);
+
+        let mut mentioned = Vec::new();
+        if cfg.link_mentions {
+            for id in &episodes {
+                mentioned.extend(self.link_mentions(*id));
+            }
+            mentioned.sort();
+            mentioned.dedup();
+        }
+
+        let mut states = Vec::new();
+        if cfg.detect_emotion {
+            for (turns, ids) in messages.chunks(cfg.segment_turns.max(1)).zip(episodes.chunks(cfg.segment_turns.max(1))) {
+                let text: Vec<&str> = turns.iter().map(|m| m.content.as_str()).collect();
+                let Some((valence, arousal)) = detect_emotion(&text.join(" ")) else { continue };
+                if valence.abs().max(arousal.abs()) < cfg.min_emotion_intensity {
+                    continue;
+                }
+                let state = self.add_state(valence, arousal);
+                for id in ids {
+                    self.store_edge(*id, Connection {
+                        target: state,
+                        weight: valence.abs().max(arousal.abs()),
+                        edge_type: FELT_EDGE.to_string(),
+                        inferred: false,
+                    });
+                    self.imprint_arousal(*id, arousal);
+                }
+                states.push(state);
+            }
+        }
+
+        self.maybe_autosave();
+        IngestReport { episodes, mentioned, states }
+    }
+}
@@ -0,0 +1,56 @@
+//! # Custom Nodes
+//! Domain-specific memory types for downstream apps. The payload is opaque
+//! JSON; the node still decays, spreads activation and is searchable through
+//! the string values inside its payload.
+
+use crate::{CustomData, LoomGraph, Node, NodeId, NodeKind};
+use serde_json::Value;
+
+impl CustomData {
+    /// All string values in the payload (keys excluded), space-separated.
+    pub fn payload_text(&self) -> String {
+        fn collect<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+            match value {
+                Value::String(s) => out.push(s),
+                Value::Array(items) => items.iter().for_each(|v| collect(v, out)),
+                Value::Object(map) => map.values().for_each(|v| collect(v, out)),
+                _ => {}
+            }
+        }
+        let mut out = Vec::new();
+        collect(&self.payload, &mut out);
+        out.join(" ")
+    }
+
+    /// Rewrites every string value in the payload (anonymization, sanitizers).
+    pub(crate) fn map_strings(&mut self, f: &dyn Fn(&str) -> String) {
+        fn walk(value: &mut Value, f: &dyn Fn(&str) -> String) {
+            match value {
+                Value::String(s) => *s = f(s),
+                Value::Array(items) => items.iter_mut().for_each(|v| walk(v, f)),
+                Value::Object(map) => map.values_mut().for_each(|v| walk(v, f)),
+                _ => {}
+            }
+        }
+        walk(&mut self.payload, f);
+    }
+}
+
+impl LoomGraph {
+    pub fn add_custom(&mut self, type_name: String, payload: Value) -> NodeId {
+        let node = Node::Custom(self.new_meta(NodeKind::Custom), CustomData { type_name, payload });
+        let id = self.add_node_internal(node);
+        self.enforce_capacity(&[id]);
+        id
+    }
+
+    /// Ids of the custom nodes of type `type_name`, most active first.
+    pub fn custom_nodes(&self, type_name: &str) -> Vec<NodeId> {
+        let mut found: Vec<(NodeId, f32)> = self.nodes.iter()
+            .filter(|(id, node)| !self.is_forgotten(**id) && matches!(node, Node::Custom(_, d) if d.type_name == type_name))
+            .map(|(id, node)| (*id, node.meta().activation))
+            .collect();
+        found.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        found.into_iter().map(|(id, _)| id).collect()
+    }
+}
@@ -0,0 +1,197 @@
+//! # Deduplication
+//! Keeps one node per real-world thing: `upsert_concept` reuses a concept
+//! with the same normalized name, `find_duplicates` lists leftovers and
+//! `merge_nodes` folds one node into another (edges, metadata and text).
+
+use crate::{tokenize, Connection, GraphEvent, LoomGraph, Node, NodeId};
+use std::collections::BTreeMap;
+
+impl LoomGraph {
+    /// The concept named `name` (case- and punctuation-insensitive), falling
+    /// back to one with `name` as an alias; with several, the oldest one.
+    pub fn find_concept_by_name(&self, name: &str) -> Option<NodeId> {
+        let wanted = tokenize(name);
+        if wanted.is_empty() {
+            return None;
+        }
+        let oldest = |by_alias: bool| self.nodes.iter()
+            .filter(|(id, _)| !self.is_forgotten(**id))
+            .filter(|(_, node)| match node {
+                Node::Concept(_, d) if by_alias => d.aliases.iter().any(|a| tokenize(a) == wanted),
+                Node::Concept(_, d) => tokenize(&d.name) == wanted,
+                _ => false,
+            })
+            .min_by_key(|(id, node)| (node.meta().seq, **id))
+            .map(|(id, _)| *id);
+        oldest(false).or_else(|| oldest(true))
+    }
+
+    /// `add_concept` unless a concept with the same normalized name exists, in
+    /// which case its definition is merged with `definition`, it gets
+    /// `config.upsert_boost` and its id is returned.
+    pub fn upsert_concept(&mut self, name: String, definition: String) -> NodeId {
+        let Some(id) = self.find_concept_by_name(&name) else {
+            return self.add_concept(name, definition);
+        };
+        if let Some(Node::Concept(_, data)) = self.nodes.get(&id) {
+            let merged = merge_text(&data.definition, &definition);
+            if merged != data.definition {
+                self.rewrite_node(id, |node| {
+                    if let Node::Concept(_, data) = node {
+                        data.definition = merged;
+                    }
+                });
+            }
+        }
+        self.boost_node(id, self.config.upsert_boost, 1);
+        self.maybe_autosave();
+        id
+    }
+
+    /// Groups of concepts (and, separately, entities) sharing a normalized
+    /// name. Each group is ordered oldest first.
+    pub fn find_duplicates(&self) -> Vec<Vec<NodeId>> {
+        // Chave: (é entidade, nome normalizado)
+        let mut groups: BTreeMap<(bool, String), Vec<(u64, NodeId)>> = BTreeMap::new();
+        for (id, node) in &self.nodes {
+            let (kind, name) = match node {
+                Node::Concept(_, d) => (false, tokenize(&d.name)),
+                Node::Entity(_, d) => (true, tokenize(&d.name)),
+                _ => continue,
+            };
+            if !name.is_empty() && !self.is_forgotten(*id) {
+                groups.entry((kind, name.join(" "))).or_default().push((node.meta().seq, *id));
+            }
+        }
+        groups.into_values()
+            .filter(|group| group.len() > 1)
+            .map(|mut group| {
+                group.sort();
+                group.into_iter().map(|(_, id)| id).collect()
+            })
+            .collect()
+    }
+
+    /// Folds `absorb` into `keep`: edges are redirected (no self-loops,
+    /// parallel edges keep the higher weight), activation and stability take
+    /// the max, access counts add up, tags and concept definitions are merged
+    /// (the absorbed concept's name becomes an alias).
+    /// `absorb` is then deleted (not archived). Returns false if either node
+    /// is missing or they are the same node.
+    pub fn merge_nodes(&mut self, keep: NodeId, absorb: NodeId) -> bool {
+        if keep == absorb || !self.nodes.contains_key(&keep) {
+            return false;
+        }
+        let Some(absorbed) = self.nodes.get(&absorb).cloned() else { return false };
+        let tick = self.current_tick;
+        let absorbed_activation = self.projected_activation(absorbed.meta(), tick);
+        let kept_activation = self.projected_activation(self.nodes[&keep].meta(), tick);
+
+        self.rewrite_node(keep, |node| {
+            if let (Node::Concept(_, ours), Node::Concept(_, theirs)) = (&mut *node, &absorbed) {
+                ours.definition = merge_text(&ours.definition, &theirs.definition);
+                // O nome do absorvido vira apelido do mantido
+                for form in std::iter::once(&theirs.name).chain(&theirs.aliases) {
+                    let known = std::iter::once(&ours.name).chain(&ours.aliases).any(|f| tokenize(f) == tokenize(form));
+                    if !known {
+                        ours.aliases.push(form.clone());
+                    }
+                }
+            }
+            let (meta, theirs) = (node.meta_mut(), absorbed.meta());
+            meta.activation = kept_activation.max(absorbed_activation);
+            meta.last_tick = tick;
+            meta.stability = meta.stability.max(theirs.stability);
+            meta.access_count += theirs.access_count;
+            meta.last_accessed = meta.last_accessed.max(theirs.last_accessed);
+            meta.always_in_context |= theirs.always_in_context;
+            if let Some(floor) = theirs.pin_floor {
+                meta.pin_floor = Some(meta.pin_floor.map_or(floor, |f| f.max(floor)));
+            }
+            for (key, value) in &theirs.tags {
+                meta.tags.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        });
+
+        // Arestas de saída e de entrada passam a apontar para `keep`
+        let mut redirected: Vec<(NodeId, Connection)> = self.adjacency.get(&absorb).cloned().unwrap_or_default()
+            .into_iter()
+            .map(|edge| (keep, edge))
+            .collect();
+        for (source, edges) in &self.adjacency {
+            if *source == absorb {
+                continue;
+            }
+            for edge in edges.iter().filter(|e| e.target == absorb) {
+                redirected.push((*source, Connection { target: keep, ..edge.clone() }));
+            }
+        }
+        redirected.sort_by_key(|(source, edge)| (*source, edge.target, edge.edge_type.clone()));
+
+        self.record(GraphEvent::Pruned { ids: vec![absorb] });
+        self.remove_nodes(&[absorb]);
+        for (source, edge) in redirected {
+            if source == edge.target || !self.nodes.contains_key(&edge.target) {
+                continue;
+            }
+            let existing = self.adjacency.get(&source)
+                .and_then(|list| list.iter().find(|c| c.target == edge.target && c.edge_type == edge.edge_type))
+                .map(|c| c.weight);
+            match existing {
+                Some(weight) => {
+                    self.reweight_edge(source, edge.target, &edge.edge_type, weight.max(edge.weight));
+                }
+                None => self.insert_edge(source, edge),
+            }
+        }
+
+        // Fusões reescrevem nós e arestas no lugar: não dá para desfazer
+        self.journal.clear();
+        self.dirty += 1;
+        self.maybe_autosave();
+        true
+    }
+
+    /// Edits a node's content in place, keeping the search index in sync.
+    pub(crate) fn rewrite_node(&mut self, id: NodeId, edit: impl FnOnce(&mut Node)) {
+        let Some(node) = self.nodes.get_mut(&id) else { return };
+        let old_tokens = node.tokens();
+        edit(node);
+        let new_tokens = node.tokens();
+        for token in old_tokens.iter().filter(|t| !new_tokens.contains(t)) {
+            if let Some(list) = self.index.get_mut(token) {
+                list.retain(|n| *n != id);
+                if list.is_empty() {
+                    self.index.remove(token);
+                }
+            }
+        }
+        for token in new_tokens.into_iter().filter(|t| !old_tokens.contains(t)) {
+            self.index.entry(token).or_default().push(id);
+        }
+        self.record_rewrite(id);
+    }
+
+    /// Logs a node's current content as `Rewritten` (or just marks the graph dirty).
+    pub(crate) fn record_rewrite(&mut self, id: NodeId) {
+        match self.nodes.get(&id).filter(|_| self.is_recording()) {
+            Some(node) => {
+                let node = Box::new(node.clone());
+                self.record(GraphEvent::Rewritten { node });
+            }
+            None => self.dirty += 1,
+        }
+    }
+}
+
+/// Combines two definitions: identical or contained text isn't repeated.
+fn merge_text(current: &str, incoming: &str) -> String {
+    let (current, incoming) = (current.trim(), incoming.trim());
+    if incoming.is_empty() || current.contains(incoming) {
+        current.to_string()
+    } else if current.is_empty() || incoming.contains(current) {
+        incoming.to_string()
+    } else {
+        format!("{}; {}", current, incoming)
+    }
+}
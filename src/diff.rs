@@ -0,0 +1,150 @@
+//! # Fork & Diff
+//! Explicit deep copies and structural diffs between two snapshots, e.g.
+//! "what did the agent learn during this conversation", or forking a brain to
+//! compare decay parameters and replaying the winner's changes onto the
+//! original.
+
+use crate::{Connection, LoomGraph, Node, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedNode {
+    pub before: Node,
+    pub after: Node,
+}
+
+/// Changes that turn one graph into another. Edges are identified by
+/// `(source, target, edge_type)`; a changed edge differs only in weight.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphDiff {
+    pub added_nodes: Vec<Node>,
+    pub removed_nodes: Vec<NodeId>,
+    pub changed_nodes: Vec<ChangedNode>,
+    pub added_edges: Vec<(NodeId, Connection)>,
+    pub removed_edges: Vec<(NodeId, Connection)>,
+    /// New weight of edges present in both graphs.
+    pub changed_edges: Vec<(NodeId, Connection)>,
+}
+
+impl GraphDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+impl LoomGraph {
+    /// Independent deep copy. The fork keeps the data, config and undo
+    /// history but no observers, autosave, WAL, or sync replica id.
+    pub fn clone_graph(&self) -> LoomGraph {
+        let mut fork = self.clone();
+        fork.sync = self.sync.forked();
+        fork
+    }
+
+    /// What changes between this graph and `other` (apply it here to get `other`).
+    pub fn diff(&self, other: &LoomGraph) -> GraphDiff {
+        let mut diff = GraphDiff::default();
+
+        for (id, theirs) in &other.nodes {
+            match self.nodes.get(id) {
+                None => diff.added_nodes.push(theirs.clone()),
+                Some(ours) if !same_node(ours, theirs) => {
+                    diff.changed_nodes.push(ChangedNode { before: ours.clone(), after: theirs.clone() });
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed_nodes = self.nodes.keys().filter(|id| !other.nodes.contains_key(id)).copied().collect();
+
+        let ours = edge_map(self);
+        let theirs = edge_map(other);
+        for (key, edge) in &theirs {
+            match ours.get(key) {
+                None => diff.added_edges.push((key.0, (*edge).clone())),
+                Some(old) if old.weight != edge.weight => diff.changed_edges.push((key.0, (*edge).clone())),
+                Some(_) => {}
+            }
+        }
+        diff.removed_edges = ours.iter()
+            .filter(|(key, _)| !theirs.contains_key(*key))
+            .map(|(key, edge)| (key.0, (*edge).clone()))
+            .collect();
+
+        diff.added_nodes.sort_by_key(|n| n.meta().id);
+        diff.removed_nodes.sort();
+        diff.changed_nodes.sort_by_key(|c| c.after.meta().id);
+        let by_key = |(source, edge): &(NodeId, Connection)| (*source, edge.target, edge.edge_type.clone());
+        diff.added_edges.sort_by_key(by_key);
+        diff.removed_edges.sort_by_key(by_key);
+        diff.changed_edges.sort_by_key(by_key);
+        diff
+    }
+
+    /// Applies a diff produced by `diff`. Entries that no longer fit (an added
+    /// node that already exists, an edge whose endpoint is gone...) are skipped.
+    /// Returns how many entries were applied.
+    pub fn apply_diff(&mut self, diff: GraphDiff) -> usize {
+        let mut applied = 0;
+
+        let removed: Vec<NodeId> = diff.removed_nodes.into_iter().filter(|id| self.nodes.contains_key(id)).collect();
+        if !removed.is_empty() {
+            applied += removed.len();
+            self.discard_nodes(&removed);
+        }
+        let mut inserted = Vec::new();
+        for node in diff.added_nodes {
+            let id = node.meta().id;
+            if !self.nodes.contains_key(&id) {
+                self.add_node_internal(node);
+                inserted.push(id);
+                applied += 1;
+            }
+        }
+        for change in diff.changed_nodes {
+            let id = change.after.meta().id;
+            let Some(seq) = self.nodes.get(&id).map(|n| n.meta().seq) else { continue };
+            let mut node = change.after;
+            node.meta_mut().seq = seq;
+            self.rewrite_node(id, |n| *n = node);
+            applied += 1;
+        }
+
+        for (source, edge) in diff.removed_edges {
+            applied += self.disconnect_edge(source, edge.target, &edge.edge_type);
+        }
+        for (source, edge) in diff.changed_edges {
+            if self.reweight_edge(source, edge.target, &edge.edge_type, edge.weight) {
+                applied += 1;
+            }
+        }
+        for (source, edge) in diff.added_edges {
+            if self.nodes.contains_key(&source) && self.nodes.contains_key(&edge.target) {
+                self.insert_edge(source, edge);
+                applied += 1;
+            }
+        }
+
+        // Substituições e remoções de arestas não passam pelo journal
+        self.journal.clear();
+        self.dirty += 1;
+        self.enforce_capacity(&inserted);
+        self.maybe_autosave();
+        applied
+    }
+}
+
+fn same_node(a: &Node, b: &Node) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+fn edge_map(graph: &LoomGraph) -> HashMap<(NodeId, NodeId, String), &Connection> {
+    graph.adjacency.iter()
+        .flat_map(|(source, edges)| edges.iter().map(move |e| ((*source, e.target, e.edge_type.clone()), e)))
+        .collect()
+}
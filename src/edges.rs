@@ -0,0 +1,110 @@
+//! # Edge Introspection
+//! Typed view of edge labels plus neighbour/degree queries, so UIs and
+//! agents can walk the graph without parsing a whole backup. Edges still
+//! store their label as a string (`Connection::edge_type`); `EdgeKind` is
+//! the parsed form, with `Other` for application-defined labels.
+
+use crate::{Connection, LoomGraph, NodeId, EVOKED_EDGE, FELT_EDGE, INHIBITED_EDGE, IS_A_EDGE, MENTIONED_EDGE, PART_OF_EDGE, PRECEDED_EDGE};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const ASSOCIATED_EDGE: &str = "Associated";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum EdgeKind {
+    Associated,
+    Mentioned,
+    Preceded,
+    PartOf,
+    IsA,
+    Felt,
+    Evoked,
+    Inhibited,
+    Other(String),
+}
+
+impl EdgeKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            EdgeKind::Associated => ASSOCIATED_EDGE,
+            EdgeKind::Mentioned => MENTIONED_EDGE,
+            EdgeKind::Preceded => PRECEDED_EDGE,
+            EdgeKind::PartOf => PART_OF_EDGE,
+            EdgeKind::IsA => IS_A_EDGE,
+            EdgeKind::Felt => FELT_EDGE,
+            EdgeKind::Evoked => EVOKED_EDGE,
+            EdgeKind::Inhibited => INHIBITED_EDGE,
+            EdgeKind::Other(label) => label,
+        }
+    }
+}
+
+impl From<&str> for EdgeKind {
+    fn from(label: &str) -> Self {
+        match label {
+            ASSOCIATED_EDGE => EdgeKind::Associated,
+            MENTIONED_EDGE => EdgeKind::Mentioned,
+            PRECEDED_EDGE => EdgeKind::Preceded,
+            PART_OF_EDGE => EdgeKind::PartOf,
+            IS_A_EDGE => EdgeKind::IsA,
+            FELT_EDGE => EdgeKind::Felt,
+            EVOKED_EDGE => EdgeKind::Evoked,
+            INHIBITED_EDGE => EdgeKind::Inhibited,
+            other => EdgeKind::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for EdgeKind {
+    fn from(label: String) -> Self {
+        EdgeKind::from(label.as_str())
+    }
+}
+
+impl From<EdgeKind> for String {
+    fn from(kind: EdgeKind) -> Self {
+        kind.as_str().to_string()
+    }
+}
+
+impl fmt::Display for EdgeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Connection {
+    pub fn kind(&self) -> EdgeKind {
+        EdgeKind::from(self.edge_type.as_str())
+    }
+}
+
+impl LoomGraph {
+    /// Outgoing edges of `id` as `(target, kind, weight)`, in insertion order.
+    pub fn neighbors(&self, id: NodeId) -> Vec<(NodeId, EdgeKind, f32)> {
+        self.adjacency.get(&id).into_iter().flatten()
+            .map(|e| (e.target, e.kind(), e.weight))
+            .collect()
+    }
+
+    /// Incoming edges of `id` as `(source, kind, weight)`. Scans all edges.
+    pub fn incoming(&self, id: NodeId) -> Vec<(NodeId, EdgeKind, f32)> {
+        let mut found: Vec<_> = self.adjacency.iter()
+            .flat_map(|(source, edges)| {
+                edges.iter().filter(move |e| e.target == id).map(move |e| (*source, e.kind(), e.weight))
+            })
+            .collect();
+        found.sort_by_key(|(source, _, _)| *source);
+        found
+    }
+
+    pub fn out_degree(&self, id: NodeId) -> usize {
+        self.adjacency.get(&id).map_or(0, Vec::len)
+    }
+
+    /// Scans all edges.
+    pub fn in_degree(&self, id: NodeId) -> usize {
+        self.adjacency.values().flatten().filter(|e| e.target == id).count()
+    }
+}
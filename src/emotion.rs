@@ -0,0 +1,247 @@
+//! # Emotional Mechanics
+//! Helpers that let State nodes (valence/arousal) modulate how the rest of the
+//! graph is retained and recalled.
+//!
+//! Mood-congruent retrieval: the agent's mood is the activation-weighted
+//! mean of the State nodes active now. A memory's emotional tone is the
+//! weighted mean valence of the States linked to it. When both lean the same
+//! way, the memory surfaces more easily (and less when they disagree),
+//! scaled by `config.emotion.congruence_bias`.
+//!
+//! Flashbulb memories: episodes tied to a high-arousal State
+//! (`config.emotion.flashbulb_arousal`) get their stability multiplied by
+//! `flashbulb_multiplier` when created with the emotion, and every stability
+//! gain from dreams and consolidation is multiplied the same way, so they
+//! decay slower.
+//!
+//! States drift: their valence and arousal regress toward (0, 0) by
+//! `config.emotion.state_drift_rate` per tick since they were recorded
+//! (computed lazily, like decay). A faded State that has drifted to neutral
+//! is pruned regardless of stability.
+//!
+//! Labels: a State's circumplex position maps to the nearest named emotion in
+//! `config.emotion.labels` (joy, anger, calm, fear, ...), so prompts read
+//! "joy" instead of two numbers.
+
+use crate::{Connection, LoomGraph, Node, NodeId, NodeKind, StateData, EVOKED_EDGE};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A named point on the valence/arousal circumplex.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmotionLabel {
+    pub name: String,
+    pub valence: f32,
+    pub arousal: f32,
+}
+
+impl EmotionLabel {
+    pub fn new(name: &str, valence: f32, arousal: f32) -> Self {
+        Self { name: name.to_string(), valence, arousal }
+    }
+
+    /// The built-in table (valence and arousal in `[-1, 1]`).
+    pub fn defaults() -> Vec<EmotionLabel> {
+        vec![
+            Self::new("joy", 0.8, 0.4),
+            Self::new("excitement", 0.6, 0.9),
+            Self::new("contentment", 0.7, -0.2),
+            Self::new("calm", 0.3, -0.7),
+            Self::new("anger", -0.8, 0.7),
+            Self::new("fear", -0.5, 0.9),
+            Self::new("sadness", -0.7, -0.4),
+            Self::new("boredom", -0.3, -0.8),
+            Self::new("neutral", 0.0, 0.0),
+        ]
+    }
+}
+
+/// Name of the label nearest to `(valence, arousal)`, if the table isn't empty.
+fn nearest_label(table: &[EmotionLabel], valence: f32, arousal: f32) -> Option<&str> {
+    let distance = |l: &EmotionLabel| (l.valence - valence).powi(2) + (l.arousal - arousal).powi(2);
+    table.iter()
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        .map(|l| l.name.as_str())
+}
+
+impl StateData {
+    /// Discrete emotion for this state's recorded values, from the built-in table.
+    pub fn label(&self) -> String {
+        nearest_label(&EmotionLabel::defaults(), self.valence, self.arousal).unwrap_or("neutral").to_string()
+    }
+
+    /// Like `label`, with a custom table.
+    pub fn label_in<'a>(&self, table: &'a [EmotionLabel]) -> Option<&'a str> {
+        nearest_label(table, self.valence, self.arousal)
+    }
+}
+
+impl LoomGraph {
+    /// A State's `(valence, arousal)` now, after drifting toward neutral.
+    pub fn state_values(&self, data: &StateData) -> (f32, f32) {
+        let rate = self.config.emotion.state_drift_rate.clamp(0.0, 1.0);
+        let elapsed = self.current_tick.saturating_sub(data.anchor_tick);
+        if rate == 0.0 || elapsed == 0 {
+            return (data.valence, data.arousal);
+        }
+        let keep = (1.0 - rate).powf(elapsed as f32);
+        (data.valence * keep, data.arousal * keep)
+    }
+
+    /// Label for a State as it is now (after drift), from `config.emotion.labels`.
+    pub fn state_label(&self, data: &StateData) -> Option<&str> {
+        let (valence, arousal) = self.state_values(data);
+        nearest_label(&self.config.emotion.labels, valence, arousal)
+    }
+
+    /// Whether a State has drifted to within `config.emotion.neutral_epsilon` of (0, 0).
+    pub(crate) fn is_neutral_state(&self, node: &Node) -> bool {
+        let Node::State(_, data) = node else { return false };
+        let (valence, arousal) = self.state_values(data);
+        let epsilon = self.config.emotion.neutral_epsilon;
+        valence.abs() <= epsilon && arousal.abs() <= epsilon
+    }
+
+    /// Strongest |valence| among the State nodes directly linked to each node
+    /// (edges in either direction). Nodes without emotional links are absent.
+    pub fn emotional_charge(&self) -> HashMap<NodeId, f32> {
+        let valence = |id: &NodeId| match self.nodes.get(id) {
+            Some(Node::State(_, d)) => Some(self.state_values(d).0.abs().min(1.0)),
+            _ => None,
+        };
+
+        let mut charge: HashMap<NodeId, f32> = HashMap::new();
+        let mut bump = |id: NodeId, v: f32| {
+            let entry = charge.entry(id).or_insert(0.0);
+            *entry = entry.max(v);
+        };
+
+        for (source, edges) in &self.adjacency {
+            for edge in edges {
+                if let Some(v) = valence(&edge.target) { bump(*source, v); }
+                if let Some(v) = valence(source) { bump(edge.target, v); }
+            }
+        }
+        charge
+    }
+
+    /// The agent's mood: activation-weighted mean `(valence, arousal)` of
+    /// State nodes whose projected activation is at least
+    /// `config.emotion.mood_min_activation`. `None` when no State is active.
+    pub fn current_mood(&self) -> Option<(f32, f32)> {
+        let min = self.config.emotion.mood_min_activation;
+        let (mut total, mut valence, mut arousal) = (0.0, 0.0, 0.0);
+        let tick = self.live_tick();
+        for node in self.nodes.values() {
+            let Node::State(meta, data) = node else { continue };
+            let activation = self.projected_activation(meta, tick);
+            if activation < min || activation <= 0.0 {
+                continue;
+            }
+            total += activation;
+            let (v, a) = self.state_values(data);
+            valence += activation * v;
+            arousal += activation * a;
+        }
+        (total > 0.0).then(|| (valence / total, arousal / total))
+    }
+
+    /// Strongest arousal among the State nodes linked to each Episode (either direction).
+    pub(crate) fn episode_arousal(&self) -> HashMap<NodeId, f32> {
+        let mut arousal: HashMap<NodeId, f32> = HashMap::new();
+        let mut bump = |id: NodeId, a: f32| {
+            let entry = arousal.entry(id).or_insert(0.0);
+            *entry = entry.max(a);
+        };
+        for (source, edges) in &self.adjacency {
+            for edge in edges {
+                match (self.nodes.get(source), self.nodes.get(&edge.target)) {
+                    (Some(Node::Episode(..)), Some(Node::State(_, d))) => bump(*source, self.state_values(d).1.abs()),
+                    (Some(Node::State(_, d)), Some(Node::Episode(..))) => bump(edge.target, self.state_values(d).1.abs()),
+                    _ => {}
+                }
+            }
+        }
+        arousal
+    }
+
+    /// Stability multiplier for a memory with the given arousal.
+    pub(crate) fn flashbulb_factor(&self, arousal: f32) -> f32 {
+        let cfg = &self.config.emotion;
+        if arousal >= cfg.flashbulb_arousal { cfg.flashbulb_multiplier } else { 1.0 }
+    }
+
+    /// Applies the creation-time flashbulb boost to a freshly stored episode.
+    pub(crate) fn imprint_arousal(&mut self, episode: NodeId, arousal: f32) {
+        let factor = self.flashbulb_factor(arousal.abs());
+        if factor == 1.0 {
+            return;
+        }
+        let cap = self.config.dynamics.stability_cap;
+        if let Some(node) = self.nodes.get_mut(&episode) {
+            let meta = node.meta_mut();
+            meta.stability = (meta.stability * factor).min(cap);
+        }
+    }
+
+    /// Stores an episode together with the State it evoked, linked
+    /// episode -> state by an `Evoked` edge weighted by the feeling's
+    /// intensity. Returns `(episode, state)`.
+    pub fn add_episode_with_emotion(&mut self, summary: String, valence: f32, arousal: f32) -> (NodeId, NodeId) {
+        let episode = self.add_episode(summary);
+        let state = self.add_node_internal(Node::State(self.new_meta(NodeKind::State), StateData { valence, arousal, anchor_tick: self.current_tick }));
+        self.insert_edge(episode, Connection {
+            target: state,
+            weight: valence.abs().max(arousal.abs()).min(1.0),
+            edge_type: EVOKED_EDGE.to_string(),
+            inferred: false,
+        });
+        self.imprint_arousal(episode, arousal);
+        self.enforce_capacity(&[episode, state]);
+        (episode, state)
+    }
+
+    /// Edge-weighted mean valence of the States linked to each node (either
+    /// direction). Nodes without emotional links are absent.
+    pub fn emotional_tone(&self) -> HashMap<NodeId, f32> {
+        let mut sums: HashMap<NodeId, (f32, f32)> = HashMap::new();
+        let mut add = |id: NodeId, valence: f32, weight: f32| {
+            let entry = sums.entry(id).or_insert((0.0, 0.0));
+            entry.0 += valence * weight;
+            entry.1 += weight;
+        };
+        for (source, edges) in &self.adjacency {
+            for edge in edges {
+                let weight = edge.weight.abs();
+                if let Some(Node::State(_, d)) = self.nodes.get(&edge.target) { add(*source, self.state_values(d).0, weight); }
+                if let Some(Node::State(_, d)) = self.nodes.get(source) { add(edge.target, self.state_values(d).0, weight); }
+            }
+        }
+        sums.into_iter()
+            .filter(|(_, (_, weight))| *weight > 0.0)
+            .map(|(id, (sum, weight))| (id, (sum / weight).clamp(-1.0, 1.0)))
+            .collect()
+    }
+
+    /// `search_native` re-ranked by emotional congruence with the current
+    /// mood: each score is multiplied by `1 + bias * mood_valence * tone`.
+    /// Without an active mood this is plain search.
+    pub fn search_mood_congruent(&self, query: &str) -> Vec<(NodeId, f32)> {
+        let mut hits = self.search_native(query);
+        let Some((mood, _)) = self.current_mood() else { return hits };
+        let tone = self.emotional_tone();
+        let bias = self.config.emotion.congruence_bias;
+        for (id, score) in &mut hits {
+            let congruence = mood * tone.get(id).copied().unwrap_or(0.0);
+            *score *= (1.0 + bias * congruence).max(0.0);
+        }
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1));
+        hits
+    }
+
+    /// Stability a node needs to survive pruning at `threshold`, lowered by its emotional charge.
+    pub(crate) fn survival_threshold(&self, threshold: f32, charge: f32) -> f32 {
+        let protection = self.config.emotional_protection.clamp(0.0, 1.0);
+        threshold * (1.0 - protection * charge)
+    }
+}
@@ -0,0 +1,43 @@
+//! # Entities
+//! Specific people, places, organizations and objects, kept apart from
+//! Concepts (which define general ideas). Entities carry a free-form
+//! attribute map and are matched by name, case- and punctuation-insensitive.
+
+use crate::{tokenize, EntityData, EntityKind, LoomGraph, Node, NodeId, NodeKind};
+use std::collections::BTreeMap;
+use std::fmt;
+
+impl fmt::Display for EntityKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            EntityKind::Person => "Person",
+            EntityKind::Place => "Place",
+            EntityKind::Organization => "Organization",
+            EntityKind::Object => "Object",
+        };
+        f.write_str(name)
+    }
+}
+
+impl LoomGraph {
+    pub fn add_entity(&mut self, name: String, kind: EntityKind, attributes: BTreeMap<String, String>) -> NodeId {
+        let node = Node::Entity(self.new_meta(NodeKind::Entity), EntityData { name, kind, attributes });
+        let id = self.add_node_internal(node);
+        self.enforce_capacity(&[id]);
+        id
+    }
+
+    /// The entity called `name` (normalized comparison); with several, the most active one.
+    pub fn find_entity_by_name(&self, name: &str) -> Option<NodeId> {
+        let wanted = tokenize(name);
+        if wanted.is_empty() {
+            return None;
+        }
+        self.nodes.iter()
+            .filter(|(id, node)| !self.is_forgotten(**id) && matches!(node, Node::Entity(_, d) if tokenize(&d.name) == wanted))
+            .max_by(|(a_id, a), (b_id, b)| {
+                a.meta().activation.total_cmp(&b.meta().activation).then_with(|| b_id.cmp(a_id))
+            })
+            .map(|(id, _)| *id)
+    }
+}
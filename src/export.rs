@@ -0,0 +1,349 @@
+//! # Graph Export
+//! Renders the graph in formats understood by external tools, for
+//! inspection and documentation. These are one-way views: load them back
+//! with `from_backup_json`, not from here.
+//!
+//! In DOT output, fill color encodes the node kind and its opacity the
+//! current activation; edge width follows the weight and the line style the
+//! edge kind (inferred edges are dashed grey, `Inhibited` ones red with a
+//! flat head).
+//!
+//! GraphML and GEXF carry the raw numbers instead (type, activation,
+//! stability, last tick; edge weight, kind and inferred flag) for network
+//! analysis in Gephi, Cytoscape, networkx...
+//!
+//! `export_vis_json` targets browser dashboards (vis-network, D3 force
+//! layouts): node `group` is the kind and `value` the activation.
+//! `export_mermaid` draws one node's neighborhood as a Mermaid flowchart,
+//! small enough for docs, issues or an agent explaining itself.
+
+use crate::{Connection, EdgeKind, LoomGraph, NodeId, NodeKind};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Default cut-off for node labels.
+const LABEL_CHARS: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DotOptions {
+    /// Nodes below this projected activation are left out (with their edges).
+    pub min_activation: f32,
+    /// Only nodes in this namespace.
+    pub namespace: Option<String>,
+    /// Print the edge type next to each edge.
+    pub edge_labels: bool,
+    /// Node labels longer than this are cut with an ellipsis.
+    pub max_label_chars: usize,
+    /// Graphviz layout direction (`LR`, `TB`, ...).
+    pub rankdir: String,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            min_activation: 0.0,
+            namespace: None,
+            edge_labels: true,
+            max_label_chars: LABEL_CHARS,
+            rankdir: "LR".to_string(),
+        }
+    }
+}
+
+pub(crate) fn short_label(text: &str, max_chars: usize) -> String {
+    if text.chars().count() > max_chars {
+        text.chars().take(max_chars).collect::<String>() + "…"
+    } else {
+        text.to_string()
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;").replace('\n', " ")
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', " ")
+}
+
+/// Cor base (RGB) de cada tipo de nó
+fn kind_color(kind: NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Episode => "4e79a7",
+        NodeKind::Concept => "59a14f",
+        NodeKind::State => "e15759",
+        NodeKind::Observation => "76b7b2",
+        NodeKind::Goal => "f28e2b",
+        NodeKind::Skill => "b07aa1",
+        NodeKind::Entity => "edc948",
+        NodeKind::Custom => "9c755f",
+    }
+}
+
+fn kind_shape(kind: NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Episode | NodeKind::Observation => "note",
+        NodeKind::Concept => "ellipse",
+        NodeKind::State => "diamond",
+        NodeKind::Goal => "doubleoctagon",
+        NodeKind::Skill => "component",
+        NodeKind::Entity => "box",
+        NodeKind::Custom => "hexagon",
+    }
+}
+
+fn edge_style(kind: &EdgeKind) -> &'static str {
+    match kind {
+        EdgeKind::IsA | EdgeKind::PartOf => "bold",
+        EdgeKind::Mentioned | EdgeKind::Evoked => "dashed",
+        EdgeKind::Preceded | EdgeKind::Felt => "dotted",
+        _ => "solid",
+    }
+}
+
+impl LoomGraph {
+    /// Graphviz DOT of the graph (`dot -Tsvg brain.dot`).
+    pub fn export_dot(&self, options: &DotOptions) -> String {
+        let ids = self.export_selection(options.min_activation, options.namespace.as_deref());
+
+        let mut out = format!(
+            "digraph loom {{\n  rankdir={};\n  node [style=filled, fontname=\"Helvetica\"];\n  edge [fontname=\"Helvetica\", fontsize=9];\n",
+            escape_dot(&options.rankdir)
+        );
+        for (id, activation) in &ids {
+            let node = &self.nodes[id];
+            let kind = node.kind();
+            // Opacidade mínima para o nó continuar visível
+            let alpha = (40.0 + activation.clamp(0.0, 1.0) * 215.0) as u8;
+            let _ = writeln!(
+                out,
+                "  \"{}\" [label=\"{}\", shape={}, fillcolor=\"#{}{:02x}\", tooltip=\"{:?} · activation {:.2} · stability {:.2}\"];",
+                id,
+                escape_dot(&short_label(&node.label(), options.max_label_chars)),
+                kind_shape(kind),
+                kind_color(kind),
+                alpha,
+                kind,
+                activation,
+                node.meta().stability
+            );
+        }
+
+        for (source, edge) in self.export_edges(&ids) {
+            let kind = edge.kind();
+            let mut attrs = vec![format!("penwidth={:.2}", 0.5 + edge.weight.clamp(0.0, 1.0) * 2.5)];
+            if edge.inferred {
+                attrs.push("style=dashed, color=\"#999999\"".to_string());
+            } else if kind == EdgeKind::Inhibited {
+                attrs.push("color=\"#d62728\", arrowhead=tee".to_string());
+            } else {
+                attrs.push(format!("style={}", edge_style(&kind)));
+            }
+            if options.edge_labels {
+                attrs.push(format!("label=\"{}\"", escape_dot(&edge.edge_type)));
+            }
+            let _ = writeln!(out, "  \"{}\" -> \"{}\" [{}];", source, edge.target, attrs.join(", "));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// GraphML document of every live node and edge.
+    pub fn export_graphml(&self) -> String {
+        let ids = self.export_selection(0.0, None);
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>\n",
+            "  <key id=\"activation\" for=\"node\" attr.name=\"activation\" attr.type=\"double\"/>\n",
+            "  <key id=\"stability\" for=\"node\" attr.name=\"stability\" attr.type=\"double\"/>\n",
+            "  <key id=\"tick\" for=\"node\" attr.name=\"tick\" attr.type=\"long\"/>\n",
+            "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n",
+            "  <key id=\"kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"inferred\" for=\"edge\" attr.name=\"inferred\" attr.type=\"boolean\"/>\n",
+            "  <key id=\"current_tick\" for=\"graph\" attr.name=\"current_tick\" attr.type=\"long\"/>\n",
+        ));
+        let _ = writeln!(out, "  <graph id=\"loom\" edgedefault=\"directed\">");
+        let _ = writeln!(out, "    <data key=\"current_tick\">{}</data>", self.current_tick);
+        for (id, activation) in &ids {
+            let node = &self.nodes[id];
+            let _ = writeln!(out, "    <node id=\"{}\">", id);
+            let _ = writeln!(out, "      <data key=\"label\">{}</data>", escape_xml(&node.label()));
+            let _ = writeln!(out, "      <data key=\"type\">{:?}</data>", node.kind());
+            let _ = writeln!(out, "      <data key=\"activation\">{}</data>", activation);
+            let _ = writeln!(out, "      <data key=\"stability\">{}</data>", node.meta().stability);
+            let _ = writeln!(out, "      <data key=\"tick\">{}</data>", node.meta().last_tick);
+            let _ = writeln!(out, "    </node>");
+        }
+        for (n, (source, edge)) in self.export_edges(&ids).into_iter().enumerate() {
+            let _ = writeln!(out, "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">", n, source, edge.target);
+            let _ = writeln!(out, "      <data key=\"weight\">{}</data>", edge.weight);
+            let _ = writeln!(out, "      <data key=\"kind\">{}</data>", escape_xml(&edge.edge_type));
+            let _ = writeln!(out, "      <data key=\"inferred\">{}</data>", edge.inferred);
+            let _ = writeln!(out, "    </edge>");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// GEXF 1.3 document (Gephi's native format) of every live node and edge.
+    pub fn export_gexf(&self) -> String {
+        let ids = self.export_selection(0.0, None);
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n",
+            "  <meta>\n    <creator>LoomDB</creator>\n  </meta>\n",
+            "  <graph defaultedgetype=\"directed\" mode=\"static\">\n",
+            "    <attributes class=\"node\">\n",
+            "      <attribute id=\"type\" title=\"type\" type=\"string\"/>\n",
+            "      <attribute id=\"activation\" title=\"activation\" type=\"double\"/>\n",
+            "      <attribute id=\"stability\" title=\"stability\" type=\"double\"/>\n",
+            "      <attribute id=\"tick\" title=\"tick\" type=\"long\"/>\n",
+            "    </attributes>\n",
+            "    <attributes class=\"edge\">\n",
+            "      <attribute id=\"kind\" title=\"kind\" type=\"string\"/>\n",
+            "      <attribute id=\"inferred\" title=\"inferred\" type=\"boolean\"/>\n",
+            "    </attributes>\n",
+            "    <nodes>\n",
+        ));
+        for (id, activation) in &ids {
+            let node = &self.nodes[id];
+            let _ = writeln!(out, "      <node id=\"{}\" label=\"{}\">", id, escape_xml(&node.label()));
+            let _ = writeln!(out, "        <attvalues>");
+            let _ = writeln!(out, "          <attvalue for=\"type\" value=\"{:?}\"/>", node.kind());
+            let _ = writeln!(out, "          <attvalue for=\"activation\" value=\"{}\"/>", activation);
+            let _ = writeln!(out, "          <attvalue for=\"stability\" value=\"{}\"/>", node.meta().stability);
+            let _ = writeln!(out, "          <attvalue for=\"tick\" value=\"{}\"/>", node.meta().last_tick);
+            let _ = writeln!(out, "        </attvalues>");
+            let _ = writeln!(out, "      </node>");
+        }
+        out.push_str("    </nodes>\n    <edges>\n");
+        for (n, (source, edge)) in self.export_edges(&ids).into_iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "      <edge id=\"{}\" source=\"{}\" target=\"{}\" weight=\"{}\" label=\"{}\">",
+                n, source, edge.target, edge.weight, escape_xml(&edge.edge_type)
+            );
+            let _ = writeln!(out, "        <attvalues>");
+            let _ = writeln!(out, "          <attvalue for=\"kind\" value=\"{}\"/>", escape_xml(&edge.edge_type));
+            let _ = writeln!(out, "          <attvalue for=\"inferred\" value=\"{}\"/>", edge.inferred);
+            let _ = writeln!(out, "        </attvalues>");
+            let _ = writeln!(out, "      </edge>");
+        }
+        out.push_str("    </edges>\n  </graph>\n</gexf>\n");
+        out
+    }
+
+    /// `{"nodes": [{id, label, group, value}], "edges": [{from, to, weight, kind}]}`
+    /// for vis.js / D3, where `group` is the node kind and `value` its activation.
+    pub fn export_vis_json(&self) -> String {
+        let ids = self.export_selection(0.0, None);
+        let nodes: Vec<serde_json::Value> = ids.iter()
+            .map(|(id, activation)| {
+                let node = &self.nodes[id];
+                json!({
+                    "id": id,
+                    "label": short_label(&node.label(), LABEL_CHARS),
+                    "group": format!("{:?}", node.kind()).to_lowercase(),
+                    "value": activation,
+                })
+            })
+            .collect();
+        let edges: Vec<serde_json::Value> = self.export_edges(&ids).into_iter()
+            .map(|(source, edge)| json!({ "from": source, "to": edge.target, "weight": edge.weight, "kind": edge.edge_type }))
+            .collect();
+        json!({ "nodes": nodes, "edges": edges }).to_string()
+    }
+
+    /// Mermaid flowchart of everything within `radius` hops of `center`
+    /// (edges followed both ways), the center drawn with a thick border.
+    /// `None` if `center` doesn't exist.
+    pub fn export_mermaid(&self, center: NodeId, radius: usize) -> Option<String> {
+        if !self.nodes.contains_key(&center) {
+            return None;
+        }
+        let sub = self.subgraph(center, radius, 0.0);
+        let mut ids: Vec<NodeId> = sub.nodes.keys().copied().filter(|id| *id != center).collect();
+        ids.sort();
+        ids.insert(0, center);
+        let short: HashMap<NodeId, String> = ids.iter().enumerate().map(|(n, id)| (*id, format!("n{}", n))).collect();
+
+        let mut out = String::from("flowchart LR\n");
+        let mut kinds: Vec<NodeKind> = Vec::new();
+        for id in &ids {
+            let node = &sub.nodes[id];
+            let label = escape_mermaid(&short_label(&node.label(), LABEL_CHARS));
+            let (open, close) = match node.kind() {
+                NodeKind::Concept | NodeKind::Entity => ("(\"", "\")"),
+                NodeKind::State => ("{\"", "\"}"),
+                NodeKind::Goal => ("{{\"", "\"}}"),
+                NodeKind::Skill => ("[[\"", "\"]]"),
+                _ => ("[\"", "\"]"),
+            };
+            let class = format!("{:?}", node.kind()).to_lowercase();
+            let _ = writeln!(out, "  {}{}{}{}:::{}", short[id], open, label, close, class);
+            if !kinds.contains(&node.kind()) {
+                kinds.push(node.kind());
+            }
+        }
+        for id in &ids {
+            for edge in sub.adjacency.get(id).into_iter().flatten() {
+                let label = escape_mermaid(&edge.edge_type);
+                let arrow = if edge.inferred {
+                    format!("-. \"{}\" .->", label)
+                } else if edge.kind() == EdgeKind::Inhibited {
+                    format!("-- \"{}\" --x", label)
+                } else if edge.kind() == EdgeKind::IsA || edge.kind() == EdgeKind::PartOf {
+                    format!("== \"{}\" ==>", label)
+                } else {
+                    format!("-- \"{}\" -->", label)
+                };
+                let _ = writeln!(out, "  {} {} {}", short[id], arrow, short[&edge.target]);
+            }
+        }
+        for kind in kinds {
+            let _ = writeln!(out, "  classDef {} fill:#{},color:#fff", format!("{:?}", kind).to_lowercase(), kind_color(kind));
+        }
+        let _ = writeln!(out, "  style n0 stroke-width:3px");
+        Some(out)
+    }
+
+    /// Live nodes at or above `min_activation` (optionally in one namespace)
+    /// with their projected activation, sorted by id.
+    fn export_selection(&self, min_activation: f32, namespace: Option<&str>) -> Vec<(NodeId, f32)> {
+        let tick = self.live_tick();
+        let mut ids: Vec<(NodeId, f32)> = self.nodes.iter()
+            .filter(|(id, node)| {
+                !self.is_forgotten(**id) && namespace.is_none_or(|ns| node.meta().namespace.as_deref() == Some(ns))
+            })
+            .map(|(id, node)| (*id, self.projected_activation(node.meta(), tick)))
+            .filter(|(_, activation)| *activation >= min_activation)
+            .collect();
+        ids.sort_by_key(|(id, _)| *id);
+        ids
+    }
+
+    /// Edges between nodes of `selection`, grouped by source in selection order.
+    fn export_edges(&self, selection: &[(NodeId, f32)]) -> Vec<(NodeId, &Connection)> {
+        let shown: HashSet<NodeId> = selection.iter().map(|(id, _)| *id).collect();
+        selection.iter()
+            .flat_map(|(source, _)| {
+                self.adjacency.get(source).into_iter().flatten()
+                    .filter(|e| shown.contains(&e.target))
+                    .map(move |e| (*source, e))
+            })
+            .collect()
+    }
+}
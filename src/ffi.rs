@@ -0,0 +1,401 @@
+//! # C API
+//! Plain C ABI over the core operations (feature `ffi`), so iOS/Android apps
+//! (Swift, Kotlin/JNI) can embed a brain for on-device memory. The matching
+//! header is `include/loom.h`.
+//!
+//! Conventions:
+//! - a graph is an opaque `LoomGraph*` from `loom_new` / `loom_load_binary` /
+//!   `loom_from_bytes`, released with `loom_free`;
+//! - strings are NUL-terminated UTF-8; ids cross as UUID strings and
+//!   structured results as JSON;
+//! - every returned `char*` is owned by the caller and released with
+//!   `loom_string_free`, byte buffers with `loom_bytes_free`;
+//! - failures return NULL/false/-1 and leave a message for `loom_last_error`
+//!   (per thread); a panic inside the library is caught at the boundary and
+//!   reported the same way instead of unwinding into C.
+//!
+//! A graph is not thread-safe: callers serialize access to one handle.
+
+use crate::{ContextFormat, LoomGraph, NodeId};
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(message: impl std::fmt::Display) {
+    let message = CString::new(message.to_string().replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+/// Runs an exported body; a panic becomes `fallback` plus a `loom_last_error`
+/// message, since unwinding across `extern "C"` aborts the host app.
+fn guard<T>(fallback: T, body: impl FnOnce() -> T) -> T {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(value) => value,
+        Err(payload) => {
+            let message = payload.downcast_ref::<&str>().copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic");
+            set_error(format!("panic: {}", message));
+            fallback
+        }
+    }
+}
+
+/// Hands a Rust string to C (NUL bytes are dropped).
+fn into_c_string(text: String) -> *mut c_char {
+    CString::new(text.replace('\0', "")).map(CString::into_raw).unwrap_or(ptr::null_mut())
+}
+
+/// Borrows a C string, recording an error for NULL or invalid UTF-8.
+unsafe fn str_arg<'a>(text: *const c_char, name: &str) -> Option<&'a str> {
+    if text.is_null() {
+        set_error(format!("{} is NULL", name));
+        return None;
+    }
+    match CStr::from_ptr(text).to_str() {
+        Ok(text) => Some(text),
+        Err(_) => {
+            set_error(format!("{} is not valid UTF-8", name));
+            None
+        }
+    }
+}
+
+unsafe fn id_arg(text: *const c_char, name: &str) -> Option<NodeId> {
+    let raw = str_arg(text, name)?;
+    match raw.parse() {
+        Ok(id) => Some(id),
+        Err(_) => {
+            set_error(format!("invalid {} '{}'", name, raw));
+            None
+        }
+    }
+}
+
+unsafe fn graph_ref<'a>(graph: *const LoomGraph) -> Option<&'a LoomGraph> {
+    if graph.is_null() {
+        set_error("graph is NULL");
+    }
+    graph.as_ref()
+}
+
+unsafe fn graph_mut<'a>(graph: *mut LoomGraph) -> Option<&'a mut LoomGraph> {
+    if graph.is_null() {
+        set_error("graph is NULL");
+    }
+    graph.as_mut()
+}
+
+/// Last error message on this thread, or NULL. Valid until the next failing
+/// call on the same thread; do not free.
+#[no_mangle]
+pub extern "C" fn loom_last_error() -> *const c_char {
+    guard(ptr::null(), || {
+        LAST_ERROR.with(|slot| slot.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+    })
+}
+
+/// # Safety
+/// `text` must be NULL or a string returned by this library, not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn loom_string_free(text: *mut c_char) {
+    guard((), || {
+        if !text.is_null() {
+            drop(CString::from_raw(text));
+        }
+    })
+}
+
+/// # Safety
+/// `bytes`/`len` must come from `loom_to_bytes`, not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn loom_bytes_free(bytes: *mut u8, len: usize) {
+    guard((), || {
+        if !bytes.is_null() {
+            drop(Vec::from_raw_parts(bytes, len, len));
+        }
+    })
+}
+
+// --- CICLO DE VIDA & PERSISTÊNCIA ---
+
+#[no_mangle]
+pub extern "C" fn loom_new(decay_rate: f32) -> *mut LoomGraph {
+    guard(ptr::null_mut(), || {
+        Box::into_raw(Box::new(LoomGraph::new(decay_rate)))
+    })
+}
+
+/// # Safety
+/// `graph` must be NULL or a handle from this library, not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn loom_free(graph: *mut LoomGraph) {
+    guard((), || {
+        if !graph.is_null() {
+            drop(Box::from_raw(graph));
+        }
+    })
+}
+
+/// Loads a brain saved with `loom_save_binary` and catches up on the time
+/// passed since (`wake_up`). NULL on failure.
+///
+/// # Safety
+/// `path` must be a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn loom_load_binary(path: *const c_char) -> *mut LoomGraph {
+    guard(ptr::null_mut(), || {
+        let Some(path) = str_arg(path, "path") else { return ptr::null_mut() };
+        match LoomGraph::load_binary(path) {
+            Ok(mut graph) => {
+                graph.wake_up();
+                Box::into_raw(Box::new(graph))
+            }
+            Err(e) => {
+                set_error(format!("{}: {}", path, e));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// # Safety
+/// `graph` must be a live handle and `path` a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn loom_save_binary(graph: *mut LoomGraph, path: *const c_char) -> bool {
+    guard(false, || {
+        let (Some(graph), Some(path)) = (graph_mut(graph), str_arg(path, "path")) else { return false };
+        graph.save_binary(path).map_err(|e| set_error(format!("{}: {}", path, e))).is_ok()
+    })
+}
+
+/// Binary snapshot (same format as `loom_save_binary`); its length goes to
+/// `out_len`. Free with `loom_bytes_free`. NULL on failure.
+///
+/// # Safety
+/// `graph` must be a live handle and `out_len` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn loom_to_bytes(graph: *const LoomGraph, out_len: *mut usize) -> *mut u8 {
+    guard(ptr::null_mut(), || {
+        let Some(graph) = graph_ref(graph) else { return ptr::null_mut() };
+        if out_len.is_null() {
+            set_error("out_len is NULL");
+            return ptr::null_mut();
+        }
+        match graph.to_binary() {
+            Ok(bytes) => {
+                let mut bytes = bytes.into_boxed_slice();
+                *out_len = bytes.len();
+                let data = bytes.as_mut_ptr();
+                std::mem::forget(bytes);
+                data
+            }
+            Err(e) => {
+                set_error(e);
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// # Safety
+/// `bytes` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn loom_from_bytes(bytes: *const u8, len: usize) -> *mut LoomGraph {
+    guard(ptr::null_mut(), || {
+        if bytes.is_null() {
+            set_error("bytes is NULL");
+            return ptr::null_mut();
+        }
+        match LoomGraph::from_binary(std::slice::from_raw_parts(bytes, len)) {
+            Ok(graph) => Box::into_raw(Box::new(graph)),
+            Err(e) => {
+                set_error(e);
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+// --- INGESTÃO & CONEXÕES ---
+
+/// Returns the new concept's id.
+///
+/// # Safety
+/// `graph` must be a live handle; strings must be valid C strings.
+#[no_mangle]
+pub unsafe extern "C" fn loom_add_concept(graph: *mut LoomGraph, name: *const c_char, definition: *const c_char) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let (Some(graph), Some(name), Some(definition)) = (graph_mut(graph), str_arg(name, "name"), str_arg(definition, "definition")) else {
+            return ptr::null_mut();
+        };
+        into_c_string(graph.add_concept(name.to_string(), definition.to_string()).to_string())
+    })
+}
+
+/// Returns the new episode's id.
+///
+/// # Safety
+/// `graph` must be a live handle and `summary` a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn loom_add_episode(graph: *mut LoomGraph, summary: *const c_char) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let (Some(graph), Some(summary)) = (graph_mut(graph), str_arg(summary, "summary")) else { return ptr::null_mut() };
+        into_c_string(graph.add_episode(summary.to_string()).to_string())
+    })
+}
+
+/// Episode at an RFC 3339 time; NULL if unparseable or in the future.
+///
+/// # Safety
+/// `graph` must be a live handle; strings must be valid C strings.
+#[no_mangle]
+pub unsafe extern "C" fn loom_add_episode_at(graph: *mut LoomGraph, summary: *const c_char, iso_datetime: *const c_char) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let (Some(graph), Some(summary), Some(time)) = (graph_mut(graph), str_arg(summary, "summary"), str_arg(iso_datetime, "iso_datetime")) else {
+            return ptr::null_mut();
+        };
+        match graph.add_episode_at(summary.to_string(), time) {
+            Ok(id) => into_c_string(id.to_string()),
+            Err(e) => {
+                set_error(e);
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// # Safety
+/// `graph` must be a live handle; ids must be valid C strings.
+#[no_mangle]
+pub unsafe extern "C" fn loom_connect(graph: *mut LoomGraph, source_id: *const c_char, target_id: *const c_char, weight: f32) -> bool {
+    guard(false, || {
+        let (Some(graph), Some(source), Some(target)) = (graph_mut(graph), id_arg(source_id, "source_id"), id_arg(target_id, "target_id")) else {
+            return false;
+        };
+        graph.connect(source, target, weight) || {
+            set_error("unknown source or target");
+            false
+        }
+    })
+}
+
+// --- BUSCA & CONTEXTO ---
+
+/// `[[id, activation], ...]` as JSON, most active first.
+///
+/// # Safety
+/// `graph` must be a live handle and `query` a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn loom_search(graph: *const LoomGraph, query: *const c_char) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let (Some(graph), Some(query)) = (graph_ref(graph), str_arg(query, "query")) else { return ptr::null_mut() };
+        into_c_string(serde_json::to_string(&graph.search_native(query)).unwrap_or("[]".to_string()))
+    })
+}
+
+/// Node JSON, or NULL for an unknown id.
+///
+/// # Safety
+/// `graph` must be a live handle and `id` a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn loom_get_node(graph: *const LoomGraph, id: *const c_char) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let (Some(graph), Some(id)) = (graph_ref(graph), id_arg(id, "id")) else { return ptr::null_mut() };
+        match graph.get_node(id) {
+            Some(node) => into_c_string(serde_json::to_string(node).unwrap_or("{}".to_string())),
+            None => {
+                set_error(format!("no memory with id {}", id));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// `format`: 0 XML, 1 JSON, 2 Markdown, 3 OpenAI messages.
+///
+/// # Safety
+/// `graph` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn loom_get_context(graph: *const LoomGraph, min_activation: f32, format: u32) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let Some(graph) = graph_ref(graph) else { return ptr::null_mut() };
+        let format = match format {
+            0 => ContextFormat::Xml,
+            1 => ContextFormat::Json,
+            2 => ContextFormat::Markdown,
+            3 => ContextFormat::OpenAiMessages,
+            other => {
+                set_error(format!("unknown context format {}", other));
+                return ptr::null_mut();
+            }
+        };
+        into_c_string(graph.get_context_as(format, min_activation))
+    })
+}
+
+// --- SIMULAÇÃO & MANUTENÇÃO ---
+
+/// # Safety
+/// `graph` must be a live handle and `id` a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn loom_stimulate(graph: *mut LoomGraph, id: *const c_char, force: f32) -> bool {
+    guard(false, || {
+        let (Some(graph), Some(id)) = (graph_mut(graph), id_arg(id, "id")) else { return false };
+        graph.stimulate(id, force) || {
+            set_error(format!("no memory with id {}", id));
+            false
+        }
+    })
+}
+
+/// Advances the clock `ticks` ticks in one `fast_forward` jump.
+///
+/// # Safety
+/// `graph` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn loom_tick(graph: *mut LoomGraph, ticks: u64) -> bool {
+    guard(false, || {
+        let Some(graph) = graph_mut(graph) else { return false };
+        graph.fast_forward(ticks);
+        true
+    })
+}
+
+/// Catches up on the time since the last save (e.g. when the app returns
+/// to the foreground). Returns the `WakeReport` as JSON.
+///
+/// # Safety
+/// `graph` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn loom_wake_up(graph: *mut LoomGraph) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let Some(graph) = graph_mut(graph) else { return ptr::null_mut() };
+        into_c_string(serde_json::to_string(&graph.wake_up()).unwrap_or("{}".to_string()))
+    })
+}
+
+/// Deletes memories below stability `threshold`; returns how many, or -1.
+///
+/// # Safety
+/// `graph` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn loom_prune(graph: *mut LoomGraph, threshold: f32) -> i64 {
+    guard(-1, || {
+        graph_mut(graph).map_or(-1, |graph| graph.prune_low_stability(threshold) as i64)
+    })
+}
+
+/// # Safety
+/// `graph` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn loom_node_count(graph: *const LoomGraph) -> usize {
+    guard(0, || {
+        graph_ref(graph).map_or(0, LoomGraph::node_count)
+    })
+}
@@ -0,0 +1,40 @@
+//! # Forgetting Forecast
+//! Projects activation forward with the decay model, without touching the
+//! graph, so an agent can see what it is about to forget and rehearse it.
+//! Forecasts assume no further boosts (scheduled events are ignored) and
+//! start from `live_tick`.
+
+use crate::{LoomGraph, NodeId};
+
+impl LoomGraph {
+    /// Projected activation for each of the next `ticks_ahead` ticks
+    /// (`[t+1, ..., t+ticks_ahead]`). Empty if the node doesn't exist.
+    pub fn forecast(&self, id: NodeId, ticks_ahead: u64) -> Vec<f32> {
+        let Some(node) = self.nodes.get(&id) else { return Vec::new() };
+        let now = self.live_tick();
+        (1..=ticks_ahead)
+            .map(|ahead| self.projected_activation(node.meta(), now.saturating_add(ahead)))
+            .collect()
+    }
+
+    /// Whether the node's activation will still be >= `threshold` in `ticks` ticks.
+    pub fn will_survive(&self, id: NodeId, ticks: u64, threshold: f32) -> bool {
+        self.nodes.get(&id).is_some_and(|node| {
+            self.projected_activation(node.meta(), self.live_tick().saturating_add(ticks)) >= threshold
+        })
+    }
+
+    /// Memories at or above `threshold` now that will fall below it within
+    /// `ticks`, as `(id, projected activation then)`, most faded first.
+    pub fn at_risk(&self, ticks: u64, threshold: f32) -> Vec<(NodeId, f32)> {
+        let now = self.live_tick();
+        let future = now.saturating_add(ticks);
+        let mut risky: Vec<(NodeId, f32)> = self.nodes.values()
+            .filter(|node| self.projected_activation(node.meta(), now) >= threshold)
+            .map(|node| (node.meta().id, self.projected_activation(node.meta(), future)))
+            .filter(|(_, later)| *later < threshold)
+            .collect();
+        risky.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        risky
+    }
+}
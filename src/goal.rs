@@ -0,0 +1,118 @@
+//! # Goals (Prospective Memory)
+//! Intentions the agent must act on later. Unlike other memories, an open
+//! goal *gains* activation as its deadline approaches: within
+//! `config.goals.horizon_ticks` of being due, its activation is raised to
+//! `priority * (1 - remaining / horizon)`, reaching `priority` when due and
+//! staying there while overdue. Completed goals decay like anything else.
+//!
+//! Urgency is settled on every tick (and after `fast_forward`/`wake_up`).
+//! Deadlines given as datetimes are measured against the graph's clock, at
+//! one tick per `config.dynamics.seconds_per_tick`.
+
+use crate::{GoalData, GoalDue, GraphEvent, LoomGraph, Node, NodeId, NodeKind};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Prospective-memory tunables.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GoalConfig {
+    /// How many ticks before its deadline a goal starts gaining activation.
+    pub horizon_ticks: u64,
+}
+
+impl Default for GoalConfig {
+    fn default() -> Self {
+        Self { horizon_ticks: 1440 }
+    }
+}
+
+impl fmt::Display for GoalDue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoalDue::Tick(tick) => write!(f, "tick {}", tick),
+            GoalDue::At(at) => write!(f, "{}", at.to_rfc3339()),
+        }
+    }
+}
+
+impl LoomGraph {
+    pub fn add_goal(&mut self, description: String, due: GoalDue, priority: f32) -> NodeId {
+        let node = Node::Goal(self.new_meta(NodeKind::Goal), GoalData {
+            description,
+            due,
+            priority: priority.clamp(0.0, 1.0),
+            completed: false,
+        });
+        let id = self.add_node_internal(node);
+        self.refresh_goals();
+        self.enforce_capacity(&[id]);
+        id
+    }
+
+    /// Ticks until the goal is due (negative when overdue).
+    pub fn ticks_until_due(&self, goal: &GoalData) -> i64 {
+        match goal.due {
+            GoalDue::Tick(tick) => tick as i64 - self.live_tick() as i64,
+            GoalDue::At(at) => (at - self.now()).num_seconds() / self.config.dynamics.seconds_per_tick.max(1) as i64,
+        }
+    }
+
+    /// Activation an open goal is owed right now (0 outside the horizon).
+    fn goal_urgency(&self, goal: &GoalData) -> f32 {
+        if goal.completed {
+            return 0.0;
+        }
+        let horizon = self.config.goals.horizon_ticks.max(1) as f32;
+        let remaining = self.ticks_until_due(goal).max(0) as f32;
+        goal.priority * (1.0 - remaining / horizon).clamp(0.0, 1.0)
+    }
+
+    /// Raises every open goal's activation to its current urgency.
+    pub(crate) fn refresh_goals(&mut self) {
+        let tick = self.current_tick;
+        let raised: Vec<(NodeId, f32)> = self.nodes.values()
+            .filter_map(|node| match node {
+                Node::Goal(meta, data) => {
+                    let urgency = self.goal_urgency(data);
+                    (urgency > self.projected_activation(meta, tick)).then_some((meta.id, urgency))
+                }
+                _ => None,
+            })
+            .collect();
+        for (id, urgency) in raised {
+            if let Some(node) = self.nodes.get_mut(&id) {
+                let meta = node.meta_mut();
+                meta.activation = urgency;
+                meta.last_tick = tick;
+            }
+        }
+    }
+
+    /// Open goals whose deadline has arrived, highest priority first (then most overdue).
+    pub fn due_goals(&self) -> Vec<NodeId> {
+        let mut due: Vec<(f32, i64, NodeId)> = self.nodes.values()
+            .filter_map(|node| match node {
+                Node::Goal(meta, data) if !data.completed => {
+                    let remaining = self.ticks_until_due(data);
+                    (remaining <= 0).then_some((data.priority, remaining, meta.id))
+                }
+                _ => None,
+            })
+            .collect();
+        due.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+        due.into_iter().map(|(_, _, id)| id).collect()
+    }
+
+    /// Marks a goal as done; it stops gaining activation. False if `id` isn't an open goal.
+    pub fn complete_goal(&mut self, id: NodeId) -> bool {
+        let Some(Node::Goal(_, data)) = self.nodes.get_mut(&id) else { return false };
+        if data.completed {
+            return false;
+        }
+        data.completed = true;
+        self.record(GraphEvent::GoalCompleted { id });
+        self.maybe_autosave();
+        true
+    }
+}
@@ -0,0 +1,77 @@
+//! # Grafting
+//! Installs an exported subgraph (e.g. a curated "memory pack") into an
+//! existing brain under a namespace, remapping ids according to an `IdPolicy`.
+
+use crate::{Connection, LoomGraph, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdPolicy {
+    /// Every grafted node gets a fresh id.
+    Regenerate,
+    /// Ids are kept; nodes whose id already exists are skipped and reported.
+    Preserve,
+    /// Ids are kept unless they collide, in which case a fresh id is issued.
+    PreserveOrRegenerate,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraftReport {
+    pub inserted: usize,
+    pub edges_inserted: usize,
+    /// Original id -> id in the host graph, for every inserted node.
+    pub id_map: HashMap<NodeId, NodeId>,
+    /// Ids from the subgraph that already existed in the host graph.
+    pub collisions: Vec<NodeId>,
+}
+
+impl LoomGraph {
+    pub fn graft(&mut self, subgraph: LoomGraph, into_namespace: &str, id_policy: IdPolicy) -> GraftReport {
+        let mut report = GraftReport::default();
+
+        let mut incoming: Vec<_> = subgraph.nodes.into_iter().collect();
+        incoming.sort_by_key(|(id, _)| *id);
+
+        for (old_id, mut node) in incoming {
+            let collides = self.nodes.contains_key(&old_id);
+            if collides {
+                report.collisions.push(old_id);
+            }
+
+            let new_id = match (id_policy, collides) {
+                (IdPolicy::Preserve, true) => continue,
+                (IdPolicy::Preserve, false) | (IdPolicy::PreserveOrRegenerate, false) => old_id,
+                _ => self.new_node_id(),
+            };
+
+            let meta = node.meta_mut();
+            meta.id = new_id;
+            meta.namespace = Some(into_namespace.to_string());
+            self.add_node_internal(node);
+
+            report.id_map.insert(old_id, new_id);
+            report.inserted += 1;
+        }
+
+        let mut sources: Vec<_> = subgraph.adjacency.into_iter().collect();
+        sources.sort_by_key(|(id, _)| *id);
+
+        for (old_source, edges) in sources {
+            let Some(&source) = report.id_map.get(&old_source) else { continue };
+            for edge in edges {
+                // Arestas para fora do pacote só sobrevivem se o alvo já existir aqui
+                let target = report.id_map.get(&edge.target).copied().unwrap_or(edge.target);
+                if !self.nodes.contains_key(&target) {
+                    continue;
+                }
+                self.insert_edge(source, Connection { target, ..edge });
+                report.edges_inserted += 1;
+            }
+        }
+
+        let inserted: Vec<NodeId> = report.id_map.values().copied().collect();
+        self.enforce_capacity(&inserted);
+        report
+    }
+}
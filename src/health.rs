@@ -0,0 +1,163 @@
+//! # Health Report
+//! A quick diagnosis of how well the brain is tuned, for users who don't want
+//! to reason about decay curves. Each metric is a ratio in `[0, 1]`; anything
+//! past its threshold lowers the score and adds a machine-readable
+//! recommendation.
+//!
+//! Note that `decay_rate` is a retention factor: a *lower* rate fades
+//! memories faster.
+
+use crate::LoomGraph;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+const SATURATED: f32 = 0.95;
+const STARVED: f32 = 0.05;
+/// Abaixo disso as proporções não dizem muita coisa
+const MIN_NODES: usize = 10;
+
+const MAX_SATURATION: f32 = 0.3;
+const MAX_STARVATION: f32 = 0.8;
+const MAX_HUB_DOMINANCE: f32 = 0.25;
+const MAX_ORPHANS: f32 = 0.5;
+const MAX_INDEX_BLOAT: f32 = 0.1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Recommendation {
+    /// Too many memories stay near full activation: fade them faster.
+    LowerDecayRate { suggested: f32 },
+    /// Almost everything has faded: retain activation longer.
+    RaiseDecayRate { suggested: f32 },
+    /// Promote what is active and prune the rest (`dream()`).
+    RunConsolidation,
+    /// Most memories have no edges; enable `config.auto_link` or connect them.
+    LinkOrphans,
+    /// A few hubs take most edges; cap spreading with `config.limits.max_ripple_nodes`.
+    LimitRipple,
+    /// The search index holds stale or duplicate entries (`rebuild_index()`).
+    RebuildIndex,
+}
+
+impl Recommendation {
+    pub fn describe(&self) -> String {
+        match self {
+            Recommendation::LowerDecayRate { suggested } => format!("lower decay_rate to {:.3} so memories fade faster", suggested),
+            Recommendation::RaiseDecayRate { suggested } => format!("raise decay_rate to {:.3} so memories last longer", suggested),
+            Recommendation::RunConsolidation => "run consolidation (dream)".to_string(),
+            Recommendation::LinkOrphans => "link orphan memories (enable auto_link)".to_string(),
+            Recommendation::LimitRipple => "limit ripple spread (limits.max_ripple_nodes)".to_string(),
+            Recommendation::RebuildIndex => "rebuild the search index".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// 0 (badly tuned) to 100 (healthy).
+    pub score: f32,
+    pub node_count: usize,
+    pub edge_count: usize,
+    /// Share of nodes with projected activation >= 0.95.
+    pub saturation: f32,
+    /// Share of nodes with projected activation <= 0.05.
+    pub starvation: f32,
+    /// Share of all edge endpoints held by the most connected node.
+    pub hub_dominance: f32,
+    /// Share of nodes without any edge.
+    pub orphan_ratio: f32,
+    /// Share of index postings that are stale, duplicated or empty.
+    pub index_bloat: f32,
+    pub recommendations: Vec<Recommendation>,
+}
+
+/// How far `value` is past `limit`, scaled to `[0, 1]`.
+fn penalty(value: f32, limit: f32) -> f32 {
+    ((value - limit) / (1.0 - limit)).clamp(0.0, 1.0)
+}
+
+impl LoomGraph {
+    pub fn health_report(&self) -> HealthReport {
+        let node_count = self.node_count();
+        let edge_count = self.edge_count();
+        let total = node_count.max(1) as f32;
+
+        let (mut saturated, mut starved) = (0usize, 0usize);
+        let tick = self.live_tick();
+        for node in self.nodes.values() {
+            let activation = self.projected_activation(node.meta(), tick);
+            if activation >= SATURATED {
+                saturated += 1;
+            } else if activation <= STARVED {
+                starved += 1;
+            }
+        }
+
+        let mut degree: HashMap<_, usize> = HashMap::new();
+        for (source, edges) in &self.adjacency {
+            for edge in edges {
+                *degree.entry(*source).or_default() += 1;
+                *degree.entry(edge.target).or_default() += 1;
+            }
+        }
+        let hub_dominance = if edge_count == 0 {
+            0.0
+        } else {
+            degree.values().copied().max().unwrap_or(0) as f32 / (2 * edge_count) as f32
+        };
+        let orphans = self.nodes.keys().filter(|id| !degree.contains_key(id)).count();
+
+        let (mut postings, mut bloat) = (0usize, 0usize);
+        for ids in self.index.values() {
+            postings += ids.len();
+            if ids.is_empty() {
+                bloat += 1;
+            }
+            let mut seen = HashSet::new();
+            bloat += ids.iter().filter(|id| !self.nodes.contains_key(id) || !seen.insert(**id)).count();
+        }
+
+        let mut report = HealthReport {
+            score: 100.0,
+            node_count,
+            edge_count,
+            saturation: saturated as f32 / total,
+            starvation: starved as f32 / total,
+            hub_dominance,
+            orphan_ratio: orphans as f32 / total,
+            index_bloat: if postings == 0 && bloat == 0 { 0.0 } else { bloat as f32 / (postings + bloat).max(1) as f32 },
+            recommendations: Vec::new(),
+        };
+        if node_count < MIN_NODES {
+            return report;
+        }
+
+        let penalties = [
+            penalty(report.saturation, MAX_SATURATION),
+            penalty(report.starvation, MAX_STARVATION),
+            penalty(report.hub_dominance, MAX_HUB_DOMINANCE),
+            penalty(report.orphan_ratio, MAX_ORPHANS),
+            penalty(report.index_bloat, MAX_INDEX_BLOAT),
+        ];
+        report.score = 100.0 * penalties.iter().map(|p| 1.0 - 0.5 * p).product::<f32>();
+
+        let recs = &mut report.recommendations;
+        if penalties[0] > 0.0 {
+            recs.push(Recommendation::LowerDecayRate { suggested: (self.decay_rate * 0.9).max(0.01) });
+            recs.push(Recommendation::RunConsolidation);
+        }
+        if penalties[1] > 0.0 {
+            recs.push(Recommendation::RaiseDecayRate { suggested: (self.decay_rate + (1.0 - self.decay_rate) * 0.5).min(0.999) });
+        }
+        if penalties[2] > 0.0 {
+            recs.push(Recommendation::LimitRipple);
+        }
+        if penalties[3] > 0.0 {
+            recs.push(Recommendation::LinkOrphans);
+        }
+        if penalties[4] > 0.0 {
+            recs.push(Recommendation::RebuildIndex);
+        }
+        report
+    }
+}
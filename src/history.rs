@@ -0,0 +1,96 @@
+//! # Activation History
+//! Opt-in (`config.history.enabled`) time series of each node's activation,
+//! for plotting how a memory evolved. A sample is taken whenever activation
+//! actually changes hands: before and after a boost, when decay is settled
+//! (`settle_decay`, decay overrides) and after a dream. Between samples the
+//! curve is the usual exponential decay.
+//!
+//! Each node keeps at most `config.history.max_samples` samples (oldest
+//! dropped first). History is runtime-only and not persisted.
+
+use crate::{LoomGraph, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ActivationSample {
+    pub tick: u64,
+    pub activation: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ActivationHistory {
+    samples: HashMap<NodeId, VecDeque<ActivationSample>>,
+}
+
+impl LoomGraph {
+    /// Samples the current (projected) activation of `ids`, if history is on.
+    pub(crate) fn sample_activation(&mut self, ids: impl IntoIterator<Item = NodeId>) {
+        if !self.config.history.enabled {
+            return;
+        }
+        let (tick, cap) = (self.current_tick, self.config.history.max_samples.max(1));
+        for id in ids {
+            let Some(node) = self.nodes.get(&id) else { continue };
+            let sample = ActivationSample { tick, activation: self.projected_activation(node.meta(), tick) };
+            let series = self.history.samples.entry(id).or_default();
+            if series.back() == Some(&sample) {
+                continue;
+            }
+            series.push_back(sample);
+            while series.len() > cap {
+                series.pop_front();
+            }
+        }
+    }
+
+    pub(crate) fn forget_history(&mut self, ids: &[NodeId]) {
+        for id in ids {
+            self.history.samples.remove(id);
+        }
+    }
+
+    /// Recorded samples for `id`, oldest first.
+    pub fn history(&self, id: NodeId) -> Vec<ActivationSample> {
+        self.history.samples.get(&id).map(|s| s.iter().copied().collect()).unwrap_or_default()
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.samples.clear();
+    }
+
+    fn history_rows(&self, ids: &[NodeId]) -> Vec<(NodeId, ActivationSample)> {
+        let mut selected: Vec<NodeId> = if ids.is_empty() {
+            self.history.samples.keys().copied().collect()
+        } else {
+            ids.to_vec()
+        };
+        selected.sort();
+        selected.dedup();
+        selected.into_iter()
+            .flat_map(|id| self.history(id).into_iter().map(move |s| (id, s)))
+            .collect()
+    }
+
+    /// `node_id,tick,activation` rows for `ids` (every tracked node if empty).
+    pub fn history_csv(&self, ids: &[NodeId]) -> String {
+        let mut out = String::from("node_id,tick,activation\n");
+        for (id, sample) in self.history_rows(ids) {
+            let _ = writeln!(out, "{},{},{}", id, sample.tick, sample.activation);
+        }
+        out
+    }
+
+    /// `{"<id>": [{"tick", "activation"}, ...]}` for `ids` (every tracked node if empty).
+    pub fn history_json(&self, ids: &[NodeId]) -> String {
+        let mut series: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
+        for (id, sample) in self.history_rows(ids) {
+            let entry = series.entry(id.to_string()).or_insert_with(|| serde_json::json!([]));
+            if let Some(list) = entry.as_array_mut() {
+                list.push(serde_json::to_value(sample).unwrap_or_default());
+            }
+        }
+        serde_json::Value::Object(series).to_string()
+    }
+}
@@ -0,0 +1,82 @@
+//! # Importance
+//! A composite score in `[0, 1]` that is smarter than raw activation for
+//! pruning and context selection. Each component is normalized to `[0, 1]`
+//! and blended with the weights in `config.importance`:
+//!
+//! - **activation**: projected activation now;
+//! - **stability**: `ln(1 + s) / ln(101)` (stability saturates around 100);
+//! - **centrality**: degree (in + out) relative to the best-connected node;
+//! - **access**: `ln(1 + access_count)` relative to the most accessed node.
+
+use crate::{LoomGraph, NodeId};
+use std::collections::HashMap;
+
+impl LoomGraph {
+    /// Importance of every node.
+    pub fn importance_scores(&self) -> HashMap<NodeId, f32> {
+        let weights = &self.config.importance;
+        let total = weights.activation + weights.stability + weights.centrality + weights.access;
+        if total <= 0.0 {
+            return self.nodes.keys().map(|id| (*id, 0.0)).collect();
+        }
+
+        let mut degree: HashMap<NodeId, usize> = HashMap::new();
+        for (source, edges) in &self.adjacency {
+            for edge in edges {
+                *degree.entry(*source).or_default() += 1;
+                *degree.entry(edge.target).or_default() += 1;
+            }
+        }
+        let max_degree = degree.values().copied().max().unwrap_or(0).max(1) as f32;
+        let stability_scale = 101f32.ln();
+        let max_access = self.nodes.values().map(|n| n.meta().access_count).max().unwrap_or(0);
+        let access_scale = (1.0 + max_access as f32).ln().max(f32::EPSILON);
+        let tick = self.live_tick();
+
+        self.nodes.values()
+            .map(|node| {
+                let meta = node.meta();
+                let activation = self.projected_activation(meta, tick).clamp(0.0, 1.0);
+                let stability = ((1.0 + meta.stability.max(0.0)).ln() / stability_scale).min(1.0);
+                let centrality = degree.get(&meta.id).copied().unwrap_or(0) as f32 / max_degree;
+                let access = (1.0 + meta.access_count as f32).ln() / access_scale;
+                let score = weights.activation * activation
+                    + weights.stability * stability
+                    + weights.centrality * centrality
+                    + weights.access * access;
+                (meta.id, score / total)
+            })
+            .collect()
+    }
+
+    pub fn importance(&self, id: NodeId) -> Option<f32> {
+        if !self.nodes.contains_key(&id) {
+            return None;
+        }
+        self.importance_scores().get(&id).copied()
+    }
+
+    /// The `k` most important nodes, highest first.
+    pub fn top_important(&self, k: usize) -> Vec<(NodeId, f32)> {
+        let mut ranked: Vec<(NodeId, f32)> = self.importance_scores().into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked.truncate(k);
+        ranked
+    }
+
+    /// Keeps the `keep` most important nodes (plus anything pinned, with
+    /// `pin` or in context) and prunes the rest. Returns how many were removed.
+    pub fn prune_least_important(&mut self, keep: usize) -> usize {
+        let mut ranked: Vec<(NodeId, f32)> = self.importance_scores().into_iter()
+            .filter(|(id, _)| !self.nodes[id].meta().always_in_context && !self.nodes[id].meta().is_pinned())
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        let to_remove: Vec<NodeId> = ranked.into_iter().skip(keep).map(|(id, _)| id).collect();
+        if to_remove.is_empty() {
+            return 0;
+        }
+        self.discard_nodes(&to_remove);
+        self.maybe_autosave();
+        to_remove.len()
+    }
+}
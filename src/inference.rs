@@ -0,0 +1,137 @@
+//! # Inference Rules
+//! User-registered chain rules applied during consolidation: "if
+//! `A -first-> B` and `B -then-> C`, add a weak `A -derive-> C`". For
+//! example, an episode that mentions "Border Collie", where "Border Collie"
+//! IsA "Dog", gets a weak `Mentioned` edge to "Dog".
+//!
+//! Derived edges carry `Connection::inferred` so they can be listed or removed
+//! in bulk. Each consolidation applies every rule once over the current
+//! edges, so chains deepen one step per pass. Rules live in
+//! `config.rules` and replay deterministically with the timeline.
+
+use crate::{Connection, GraphEvent, LoomGraph, NodeId, NodeKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InferenceRule {
+    pub name: String,
+    /// Only chains starting at this kind of node (`None` = any).
+    #[serde(default)]
+    pub source_kind: Option<NodeKind>,
+    /// Edge type of `A -> B`.
+    pub first: String,
+    /// Edge type of `B -> C`.
+    pub then: String,
+    /// Edge type of the derived `A -> C`.
+    pub derive: String,
+    /// Weight of derived edges.
+    pub weight: f32,
+}
+
+impl InferenceRule {
+    pub fn chain(name: &str, first: &str, then: &str, derive: &str, weight: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            source_kind: None,
+            first: first.to_string(),
+            then: then.to_string(),
+            derive: derive.to_string(),
+            weight,
+        }
+    }
+
+    pub fn from_kind(mut self, kind: NodeKind) -> Self {
+        self.source_kind = Some(kind);
+        self
+    }
+}
+
+impl LoomGraph {
+    /// Adds a rule (replacing any rule with the same name); it runs from the next consolidation on.
+    pub fn register_rule(&mut self, mut rule: InferenceRule) {
+        rule.weight = rule.weight.clamp(0.0, 1.0);
+        self.config.rules.retain(|r| r.name != rule.name);
+        self.config.rules.push(rule);
+        self.record_config();
+    }
+
+    pub fn remove_rule(&mut self, name: &str) -> bool {
+        let before = self.config.rules.len();
+        self.config.rules.retain(|r| r.name != name);
+        let removed = self.config.rules.len() != before;
+        if removed {
+            self.record_config();
+        }
+        removed
+    }
+
+    /// Every inferred edge as `(source, edge)`, sorted.
+    pub fn inferred_edges(&self) -> Vec<(NodeId, Connection)> {
+        let mut found: Vec<(NodeId, Connection)> = self.adjacency.iter()
+            .flat_map(|(source, edges)| edges.iter().filter(|e| e.inferred).map(move |e| (*source, e.clone())))
+            .collect();
+        found.sort_by(|a, b| (a.0, a.1.target, &a.1.edge_type).cmp(&(b.0, b.1.target, &b.1.edge_type)));
+        found
+    }
+
+    /// Deletes every inferred edge. Returns how many were removed.
+    pub fn remove_inferred_edges(&mut self) -> usize {
+        let mut removed = 0;
+        for edges in self.adjacency.values_mut() {
+            let before = edges.len();
+            edges.retain(|e| !e.inferred);
+            removed += before - edges.len();
+        }
+        if removed > 0 {
+            // Remoções em massa não passam pelo journal
+            self.journal.clear();
+            self.record(GraphEvent::InferredEdgesRemoved);
+            self.maybe_autosave();
+        }
+        removed
+    }
+
+    /// One round of every rule over the current edges (used by consolidation,
+    /// which records the whole pass as one event). Returns how many edges were added.
+    pub(crate) fn apply_rules(&mut self) -> usize {
+        if self.config.rules.is_empty() {
+            return 0;
+        }
+        let mut existing: HashSet<(NodeId, NodeId, String)> = self.adjacency.iter()
+            .flat_map(|(source, edges)| edges.iter().map(move |e| (*source, e.target, e.edge_type.clone())))
+            .collect();
+        let mut sources: Vec<NodeId> = self.adjacency.keys().copied().collect();
+        sources.sort();
+
+        let mut derived: Vec<(NodeId, Connection)> = Vec::new();
+        for rule in &self.config.rules {
+            for a in &sources {
+                if rule.source_kind.is_some_and(|kind| self.nodes.get(a).map(|n| n.kind()) != Some(kind)) {
+                    continue;
+                }
+                for b in self.adjacency[a].iter().filter(|e| e.edge_type == rule.first) {
+                    for c in self.adjacency.get(&b.target).into_iter().flatten().filter(|e| e.edge_type == rule.then) {
+                        if c.target == *a || !self.nodes.contains_key(&c.target) {
+                            continue;
+                        }
+                        if existing.insert((*a, c.target, rule.derive.clone())) {
+                            derived.push((*a, Connection {
+                                target: c.target,
+                                weight: rule.weight,
+                                edge_type: rule.derive.clone(),
+                                inferred: true,
+                            }));
+                        }
+                    }
+                }
+            }
+        }
+
+        let added = derived.len();
+        for (source, edge) in derived {
+            self.adjacency.entry(source).or_default().push(edge);
+        }
+        added
+    }
+}
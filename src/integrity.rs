@@ -0,0 +1,136 @@
+//! # Integrity Checks
+//! `validate()` walks the whole graph looking for broken invariants: edges to
+//! nodes that no longer exist, index entries out of sync with the nodes,
+//! non-finite numbers and nodes stored under a key that differs from their
+//! own id (how duplicate UUIDs show up in a map). `repair()` fixes all of it
+//! in place and reports what it did.
+//!
+//! On a lazily opened graph (`open_lazy`) edges to non-resident nodes look
+//! dangling; hydrate everything before repairing.
+
+use crate::{LoomGraph, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum IntegrityIssue {
+    /// Edge whose source or target is not in the graph.
+    DanglingEdge { source: NodeId, target: NodeId },
+    /// Edge with a NaN or infinite weight.
+    NonFiniteWeight { source: NodeId, target: NodeId },
+    /// Index points at a node that is gone, or doesn't contain that token.
+    StaleIndexEntry { token: String, id: NodeId },
+    /// A node token the index doesn't know about (the node is unsearchable by it).
+    MissingIndexEntry { token: String, id: NodeId },
+    DuplicateIndexEntry { token: String, id: NodeId },
+    EmptyIndexToken { token: String },
+    NonFiniteActivation { id: NodeId },
+    /// Stability that is NaN, infinite or not positive (decay divides by it).
+    InvalidStability { id: NodeId },
+    /// Node stored under `key` but carrying a different `id` (e.g. a duplicated UUID).
+    IdMismatch { key: NodeId, id: NodeId },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepairReport {
+    pub fixed: Vec<IntegrityIssue>,
+    /// Issues still present after repairing (should be empty).
+    pub remaining: Vec<IntegrityIssue>,
+}
+
+impl LoomGraph {
+    pub fn validate(&self) -> Vec<IntegrityIssue> {
+        let mut issues = Vec::new();
+
+        let mut keys: Vec<&NodeId> = self.nodes.keys().collect();
+        keys.sort();
+        for key in keys {
+            let meta = self.nodes[key].meta();
+            if meta.id != *key {
+                issues.push(IntegrityIssue::IdMismatch { key: *key, id: meta.id });
+            }
+            if !meta.activation.is_finite() {
+                issues.push(IntegrityIssue::NonFiniteActivation { id: *key });
+            }
+            if !meta.stability.is_finite() || meta.stability <= 0.0 {
+                issues.push(IntegrityIssue::InvalidStability { id: *key });
+            }
+        }
+
+        let mut sources: Vec<&NodeId> = self.adjacency.keys().collect();
+        sources.sort();
+        for source in sources {
+            for edge in &self.adjacency[source] {
+                if !self.nodes.contains_key(source) || !self.nodes.contains_key(&edge.target) {
+                    issues.push(IntegrityIssue::DanglingEdge { source: *source, target: edge.target });
+                } else if !edge.weight.is_finite() {
+                    issues.push(IntegrityIssue::NonFiniteWeight { source: *source, target: edge.target });
+                }
+            }
+        }
+
+        // Índice esperado, derivado dos próprios nós
+        let mut expected: HashMap<String, HashSet<NodeId>> = HashMap::new();
+        for (key, node) in &self.nodes {
+            for token in node.tokens() {
+                expected.entry(token).or_default().insert(*key);
+            }
+        }
+        let mut tokens: Vec<&String> = self.index.keys().collect();
+        tokens.sort();
+        for token in tokens {
+            let ids = &self.index[token];
+            if ids.is_empty() {
+                issues.push(IntegrityIssue::EmptyIndexToken { token: token.clone() });
+            }
+            let mut seen = HashSet::new();
+            for id in ids {
+                if !seen.insert(*id) {
+                    issues.push(IntegrityIssue::DuplicateIndexEntry { token: token.clone(), id: *id });
+                } else if !expected.get(token).is_some_and(|set| set.contains(id)) {
+                    issues.push(IntegrityIssue::StaleIndexEntry { token: token.clone(), id: *id });
+                }
+            }
+        }
+        let mut missing: Vec<(String, NodeId)> = expected.into_iter()
+            .flat_map(|(token, ids)| ids.into_iter().map(move |id| (token.clone(), id)))
+            .filter(|(token, id)| !self.index.get(token).is_some_and(|ids| ids.contains(id)))
+            .collect();
+        missing.sort();
+        issues.extend(missing.into_iter().map(|(token, id)| IntegrityIssue::MissingIndexEntry { token, id }));
+
+        issues
+    }
+
+    /// Fixes every issue `validate` reports: nodes are re-keyed to their key
+    /// (so duplicated UUIDs stay distinct), bad numbers are reset
+    /// (activation 0, stability 1), broken edges dropped and the index rebuilt.
+    pub fn repair(&mut self) -> RepairReport {
+        let fixed = self.validate();
+        if fixed.is_empty() {
+            return RepairReport::default();
+        }
+
+        for (key, node) in self.nodes.iter_mut() {
+            let meta = node.meta_mut();
+            meta.id = *key;
+            if !meta.activation.is_finite() {
+                meta.activation = 0.0;
+            }
+            if !meta.stability.is_finite() || meta.stability <= 0.0 {
+                meta.stability = 1.0;
+            }
+        }
+
+        let nodes = &self.nodes;
+        self.adjacency.retain(|source, _| nodes.contains_key(source));
+        for edges in self.adjacency.values_mut() {
+            edges.retain(|e| nodes.contains_key(&e.target) && e.weight.is_finite());
+        }
+
+        self.rebuild_index();
+        self.dirty += fixed.len() as u64;
+        self.maybe_autosave();
+        RepairReport { fixed, remaining: self.validate() }
+    }
+}
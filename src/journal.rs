@@ -0,0 +1,206 @@
+//! # Undo / Redo Journal
+//! Keeps the last `config.journal_capacity` mutating operations (node
+//! added, edge connected, boost, prune) with what is needed to reverse them,
+//! so memory-editing UIs can offer undo/redo and agent behaviour can be
+//! inspected step by step (`journal_json`).
+//!
+//! Undo walks back in LIFO order; any new operation clears the redo stack.
+//! Dreams and consolidations rewrite too much of the graph to reverse cheaply,
+//! so they clear the journal (nothing before them can be undone).
+//!
+//! Undo is written to the event log and the WAL as `Undone` (with its
+//! inverse), and redo as the operations it re-applies, so replaying either
+//! reaches the same state as the live graph.
+
+use crate::{ArchivedNode, GraphEvent, LoomGraph, NodeId, NodeMetadata};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// How to reverse a journaled operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Inverse {
+    RemoveNode(NodeId),
+    RemoveEdge { source: NodeId, target: NodeId, edge_type: String },
+    /// Metadata of every node the operation touched, as it was before.
+    RestoreMeta(Vec<NodeMetadata>),
+    /// Pruned nodes with their edges.
+    Reinsert(Vec<ArchivedNode>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub tick: u64,
+    pub events: Vec<GraphEvent>,
+    pub inverse: Inverse,
+}
+
+/// Runtime-only journal (never persisted).
+#[derive(Debug, Clone, Default)]
+pub struct Journal {
+    done: VecDeque<JournalEntry>,
+    undone: Vec<JournalEntry>,
+    suspended: bool,
+    // Metadados anteriores coletados durante um ripple em andamento
+    pending_meta: Option<HashMap<NodeId, NodeMetadata>>,
+}
+
+impl Journal {
+    pub fn len(&self) -> usize {
+        self.done.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty()
+    }
+
+    pub fn redo_len(&self) -> usize {
+        self.undone.len()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &JournalEntry> {
+        self.done.iter()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+
+    /// Starts collecting pre-boost metadata for the ripple about to run.
+    pub(crate) fn begin_boost(&mut self) {
+        if !self.suspended {
+            self.pending_meta = Some(HashMap::new());
+        }
+    }
+
+    /// Remembers a node's metadata the first time the current boost touches it.
+    pub(crate) fn remember(&mut self, meta: &NodeMetadata) {
+        if let Some(pending) = &mut self.pending_meta {
+            pending.entry(meta.id).or_insert_with(|| meta.clone());
+        }
+    }
+
+    pub(crate) fn end_boost(&mut self) -> Vec<NodeMetadata> {
+        let mut metas: Vec<NodeMetadata> = self.pending_meta.take().unwrap_or_default().into_values().collect();
+        metas.sort_by_key(|meta| meta.id);
+        metas
+    }
+}
+
+impl LoomGraph {
+    /// Whether a new operation would be journaled (lets callers skip building it).
+    pub(crate) fn journaling(&self) -> bool {
+        !self.journal.suspended && self.config.journal_capacity > 0
+    }
+
+    pub(crate) fn journal_push(&mut self, events: Vec<GraphEvent>, inverse: Inverse) {
+        let capacity = self.config.journal_capacity;
+        if self.journal.suspended || capacity == 0 {
+            return;
+        }
+        self.journal.undone.clear();
+        self.journal.done.push_back(JournalEntry { tick: self.current_tick, events, inverse });
+        while self.journal.done.len() > capacity {
+            self.journal.done.pop_front();
+        }
+    }
+
+    /// Reverses the last `n` journaled operations. Returns how many were undone.
+    pub fn undo(&mut self, n: usize) -> usize {
+        let mut undone = 0;
+        while undone < n {
+            let Some(entry) = self.journal.done.pop_back() else { break };
+            self.journal.suspended = true;
+            self.apply_inverse(&entry.inverse);
+            self.journal.suspended = false;
+            if self.is_recording() {
+                self.record(GraphEvent::Undone { inverse: entry.inverse.clone() });
+            }
+            self.journal.undone.push(entry);
+            undone += 1;
+        }
+        if undone > 0 {
+            self.dirty += 1;
+            self.maybe_autosave();
+        }
+        undone
+    }
+
+    /// Re-applies the last `n` undone operations. Returns how many were redone.
+    pub fn redo(&mut self, n: usize) -> usize {
+        let mut redone = 0;
+        while redone < n {
+            let Some(entry) = self.journal.undone.pop() else { break };
+            self.journal.suspended = true;
+            for event in &entry.events {
+                match event {
+                    // Refaz a poda pelo mesmo caminho (respeita o archive tier)
+                    GraphEvent::Pruned { ids } => self.discard_nodes(ids),
+                    _ => self.apply_event(event),
+                }
+            }
+            self.journal.suspended = false;
+            self.journal.done.push_back(entry);
+            redone += 1;
+        }
+        if redone > 0 {
+            self.dirty += 1;
+            self.maybe_autosave();
+        }
+        redone
+    }
+
+    /// The journal as a JSON array of entries, oldest first.
+    pub fn journal_json(&self) -> String {
+        let entries: Vec<&JournalEntry> = self.journal.entries().collect();
+        serde_json::to_string(&entries).unwrap_or("[]".to_string())
+    }
+
+    pub fn clear_journal(&mut self) {
+        self.journal.clear();
+    }
+
+    pub(crate) fn apply_inverse(&mut self, inverse: &Inverse) {
+        match inverse {
+            Inverse::RemoveNode(id) => self.remove_nodes(&[*id]),
+            Inverse::RemoveEdge { source, target, edge_type } => {
+                if let Some(edges) = self.adjacency.get_mut(source) {
+                    if let Some(at) = edges.iter().rposition(|e| e.target == *target && e.edge_type == *edge_type) {
+                        edges.remove(at);
+                    }
+                }
+            }
+            Inverse::RestoreMeta(metas) => {
+                for meta in metas {
+                    if let Some(node) = self.nodes.get_mut(&meta.id) {
+                        *node.meta_mut() = meta.clone();
+                    }
+                }
+            }
+            Inverse::Reinsert(records) => {
+                for record in records {
+                    let id = record.node.meta().id;
+                    self.archived.remove(&id);
+                    if !self.nodes.contains_key(&id) {
+                        self.index_node(&record.node);
+                        self.nodes.insert(id, record.node.clone());
+                    }
+                }
+                // Arestas só depois que todos os nós voltaram
+                for record in records {
+                    let id = record.node.meta().id;
+                    for edge in &record.outgoing {
+                        if self.nodes.contains_key(&edge.target) {
+                            self.adjacency.entry(id).or_default().push(edge.clone());
+                        }
+                    }
+                    for (source, edge) in &record.incoming {
+                        if self.nodes.contains_key(source) {
+                            self.adjacency.entry(*source).or_default().push(edge.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
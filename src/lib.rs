@@ -10,59 +10,411 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
+use std::io::{BufReader, BufWriter, Read};
+use std::str::FromStr;
+use std::sync::Arc;
 use uuid::Uuid;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
+mod abstraction;
+mod access;
+mod alias;
+mod anonymize;
+mod archive;
+#[cfg(feature = "tokio")]
+mod async_api;
+mod attachment;
+mod autolink;
+mod autosave;
+mod batch;
+mod bulk;
+mod calibrate;
+mod capacity;
+mod centrality;
+mod chunking;
+mod clock;
+mod community;
+mod confidence;
+mod config;
+mod consolidate;
+mod context;
+mod custom;
+mod dedupe;
+mod diff;
+mod conversation;
+mod edges;
+mod emotion;
+mod entity;
+mod export;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod forecast;
+mod goal;
+mod graft;
+mod health;
+mod history;
+mod importance;
+mod inference;
+mod integrity;
+mod journal;
+mod limits;
+#[cfg(feature = "mcp")]
+mod mcp;
+mod merge;
+mod migration;
+mod namespace;
+#[cfg(feature = "obsidian")]
+mod obsidian;
+mod observer;
+mod pack;
+mod parallel;
+mod perf;
+mod pin;
+mod persistence;
+mod provenance;
+mod rehearsal;
+mod retrieval;
+mod schedule;
+#[cfg(feature = "scheduler")]
+mod scheduler;
+#[cfg(feature = "server")]
+mod server;
+mod session;
+mod shared;
+mod skill;
+mod storage;
+mod subgraph;
+mod sync;
+mod tags;
+mod taxonomy;
+mod temporal;
+mod timeline;
+mod tombstone;
+mod wal;
+mod wake;
+mod walk;
+#[cfg(feature = "wasm")]
+mod wasm_api;
+
+pub use abstraction::{Summarizer, EVOKED_EDGE};
+pub use archive::{ArchiveTier, ArchivedNode, ColdArchive};
+#[cfg(feature = "tokio")]
+pub use async_api::AsyncLoom;
+pub use attachment::Attachment;
+pub use autolink::MENTIONED_EDGE;
+pub use autosave::{AutosaveClock, AutosaveHandle, AutosavePolicy, AutosaveSink};
+pub use calibrate::StabilityPolicy;
+pub use capacity::EvictionPolicy;
+pub use centrality::CentralityCache;
+pub use clock::{IdSource, ManualClock, RandomIds, SeededIds, Sources, SystemClock, TimeSource};
+pub use chunking::{split_into_chunks, PART_OF_EDGE};
+pub use confidence::{ConfidenceConfig, INHIBITED_EDGE};
+pub use config::{AnonymizeConfig, AutoLinkConfig, CapacityConfig, ChunkingConfig, ClockMode, ConfigError, ConversationConfig, DynamicsConfig, EmotionConfig, HistoryConfig, ImportanceConfig, LimitsConfig, LoomConfig, LoomConfigBuilder, NodeDefaults, PerfConfig, RehearsalConfig, SanitizerConfig, WakeConfig, merge_json, DEFAULT_DECAY_RATE};
+pub use consolidate::{ConsolidationConfig, ConsolidationReport};
+pub use journal::{Inverse, Journal, JournalEntry};
+pub use context::{CharEstimator, ChatMessage, ContextBuilder, ContextFormat, MessageGrouping, SortStrategy, TokenEstimator};
+pub use emotion::EmotionLabel;
+pub use goal::GoalConfig;
+pub use skill::SkillConfig;
+pub use conversation::{detect_emotion, IngestReport, FELT_EDGE, PRECEDED_EDGE};
+pub use diff::{ChangedNode, GraphDiff};
+pub use edges::{EdgeKind, ASSOCIATED_EDGE};
+pub use export::DotOptions;
+pub use graft::{GraftReport, IdPolicy};
+pub use health::{HealthReport, Recommendation};
+pub use inference::InferenceRule;
+pub use history::{ActivationHistory, ActivationSample};
+pub use integrity::{IntegrityIssue, RepairReport};
+pub use limits::{RippleReport, SearchOutcome};
+#[cfg(feature = "mcp")]
+pub use mcp::{McpServer, MCP_PROTOCOL_VERSION};
+pub use merge::{MergePolicy, MergeReport};
+pub use migration::{Migration, MigrationError, MigrationRegistry, SCHEMA_VERSION};
+#[cfg(feature = "obsidian")]
+pub use obsidian::VaultReport;
+pub use observer::{Observer, ObserverId, Observers};
+pub use pack::{MemoryPack, PackConcept, PackError, PackManifest, PackRelation};
+pub use perf::{OpKind, OpStats, PerfMonitor, PerfStats, SlowOp};
+pub use persistence::{BINARY_MAGIC, BINARY_VERSION};
+#[cfg(feature = "encryption")]
+pub use persistence::ENCRYPTED_MAGIC;
+pub use provenance::{Source, SourceOrigin};
+pub use retrieval::{ChunkHit, HitRelation, RelatedHit};
+pub use schedule::{ScheduledAction, ScheduledEvent};
+#[cfg(feature = "scheduler")]
+pub use scheduler::TickScheduler;
+#[cfg(feature = "server")]
+pub use server::{http_router, serve_http, ApiError, ServerEvent, EVENT_BUFFER, MAX_TICKS_PER_REQUEST};
+pub use session::{ActiveSession, SessionId};
+pub use shared::SharedLoom;
+pub use storage::{MemoryBackend, StorageBackend};
+#[cfg(feature = "sqlite")]
+pub use storage::SqliteBackend;
+pub use sync::{ChangeSet, NodeChange, Stamp, SyncReport, SyncState, VectorClock};
+pub use taxonomy::{TaxonomyConfig, IS_A_EDGE};
+pub use temporal::{parse_timestamp, TimeError};
+pub use timeline::{ActivationChange, EventLog, GraphEvent, MemoryComparison, TimedEvent};
+pub use wake::{DueReminders, WakeReport};
+pub use wal::{WalEntry, WalHandle, WalOptions};
+
 // ============================================================================
 // 1. ESTRUTURAS DE DADOS (Topology & Storage)
 // ============================================================================
 
+/// Typed node identifier. Serializes as the plain UUID string, so backups are unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NodeId(Uuid);
+
+impl NodeId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    pub fn as_uuid(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl Default for NodeId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for NodeId {
+    type Err = uuid::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s).map(Self)
+    }
+}
+
+impl From<Uuid> for NodeId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+impl From<NodeId> for Uuid {
+    fn from(id: NodeId) -> Self {
+        id.0
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Connection {
-    pub target: Uuid,
+    pub target: NodeId,
     pub weight: f32,
     pub edge_type: String, 
+    /// Derived by an inference rule rather than stored explicitly.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub inferred: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeMetadata {
-    pub id: Uuid,
+    pub id: NodeId,
     pub activation: f32,
     pub stability: f32,
     pub last_tick: u64,
+    #[serde(default)]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    pub decay_override: Option<DecayOverride>,
+    #[serde(default)]
+    pub always_in_context: bool,
+    /// Activation floor set by `pin`; pinned nodes are never pruned.
+    #[serde(default)]
+    pub pin_floor: Option<f32>,
+    /// Insertion order within the graph; breaks ties between identical timestamps.
+    #[serde(default)]
+    pub seq: u64,
+    #[serde(default)]
+    pub session: Option<SessionId>,
+    /// Search hits (via `recall`) and direct boosts.
+    #[serde(default)]
+    pub access_count: u64,
+    #[serde(default)]
+    pub last_accessed: Option<DateTime<Utc>>,
+    /// Free-form labels (source, user id, topic...); see `set_tag`.
+    #[serde(default, serialize_with = "persistence::sorted_map")]
+    pub tags: HashMap<String, String>,
+    /// Where the memory came from; see `set_source`.
+    #[serde(default)]
+    pub source: Option<Source>,
+}
+
+/// Per-node replacement for the graph-wide decay rate.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DecayOverride {
+    Rate(f32),
+    Never,
 }
 
 impl NodeMetadata {
     pub fn new() -> Self {
         Self {
-            id: Uuid::new_v4(),
+            id: NodeId::new(),
             activation: 1.0,
             stability: 1.0,
             last_tick: 0,
+            namespace: None,
+            decay_override: None,
+            always_in_context: false,
+            pin_floor: None,
+            seq: 0,
+            session: None,
+            access_count: 0,
+            last_accessed: None,
+            tags: HashMap::new(),
+            source: None,
+        }
+    }
+}
+
+impl NodeMetadata {
+    pub fn is_pinned(&self) -> bool {
+        self.pin_floor.is_some()
+    }
+
+    /// `activation`, raised to the pin floor if the node is pinned.
+    pub(crate) fn floored(&self, activation: f32) -> f32 {
+        match self.pin_floor {
+            Some(floor) => activation.max(floor),
+            None => activation,
         }
     }
 }
 
+impl Default for NodeMetadata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // -- Tipos de Dados dos Nós --
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EpisodeData {
     pub summary: String,
     pub timestamp: DateTime<Utc>,
+    /// How much the memory is trusted (0..=1); `None` = never rated.
+    #[serde(default)]
+    pub confidence: Option<f32>,
+    /// Externally stored media this memory refers to; see `attach`.
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConceptData {
     pub name: String,
     pub definition: String,
+    /// Other surface forms ("JS" for "JavaScript"); searchable and auto-linked like the name.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// How much the definition is trusted (0..=1); `None` = never rated.
+    #[serde(default)]
+    pub confidence: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateData {
     pub valence: f32,
     pub arousal: f32,
+    /// Tick at which `valence`/`arousal` were recorded; they drift toward
+    /// neutral from here (see `LoomGraph::state_values`).
+    #[serde(default)]
+    pub anchor_tick: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObservationData {
+    pub tool: String,
+    pub input: String,
+    pub output: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// An intention the agent must remember to act on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalData {
+    pub description: String,
+    pub due: GoalDue,
+    /// In `[0, 1]`; scales how strongly the goal surfaces near its deadline.
+    pub priority: f32,
+    pub completed: bool,
+}
+
+/// Procedural memory: something the agent knows how to do.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillData {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub steps: Vec<String>,
+    #[serde(default)]
+    pub attempts: u64,
+    #[serde(default)]
+    pub successes: u64,
+}
+
+/// A specific person, place, organization or object (as opposed to a Concept,
+/// which defines a general idea).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityData {
+    pub name: String,
+    pub kind: EntityKind,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
+}
+
+/// Application-defined memory: `type_name` identifies the kind, `payload` is opaque to the engine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomData {
+    pub type_name: String,
+    pub payload: serde_json::Value,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EntityKind {
+    Person,
+    Place,
+    Organization,
+    Object,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GoalDue {
+    Tick(u64),
+    At(DateTime<Utc>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum NodeKind {
+    Episode,
+    Concept,
+    State,
+    Observation,
+    Goal,
+    Skill,
+    Entity,
+    Custom,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,14 +422,37 @@ pub enum Node {
     Episode(NodeMetadata, EpisodeData),
     Concept(NodeMetadata, ConceptData),
     State(NodeMetadata, StateData),
+    Observation(NodeMetadata, ObservationData),
+    Goal(NodeMetadata, GoalData),
+    Skill(NodeMetadata, SkillData),
+    Entity(NodeMetadata, EntityData),
+    Custom(NodeMetadata, CustomData),
 }
 
 impl Node {
+    pub fn kind(&self) -> NodeKind {
+        match self {
+            Node::Episode(..) => NodeKind::Episode,
+            Node::Concept(..) => NodeKind::Concept,
+            Node::State(..) => NodeKind::State,
+            Node::Observation(..) => NodeKind::Observation,
+            Node::Goal(..) => NodeKind::Goal,
+            Node::Skill(..) => NodeKind::Skill,
+            Node::Entity(..) => NodeKind::Entity,
+            Node::Custom(..) => NodeKind::Custom,
+        }
+    }
+
     pub fn meta(&self) -> &NodeMetadata {
         match self {
             Node::Episode(m, _) => m,
             Node::Concept(m, _) => m,
             Node::State(m, _) => m,
+            Node::Observation(m, _) => m,
+            Node::Goal(m, _) => m,
+            Node::Skill(m, _) => m,
+            Node::Entity(m, _) => m,
+            Node::Custom(m, _) => m,
         }
     }
 
@@ -86,218 +461,571 @@ impl Node {
             Node::Episode(m, _) => m,
             Node::Concept(m, _) => m,
             Node::State(m, _) => m,
+            Node::Observation(m, _) => m,
+            Node::Goal(m, _) => m,
+            Node::Skill(m, _) => m,
+            Node::Entity(m, _) => m,
+            Node::Custom(m, _) => m,
+        }
+    }
+
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Node::Episode(_, d) => Some(d.timestamp),
+            Node::Observation(_, d) => Some(d.timestamp),
+            _ => None,
+        }
+    }
+
+    /// Normalized search tokens (lowercase, trimmed of punctuation).
+    pub fn tokens(&self) -> Vec<String> {
+        tokenize(&self.extract_text())
+    }
+
+    /// Short display name: the name of concepts, entities and skills, the
+    /// text of episodes and goals, the tool of observations.
+    pub fn label(&self) -> String {
+        match self {
+            Node::Episode(_, d) => d.summary.clone(),
+            Node::Concept(_, d) => d.name.clone(),
+            Node::State(_, d) => format!("valence {:.2}, arousal {:.2}", d.valence, d.arousal),
+            Node::Observation(_, d) => d.tool.clone(),
+            Node::Goal(_, d) => d.description.clone(),
+            Node::Skill(_, d) => d.name.clone(),
+            Node::Entity(_, d) => d.name.clone(),
+            Node::Custom(_, d) => d.type_name.clone(),
         }
     }
 
     pub fn extract_text(&self) -> String {
         match self {
             Node::Episode(_, d) => d.summary.clone(),
-            Node::Concept(_, d) => format!("{} {}", d.name, d.definition),
+            Node::Concept(_, d) if d.aliases.is_empty() => format!("{} {}", d.name, d.definition),
+            Node::Concept(_, d) => format!("{} {} {}", d.name, d.aliases.join(" "), d.definition),
             Node::State(_, _) => "".to_string(),
+            Node::Observation(_, d) => format!("{} {}", d.tool, d.output),
+            Node::Goal(_, d) => d.description.clone(),
+            Node::Skill(_, d) => format!("{} {} {}", d.name, d.description, d.steps.join(" ")),
+            Node::Entity(_, d) => {
+                let attributes: Vec<String> = d.attributes.iter().map(|(k, v)| format!("{} {}", k, v)).collect();
+                format!("{} {}", d.name, attributes.join(" "))
+            }
+            Node::Custom(_, d) => format!("{} {}", d.type_name, d.payload_text()),
         }
     }
 }
 
+/// Tokenization shared by the index and phrase matching.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split_whitespace()
+        .map(|token| token.trim_matches(|c: char| !c.is_alphanumeric()).to_string())
+        .filter(|clean| !clean.is_empty())
+        .collect()
+}
+
 // ============================================================================
 // 2. O MOTOR (LoomGraph)
 // ============================================================================
 
-#[wasm_bindgen]
-#[derive(Serialize, Deserialize)]
+pub type SanitizerHook = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct LoomGraph {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(default = "migration::legacy_schema_version")]
+    pub schema_version: u32,
+
     // Storage Primário: O(1) Access
-    #[wasm_bindgen(skip)]
-    pub nodes: HashMap<Uuid, Node>,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(serialize_with = "persistence::sorted_map")]
+    pub nodes: HashMap<NodeId, Node>,
 
     // Topologia: O(1) Neighbor Lookup
-    #[wasm_bindgen(skip)]
-    pub adjacency: HashMap<Uuid, Vec<Connection>>,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(serialize_with = "persistence::sorted_adjacency")]
+    pub adjacency: HashMap<NodeId, Vec<Connection>>,
 
     // Índice de Busca
-    #[wasm_bindgen(skip)]
-    pub index: HashMap<String, Vec<Uuid>>,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(serialize_with = "persistence::sorted_index")]
+    pub index: HashMap<String, Vec<NodeId>>,
 
-    #[wasm_bindgen(skip)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
     pub current_tick: u64,
-    #[wasm_bindgen(skip)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
     pub decay_rate: f32,
-    #[wasm_bindgen(skip)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
     pub last_saved: Option<DateTime<Utc>>,
+    // Âncora do relógio real (`ClockMode::RealTime`)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(default)]
+    pub clock_synced_at: Option<DateTime<Utc>>,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(default)]
+    pub next_seq: u64,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(default)]
+    pub active_session: Option<ActiveSession>,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(default)]
+    pub scheduled: Vec<ScheduledEvent>,
+    // Memórias podadas com `ArchiveTier::Memory`
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(default, serialize_with = "persistence::sorted_map")]
+    pub archived: HashMap<NodeId, ArchivedNode>,
+    // Nós esquecidos (soft delete) -> tick do esquecimento
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(default, serialize_with = "persistence::sorted_map")]
+    pub tombstones: HashMap<NodeId, u64>,
+    // Carimbos e digests da sincronização entre réplicas
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(default)]
+    pub sync: SyncState,
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(default)]
+    pub config: LoomConfig,
+
+    // Hook de sanitização customizado (não persistido)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub sanitizer_hook: Option<SanitizerHook>,
+
+    // Histórico de Mutações (opt-in, para time-travel; só em memória)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub event_log: EventLog,
+
+    // Write-Ahead Log: último seq refletido neste estado + handle do arquivo
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(default)]
+    pub wal_seq: u64,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub wal: WalHandle,
+
+    // Mutações desde o último save + política de autosave (não persistidos)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub dirty: u64,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub autosave: AutosaveHandle,
+
+    // Métricas de latência (não persistidas)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub perf: PerfMonitor,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub observers: Observers,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub history: ActivationHistory,
+    // Última detecção de comunidades (não persistida)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub communities: HashMap<NodeId, usize>,
+    // PageRank/betweenness por tick (não persistidos)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub centrality: CentralityCache,
+    // Undo/redo (não persistido)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub journal: Journal,
+    // Relógio e gerador de ids (não persistidos)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub sources: Sources,
+    // Namespace aplicado aos nós novos (não persistido)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub active_namespace: Option<String>,
+    // Nós removidos desde o último `save_to_backend` (não persistido)
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    #[serde(skip)]
+    pub removed_since_save: HashSet<NodeId>,
 }
 
 // ----------------------------------------------------------------------------
-// API PÚBLICA (WASM)
+// API PÚBLICA
 // ----------------------------------------------------------------------------
-#[wasm_bindgen]
 impl LoomGraph {
-    #[wasm_bindgen(constructor)]
     pub fn new(decay_rate: f32) -> Self {
         Self {
+            schema_version: migration::current_schema_version(),
             nodes: HashMap::new(),
             adjacency: HashMap::new(),
             index: HashMap::new(),
             current_tick: 0,
             decay_rate,
             last_saved: None,
+            clock_synced_at: None,
+            next_seq: 0,
+            active_session: None,
+            scheduled: Vec::new(),
+            archived: HashMap::new(),
+            tombstones: HashMap::new(),
+            sync: SyncState::default(),
+            config: LoomConfig::default(),
+            sanitizer_hook: None,
+            event_log: EventLog::default(),
+            wal_seq: 0,
+            wal: WalHandle::default(),
+            dirty: 0,
+            autosave: AutosaveHandle::default(),
+            perf: PerfMonitor::default(),
+            observers: Observers::default(),
+            history: ActivationHistory::default(),
+            communities: HashMap::new(),
+            centrality: CentralityCache::default(),
+            journal: Journal::default(),
+            sources: Sources::default(),
+            active_namespace: None,
+            removed_since_save: HashSet::new(),
         }
     }
 
-    // --- INGESTÃO DE DADOS ---
+    // --- SIMULAÇÃO & TEMPO ---
 
-    #[wasm_bindgen]
-    pub fn add_concept(&mut self, name: String, definition: String) -> String {
-        let node = Node::Concept(NodeMetadata::new(), ConceptData { name, definition });
-        let id = node.meta().id.to_string();
-        self.add_node_internal(node);
-        id
+    pub fn tick(&mut self) {
+        self.current_tick += 1;
+        self.notify_decay(self.current_tick - 1, self.current_tick);
+        self.run_due_events();
+        self.expire_tombstones();
+        self.refresh_goals();
+        self.append_wal(&GraphEvent::Ticked);
+        self.maybe_compact_wal();
+        self.maybe_autosave();
     }
 
-    #[wasm_bindgen]
-    pub fn add_episode(&mut self, summary: String) -> String {
-        let node = Node::Episode(NodeMetadata::new(), EpisodeData { 
-            summary, 
-            timestamp: Utc::now() 
-        });
-        let id = node.meta().id.to_string();
-        self.add_node_internal(node);
+    // --- DREAM PROTOCOL ---
+
+    pub fn dream(&mut self) -> String {
+        let (promoted, removed) = self.dream_pass();
+        format!("Ciclo REM: {} consolidadas, {} removidas.", promoted, removed)
+    }
+
+    /// One full sleep: consolidation + washout (+8h) and safe pruning.
+    /// Returns (consolidated, removed).
+    pub(crate) fn dream_pass(&mut self) -> (usize, usize) {
+        self.catch_up_clock();
+        self.record(GraphEvent::Dreamed);
+        let promoted = self.dream_cycle();
+
+        // Poda Segura
+        let removed = self.prune_low_stability(self.config.dynamics.prune_stability);
+        self.journal.clear();
+        self.maybe_autosave();
+        (promoted, removed)
+    }
+
+    // --- EXPORT ---
+
+    pub fn export_backup(&self) -> String {
+        serde_json::to_string(&self).unwrap_or("{}".to_string())
+    }
+
+    pub fn get_context_budgeted(&self, min_activation: f32, max_tokens: usize) -> String {
+        self.get_context_budgeted_with(min_activation, max_tokens, &CharEstimator::default())
+    }
+
+    pub fn prune_low_stability(&mut self, threshold: f32) -> usize {
+        self.catch_up_clock();
+        let started = Utc::now();
+        let to_remove = self.prune_candidates(threshold);
+
+        if to_remove.is_empty() {
+            self.record_timing(OpKind::Prune, started);
+            return 0;
+        }
+
+        self.discard_nodes(&to_remove);
+        self.record_timing(OpKind::Prune, started);
+        self.maybe_autosave();
+        to_remove.len()
+    }
+
+    /// Nodes `prune_low_stability(threshold)` would remove.
+    pub(crate) fn prune_candidates(&self, threshold: f32) -> Vec<NodeId> {
+        // Memórias com carga emocional exigem menos estabilidade para sobreviver
+        let charge = self.emotional_charge();
+        let faded = self.config.dynamics.prune_activation;
+        self.nodes.iter()
+            .filter(|(id, n)| {
+                let required = self.survival_threshold(threshold, charge.get(id).copied().unwrap_or(0.0));
+                // Estados que já voltaram ao neutro e apagaram saem sem exigir estabilidade
+                if self.is_neutral_state(n) && self.projected_activation(n.meta(), self.current_tick) < faded {
+                    return true;
+                }
+                n.meta().stability < required && n.meta().activation < faded
+            })
+            .filter(|(_, n)| !n.meta().is_pinned())
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    // --- TIME TRAVEL ---
+
+    pub fn enable_event_log(&mut self) {
+        if self.event_log.is_enabled() { return; }
+        let mut base = self.clone();
+        base.event_log = EventLog::default();
+        self.event_log = EventLog::starting_from(base);
+    }
+
+    pub fn reconstruct_at(&self, tick: u64) -> Option<LoomGraph> {
+        self.event_log.reconstruct(tick)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// API INTERNA (Rust Only)
+// ----------------------------------------------------------------------------
+impl LoomGraph {
+    pub fn add_concept(&mut self, name: String, definition: String) -> NodeId {
+        let node = Node::Concept(self.new_meta(NodeKind::Concept), ConceptData { name, definition, aliases: Vec::new(), confidence: None });
+        let id = self.add_node_internal(node);
+        self.enforce_capacity(&[id]);
         id
     }
 
-    #[wasm_bindgen]
-    pub fn add_state(&mut self, valence: f32, arousal: f32) -> String {
-        let node = Node::State(NodeMetadata::new(), StateData { valence, arousal });
-        let id = node.meta().id.to_string();
-        self.add_node_internal(node);
+    pub fn add_episode(&mut self, summary: String) -> NodeId {
+        self.insert_episode(summary, self.now())
+    }
+
+    fn insert_episode(&mut self, summary: String, timestamp: DateTime<Utc>) -> NodeId {
+        let node = Node::Episode(self.episode_meta(), EpisodeData { summary, timestamp, confidence: None, attachments: Vec::new() });
+        let id = self.add_node_internal(node);
+        self.chain_session(&[id]);
+        if self.config.auto_link.enabled {
+            self.link_mentions(id);
+        }
+        self.enforce_capacity(&[id]);
         id
     }
 
-    // --- CONEXÕES ---
+    pub fn add_state(&mut self, valence: f32, arousal: f32) -> NodeId {
+        let node = Node::State(self.new_meta(NodeKind::State), StateData { valence, arousal, anchor_tick: self.current_tick });
+        let id = self.add_node_internal(node);
+        self.enforce_capacity(&[id]);
+        id
+    }
 
-    #[wasm_bindgen]
-    pub fn connect(&mut self, source_id: &str, target_id: &str, weight: f32) -> bool {
-        let s_uuid = match Uuid::parse_str(source_id) { Ok(u) => u, Err(_) => return false };
-        let t_uuid = match Uuid::parse_str(target_id) { Ok(u) => u, Err(_) => return false };
+    pub fn add_observation(&mut self, tool: String, input: String, output: String) -> NodeId {
+        let node = Node::Observation(self.new_meta(NodeKind::Observation), ObservationData {
+            tool,
+            input,
+            output,
+            timestamp: self.now(),
+        });
+        let id = self.add_node_internal(node);
+        self.enforce_capacity(&[id]);
+        id
+    }
 
-        if self.nodes.contains_key(&s_uuid) && self.nodes.contains_key(&t_uuid) {
-            self.adjacency.entry(s_uuid).or_insert(Vec::new()).push(Connection {
-                target: t_uuid,
+    pub fn connect(&mut self, source: NodeId, target: NodeId, weight: f32) -> bool {
+        if self.nodes.contains_key(&source) && self.nodes.contains_key(&target) {
+            self.insert_edge(source, Connection {
+                target,
                 weight,
-                edge_type: "Associated".to_string(),
+                edge_type: ASSOCIATED_EDGE.to_string(),
+                inferred: false,
             });
             return true;
         }
         false
     }
 
-    // --- BUSCA & RECUPERAÇÃO ---
+    fn insert_edge(&mut self, source: NodeId, edge: Connection) {
+        self.store_edge(source, edge);
+        self.maybe_autosave();
+    }
 
-    #[wasm_bindgen]
-    pub fn search(&mut self, query: &str) -> String {
-        let results = self.search_native(query);
-        serde_json::to_string(&results).unwrap_or("[]".to_string())
+    /// Records and stores an edge (no autosave check).
+    fn store_edge(&mut self, source: NodeId, edge: Connection) {
+        if self.is_recording() {
+            self.record(GraphEvent::Connected { source, edge: edge.clone() });
+        } else {
+            self.dirty += 1;
+        }
+        self.notify(|o| o.on_edge_added(source, &edge));
+        if self.journaling() {
+            let inverse = Inverse::RemoveEdge { source, target: edge.target, edge_type: edge.edge_type.clone() };
+            self.journal_push(vec![GraphEvent::Connected { source, edge: edge.clone() }], inverse);
+        }
+        self.adjacency.entry(source).or_default().push(edge);
     }
 
-    #[wasm_bindgen]
-    pub fn get_node_info(&self, id_str: &str) -> String {
-        if let Ok(uuid) = Uuid::parse_str(id_str) {
-            if let Some(node) = self.nodes.get(&uuid) {
-                return serde_json::to_string(node).unwrap_or("{}".to_string());
-            }
+    /// Sets the weight of an existing edge. Returns false if there is none.
+    pub(crate) fn reweight_edge(&mut self, source: NodeId, target: NodeId, edge_type: &str, weight: f32) -> bool {
+        let existing = self.adjacency.get_mut(&source)
+            .and_then(|list| list.iter_mut().find(|c| c.target == target && c.edge_type == edge_type));
+        let Some(conn) = existing else { return false };
+        if conn.weight != weight {
+            conn.weight = weight;
+            self.record(GraphEvent::EdgeReweighted { source, target, edge_type: edge_type.to_string(), weight });
         }
-        "{}" .to_string()
+        true
     }
 
-    // --- SIMULAÇÃO & TEMPO ---
+    /// Removes every `source -> target` edge labelled `edge_type`. Returns how many.
+    pub(crate) fn disconnect_edge(&mut self, source: NodeId, target: NodeId, edge_type: &str) -> usize {
+        let Some(list) = self.adjacency.get_mut(&source) else { return 0 };
+        let before = list.len();
+        list.retain(|c| !(c.target == target && c.edge_type == edge_type));
+        let removed = before - list.len();
+        if removed > 0 {
+            self.record(GraphEvent::Disconnected { source, target, edge_type: edge_type.to_string() });
+        }
+        removed
+    }
 
-    #[wasm_bindgen]
-    pub fn tick(&mut self) {
-        self.current_tick += 1;
+    pub fn compare(&self, tick_a: u64, tick_b: u64) -> Option<MemoryComparison> {
+        let before = self.reconstruct_at(tick_a)?;
+        let after = self.reconstruct_at(tick_b)?;
+        Some(MemoryComparison::between(&before, &after))
     }
 
-    #[wasm_bindgen]
-    pub fn stimulate(&mut self, id_str: &str, force: f32) -> bool {
-        if let Ok(uuid) = Uuid::parse_str(id_str) {
-            if self.nodes.contains_key(&uuid) {
-                self.boost_node(uuid, force, 3); // Depth = 3 (Ripple Effect)
-                return true;
-            }
+    pub fn stimulate(&mut self, id: NodeId, force: f32) -> bool {
+        if self.nodes.contains_key(&id) {
+            self.boost_node(id, force, self.config.dynamics.max_hops); // Ripple Effect
+            return true;
         }
         false
     }
 
-    #[wasm_bindgen]
-    pub fn wake_up(&mut self) {
-        if let Some(last_time) = self.last_saved {
-            let now = Utc::now();
-            let minutes = (now - last_time).num_minutes();
-            if minutes > 0 { self.current_tick += minutes as u64; }
+    /// Gives a node its own decay curve. Pending decay is settled first so the
+    /// new rate only applies from the current tick onward. False for an
+    /// unknown id or a `Rate` outside (0, 1].
+    pub fn set_decay_override(&mut self, id: NodeId, decay: Option<DecayOverride>) -> bool {
+        // Escrito assim para que NaN também seja recusado
+        if let Some(DecayOverride::Rate(rate)) = decay {
+            if !(rate > 0.0 && rate <= 1.0) {
+                return false;
+            }
+        }
+        let tick = self.current_tick;
+        let settled = match self.nodes.get(&id) {
+            Some(node) => self.projected_activation(node.meta(), tick),
+            None => return false,
+        };
+        if let Some(node) = self.nodes.get_mut(&id) {
+            let meta = node.meta_mut();
+            meta.activation = settled;
+            meta.last_tick = tick;
+            meta.decay_override = decay;
         }
-        self.last_saved = Some(Utc::now());
+        self.sample_activation([id]);
+        self.record(GraphEvent::DecayOverridden { id, decay });
+        true
     }
 
-    // --- DREAM PROTOCOL ---
-
-    #[wasm_bindgen]
-    pub fn dream(&mut self) -> String {
-        let mut promoted = 0;
-        self.current_tick += 480; // +8 horas
-
-        // Iterar valores mutáveis do HashMap é seguro
-        for node in self.nodes.values_mut() {
-            let meta = node.meta_mut();
-            
-            // Consolidação (LTP)
-            if meta.activation > 0.7 {
-                let gain = 0.5 * (1.0 - (meta.stability / 100.0));
-                meta.stability += gain;
-                promoted += 1;
+    /// Marks a node to lead every generated context regardless of activation
+    /// (up to `config.max_context_pinned` such nodes).
+    pub fn set_always_in_context(&mut self, id: NodeId, pinned: bool) -> bool {
+        match self.nodes.get_mut(&id) {
+            Some(node) => {
+                node.meta_mut().always_in_context = pinned;
+                self.record(GraphEvent::AlwaysInContext { id, pinned });
+                true
             }
-            
-            // Washout (Limpeza de Adenosina)
-            let baseline = (meta.stability / 100.0).min(0.2);
-            meta.activation = meta.activation * 0.3 + baseline;
+            None => false,
         }
+    }
 
-        // Poda Segura
-        let removed = self.prune_low_stability(1.2);
+    pub fn get_node(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.get(&id)
+    }
 
-        format!("Ciclo REM: {} consolidadas, {} removidas.", promoted, removed)
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
     }
 
-    // --- EXPORT/IMPORT ---
+    /// Directed edges, inferred ones included.
+    pub fn edge_count(&self) -> usize {
+        self.adjacency.values().map(Vec::len).sum()
+    }
 
-    #[wasm_bindgen]
-    pub fn export_backup(&self) -> String {
-        serde_json::to_string(&self).unwrap_or("{}".to_string())
+    /// Activation projected to the current tick, without settling the decay.
+    pub fn peek_activation(&self, id: NodeId) -> Option<f32> {
+        let node = self.nodes.get(&id)?;
+        Some(self.projected_activation(node.meta(), self.live_tick()))
     }
 
-    #[wasm_bindgen]
-    pub fn import_backup(json: &str) -> LoomGraph {
-        serde_json::from_str(json).unwrap_or_else(|_| LoomGraph::new(0.95))
+    fn add_node_internal(&mut self, node: Node) -> NodeId {
+        self.catch_up_clock();
+        let id = node.meta().id;
+        self.index_node(&node);
+        self.store_node(node);
+        self.maybe_autosave();
+        id
     }
 
-    #[wasm_bindgen]
-    pub fn get_context(&mut self, min_activation: f32) -> String {
-        self.get_context_prompt(min_activation)
+    /// Stamps, records and stores an already-indexed node (no autosave check).
+    fn store_node(&mut self, node: Node) {
+        let mut n = node;
+        n.meta_mut().last_tick = self.current_tick;
+        n.meta_mut().seq = self.next_seq;
+        self.next_seq += 1;
+        if self.is_recording() {
+            self.record(GraphEvent::NodeAdded { node: Box::new(n.clone()) });
+        } else {
+            self.dirty += 1;
+        }
+        self.notify(|o| o.on_node_added(&n));
+        if self.journaling() {
+            self.journal_push(vec![GraphEvent::NodeAdded { node: Box::new(n.clone()) }], Inverse::RemoveNode(n.meta().id));
+        }
+        self.nodes.insert(n.meta().id, n);
     }
 
-    #[wasm_bindgen]
-    pub fn prune_low_stability(&mut self, threshold: f32) -> usize {
-        let to_remove: Vec<Uuid> = self.nodes.iter()
-            .filter(|(_, n)| n.meta().stability < threshold && n.meta().activation < 0.1)
-            .map(|(id, _)| *id)
-            .collect();
+    fn index_node(&mut self, node: &Node) {
+        let id = node.meta().id;
+        for token in node.tokens() {
+            self.index.entry(token).or_default().push(id);
+        }
+    }
 
-        if to_remove.is_empty() { return 0; }
+    /// Rebuilds the search index from the nodes (drops stale and duplicate entries).
+    pub fn rebuild_index(&mut self) {
+        let mut index: HashMap<String, Vec<NodeId>> = HashMap::new();
+        for node in self.nodes.values() {
+            let id = node.meta().id;
+            for token in node.tokens() {
+                index.entry(token).or_default().push(id);
+            }
+        }
+        for ids in index.values_mut() {
+            ids.sort();
+            ids.dedup();
+        }
+        self.index = index;
+        self.dirty += 1;
+    }
 
-        for id in &to_remove {
+    fn remove_nodes(&mut self, to_remove: &[NodeId]) {
+        let removed: Vec<NodeId> = to_remove.iter().copied().filter(|id| self.nodes.contains_key(id)).collect();
+        if !removed.is_empty() {
+            self.notify(|o| o.on_pruned(&removed));
+            self.forget_history(&removed);
+            for id in &removed {
+                self.tombstones.remove(id);
+            }
+            self.removed_since_save.extend(removed.iter().copied());
+        }
+        for id in to_remove {
             if let Some(node) = self.nodes.remove(id) {
                 // Limpa Index
-                let text = node.extract_text().to_lowercase();
-                for token in text.split_whitespace() {
-                    let clean = token.trim_matches(|c: char| !c.is_alphanumeric()).to_string();
-                    if let Some(list) = self.index.get_mut(&clean) {
-                        list.retain(|&uuid| uuid != *id);
+                for token in node.tokens() {
+                    if let Some(list) = self.index.get_mut(&token) {
+                        list.retain(|node_id| node_id != id);
+                        if list.is_empty() {
+                            self.index.remove(&token);
+                        }
                     }
                 }
             }
@@ -309,57 +1037,110 @@ impl LoomGraph {
         for edges in self.adjacency.values_mut() {
             edges.retain(|conn| !to_remove.contains(&conn.target));
         }
-
-        to_remove.len()
     }
-}
 
-// ----------------------------------------------------------------------------
-// API INTERNA (Rust Only)
-// ----------------------------------------------------------------------------
-impl LoomGraph {
-    fn add_node_internal(&mut self, node: Node) {
-        let id = node.meta().id;
-        let text = node.extract_text().to_lowercase();
-        
-        if !text.is_empty() {
-            let tokens: Vec<&str> = text.split_whitespace().collect();
-            for token in tokens {
-                let clean = token.trim_matches(|c: char| !c.is_alphanumeric()).to_string();
-                if !clean.is_empty() {
-                    self.index.entry(clean).or_insert(Vec::new()).push(id);
-                }
+    fn dream_cycle(&mut self) -> usize {
+        let mut promoted = 0;
+        let cap = self.config.dynamics.stability_cap;
+        self.current_tick += self.config.dynamics.ticks_for_secs(wake::DREAM_SECS); // +8 horas
+        let arousal = self.episode_arousal();
+        let factors: HashMap<NodeId, f32> = arousal.iter()
+            .map(|(id, a)| (*id, self.flashbulb_factor(*a)))
+            .collect();
+
+        // Iterar valores mutáveis do HashMap é seguro
+        for node in self.nodes.values_mut() {
+            let meta = node.meta_mut();
+            
+            // Consolidação (LTP)
+            if meta.activation > 0.7 {
+                let gain = 0.5 * (1.0 - (meta.stability / cap));
+                let factor = factors.get(&meta.id).copied().unwrap_or(1.0);
+                meta.stability = (meta.stability + gain * factor).min(cap);
+                promoted += 1;
             }
+            
+            // Washout (Limpeza de Adenosina) — não se aplica a memórias sem decaimento
+            if meta.decay_override == Some(DecayOverride::Never) { continue; }
+            let baseline = (meta.stability / cap).min(0.2);
+            meta.activation = meta.floored(meta.activation * 0.3 + baseline);
+        }
+        if self.config.history.enabled {
+            let ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+            self.sample_activation(ids);
         }
+        promoted
+    }
 
-        let mut n = node;
-        n.meta_mut().last_tick = self.current_tick;
-        self.nodes.insert(id, n);
+    pub(crate) fn is_recording(&self) -> bool {
+        self.event_log.is_enabled() || self.wal.is_attached()
+    }
+
+    pub(crate) fn record(&mut self, event: GraphEvent) {
+        self.dirty += 1;
+        self.append_wal(&event);
+        self.event_log.push(self.current_tick, event, self.config.event_log_capacity);
+    }
+
+    pub fn boost_node(&mut self, id: NodeId, amount: f32, depth: u8) -> RippleReport {
+        self.boost(id, amount, depth, true)
+    }
+
+    /// `boost_node`; replay passes `count_access = false` because the access
+    /// it caused was logged as its own `Accessed` event.
+    pub(crate) fn boost(&mut self, id: NodeId, amount: f32, depth: u8, count_access: bool) -> RippleReport {
+        self.catch_up_clock();
+        let started = Utc::now();
+        let exists = self.nodes.contains_key(&id);
+        if exists {
+            self.record(GraphEvent::Boosted { id, amount, depth });
+            self.journal.begin_boost();
+            self.journal.remember(self.nodes[&id].meta());
+            if count_access {
+                self.record_access(&[id]);
+            }
+        }
+        let mut report = RippleReport::default();
+        let budget = self.config.limits.max_ripple_nodes.unwrap_or(usize::MAX);
+        self.ripple(id, amount, depth, budget, &mut report);
+        if exists && depth > 0 {
+            self.leak_upward(id, amount, budget, &mut report);
+        }
+        if exists {
+            let before = self.journal.end_boost();
+            self.journal_push(vec![GraphEvent::Boosted { id, amount, depth }], Inverse::RestoreMeta(before));
+        }
+        if let Some(node) = self.nodes.get(&id) {
+            let activation = node.meta().activation;
+            self.notify(|o| o.on_boost(id, amount, activation));
+        }
+        self.record_timing(OpKind::Boost, started);
+        self.maybe_autosave();
+        report
     }
 
-    pub fn boost_node(&mut self, id: Uuid, amount: f32, depth: u8) {
+    fn ripple(&mut self, id: NodeId, amount: f32, depth: u8, budget: usize, report: &mut RippleReport) {
         if depth == 0 { return; }
+        if report.touched >= budget {
+            report.truncated = true; // Cascata grande demais: para aqui
+            return;
+        }
 
         // 1. Boost Local (Mutable Borrow)
+        let (tick, spread) = (self.current_tick, self.config.dynamics.spread_factor);
+        let rate = match self.nodes.get(&id) {
+            Some(node) => self.kind_decay_rate(node.kind()),
+            None => return,
+        };
+        self.sample_activation([id]);
         if let Some(node) = self.nodes.get_mut(&id) {
-            let tick = self.current_tick;
-            let decay = self.decay_rate;
-            let meta = node.meta_mut();
-            
-            // Lazy Decay
-            if meta.last_tick < tick {
-                let delta = (tick - meta.last_tick) as f32;
-                let effective_decay = decay.powf(delta / meta.stability);
-                meta.activation *= effective_decay;
-                meta.last_tick = tick;
-            }
-
-            let real_boost = (1.0 - meta.activation) * amount;
-            meta.activation += real_boost;
-            meta.stability += (50.0 - meta.stability) * (amount * 0.05);
+            self.journal.remember(node.meta());
+            Self::boost_meta(node.meta_mut(), amount, tick, rate);
+            report.touched += 1;
         } else {
             return; 
         }
+        self.sample_activation([id]);
 
         // 2. Coleta Vizinhos (Clone leve apenas da lista deste nó)
         let neighbors = if let Some(list) = self.adjacency.get(&id) {
@@ -370,117 +1151,232 @@ impl LoomGraph {
 
         // 3. Propagação Recursiva
         for conn in neighbors {
-            let ripple = amount * conn.weight * 0.5;
+            // Hierarquia (IsA/PartOf) só propaga para cima, via `leak_upward`
+            if taxonomy::is_taxonomy_edge(&conn.edge_type) {
+                continue;
+            }
+            // Contradições suprimem em vez de propagar
+            if conn.edge_type == INHIBITED_EDGE {
+                self.suppress(conn.target, amount * conn.weight * spread);
+                continue;
+            }
+            let ripple = amount * conn.weight * spread;
             if ripple.abs() > 0.01 {
-                self.boost_node(conn.target, ripple, depth - 1);
+                self.ripple(conn.target, ripple, depth - 1, budget, report);
             }
         }
     }
 
 
-    pub fn search_native(&mut self, query: &str) -> Vec<(String, f32)> {
-        let clean = query.trim().to_lowercase();
-        if clean.is_empty() { return Vec::new(); }
+    pub fn search_native(&self, query: &str) -> Vec<(NodeId, f32)> {
+        let started = Utc::now();
+        let results = self.search_kinds(query, &[]);
+        self.record_timing(OpKind::Search, started);
+        results
+    }
 
-        let mut candidates = HashSet::new();
+    /// Pure variant of `search_native`: no side effects at all (not even
+    /// timing stats), so it is safe on snapshots and from parallel readers.
+    pub fn search_readonly(&self, query: &str) -> Vec<(NodeId, f32)> {
+        self.search_bounded(query, &[]).hits
+    }
 
-        for (key, uuids) in &self.index {
-            if key.contains(&clean) {
-                for id in uuids { candidates.insert(*id); }
-            }
+    /// Like `search_native`, restricted to the given node kinds (empty = all kinds).
+    pub fn search_kinds(&self, query: &str, kinds: &[NodeKind]) -> Vec<(NodeId, f32)> {
+        self.search_bounded(query, kinds).hits
+    }
+
+    /// Settles lazy decay, then applies an asymptotic boost (no ripple).
+    fn boost_meta(meta: &mut NodeMetadata, amount: f32, tick: u64, default_rate: f32) {
+        // Lazy Decay
+        if meta.last_tick < tick {
+            meta.activation = Self::decay_projection(meta, tick, default_rate);
+            meta.last_tick = tick;
         }
 
-        let mut results = Vec::new();
-        let tick = self.current_tick;
+        let real_boost = (1.0 - meta.activation) * amount;
+        meta.activation += real_boost;
+        meta.stability += (50.0 - meta.stability) * (amount * 0.05);
+    }
 
-        for id in candidates {
-            if let Some(node) = self.nodes.get(&id) {
-                // Cálculo PROJETADO (Sem mutar o estado)
-                let meta = node.meta();
-                
-                // Se tick > last_tick, calcula quanto cairia
-                let projected_activation = if tick > meta.last_tick {
-                    let delta = (tick - meta.last_tick) as f32;
-                    let effective_decay = self.decay_rate.powf(delta / meta.stability);
-                    meta.activation * effective_decay
-                } else {
-                    meta.activation
-                };
+    pub fn projected_activation(&self, meta: &NodeMetadata, tick: u64) -> f32 {
+        let rate = match self.nodes.get(&meta.id) {
+            Some(node) => self.kind_decay_rate(node.kind()),
+            None => self.decay_rate,
+        };
+        Self::decay_projection(meta, tick, rate)
+    }
 
-                results.push((id.to_string(), projected_activation));
-            }
+    fn decay_projection(meta: &NodeMetadata, tick: u64, default_rate: f32) -> f32 {
+        let rate = match meta.decay_override {
+            Some(DecayOverride::Never) => return meta.activation,
+            Some(DecayOverride::Rate(rate)) => rate,
+            None => default_rate,
+        };
+
+        // Se tick > last_tick, calcula quanto cairia
+        if tick > meta.last_tick {
+            let delta = (tick - meta.last_tick) as f32;
+            let effective_decay = rate.powf(delta / meta.stability);
+            meta.floored(meta.activation * effective_decay)
+        } else {
+            meta.activation
         }
+    }
 
-        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-        results
+    fn sanitize_xml(&self, input: &str) -> String {
+        self.clean_text(input)
+            .replace("&", "&amp;")
+            .replace("<", "&lt;")
+            .replace(">", "&gt;")
+            .replace("\"", "&quot;")
+            .replace("'", "&apos;")
     }
 
-    fn sanitize_xml(input: &str) -> String {
-        input.replace("&", "&amp;")
-             .replace("<", "&lt;")
-             .replace(">", "&gt;")
-             .replace("\"", "&quot;")
-             .replace("'", "&apos;")
+    pub fn get_context_prompt(&self, min_activation: f32) -> String {
+        self.get_context_as(ContextFormat::Xml, min_activation)
     }
 
-    pub fn get_context_prompt(&mut self, min_activation: f32) -> String {
-        let mut buffer = String::new();
-        buffer.push_str("<active_memories>\n");
-        
-        let mut active_nodes: Vec<&Node> = self.nodes.values()
-            .filter(|n| n.meta().activation > min_activation)
-            .collect();
-        
-        active_nodes.sort_by(|a, b| b.meta().activation.partial_cmp(&a.meta().activation).unwrap());
+    fn active_nodes(&self, min_activation: f32) -> Vec<&Node> {
+        let mut active_nodes = self.ranked_nodes(|n, activation| {
+            activation > min_activation && !n.meta().always_in_context && !self.is_forgotten(n.meta().id)
+        });
 
-        if active_nodes.is_empty() {
-            buffer.push_str("  <memory>No relevant active memories.</memory>\n");
-        } else {
-            for node in active_nodes {
-                let meta = node.meta();
-                match node {
-                    Node::Concept(_, d) => {
-                        buffer.push_str(&format!(
-                            "  <memory type='concept' activation='{:.2}' stability='{:.2}'>\n    <name>{}</name>\n    <definition>{}</definition>\n  </memory>\n",
-                            meta.activation, meta.stability, 
-                            Self::sanitize_xml(&d.name), 
-                            Self::sanitize_xml(&d.definition)
-                        ));
-                    },
-                    Node::Episode(_, d) => {
-                        buffer.push_str(&format!(
-                            "  <memory type='episode' activation='{:.2}' stability='{:.2}' time='{}'>\n    <summary>{}</summary>\n  </memory>\n",
-                            meta.activation, meta.stability, 
-                            d.timestamp.to_rfc3339(), 
-                            Self::sanitize_xml(&d.summary)
-                        ));
-                    },
-                    Node::State(_, d) => {
-                        buffer.push_str(&format!(
-                            "  <state activation='{:.2}' stability='{:.2}'>\n    <mood valence='{:.2}' arousal='{:.2}' />\n  </state>\n",
-                            meta.activation, meta.stability, d.valence, d.arousal
-                        ));
-                    }
-                }
+        // Fixados vêm primeiro, independente da ativação
+        let mut pinned = self.context_pinned_nodes();
+        pinned.append(&mut active_nodes);
+        pinned
+    }
+
+    fn context_pinned_nodes(&self) -> Vec<&Node> {
+        let mut pinned = self.ranked_nodes(|n, _| n.meta().always_in_context && !self.is_forgotten(n.meta().id));
+        pinned.truncate(self.config.max_context_pinned);
+        pinned
+    }
+
+    fn memory_attrs(&self, meta: &NodeMetadata) -> String {
+        let pinned = if meta.always_in_context || meta.is_pinned() { " pinned='true'" } else { "" };
+        let source = match &meta.source {
+            Some(source) if self.config.cite_sources => {
+                format!(" source='{}' ref='{}'", source.origin, self.sanitize_xml(&source.reference))
+            }
+            _ => String::new(),
+        };
+        format!("activation='{:.2}' stability='{:.2}'{}{}", meta.activation, meta.stability, pinned, source)
+    }
+
+    fn render_memory_xml(&self, node: &Node) -> String {
+        let attrs = self.memory_attrs(node.meta());
+        match node {
+            Node::Concept(_, d) => {
+                let aliases = if d.aliases.is_empty() {
+                    String::new()
+                } else {
+                    format!("    <aliases>{}</aliases>\n", self.sanitize_xml(&d.aliases.join(", ")))
+                };
+                format!(
+                    "  <memory type='concept' {}>\n    <name>{}</name>\n{}    <definition>{}</definition>\n  </memory>\n",
+                    attrs, 
+                    self.sanitize_xml(&d.name), 
+                    aliases,
+                    self.sanitize_xml(&d.definition)
+                )
+            },
+            Node::Episode(_, d) => {
+                let attachments: String = d.attachments.iter()
+                    .map(|a| format!("    <attachment mime='{}' uri='{}' />\n", self.sanitize_xml(&a.mime_type), self.sanitize_xml(&a.uri)))
+                    .collect();
+                format!(
+                    "  <memory type='episode' {} time='{}'>\n    <summary>{}</summary>\n{}  </memory>\n",
+                    attrs, 
+                    d.timestamp.to_rfc3339(), 
+                    self.sanitize_xml(&d.summary),
+                    attachments
+                )
+            },
+            Node::State(_, d) => {
+                let (valence, arousal) = self.state_values(d);
+                let label = self.state_label(d).map(|l| format!(" label='{}'", self.sanitize_xml(l))).unwrap_or_default();
+                format!(
+                    "  <state {}>\n    <mood{} valence='{:.2}' arousal='{:.2}' />\n  </state>\n",
+                    attrs, label, valence, arousal
+                )
+            },
+            Node::Observation(_, d) => {
+                format!(
+                    "  <memory type='observation' {} time='{}' tool='{}'>\n    <input>{}</input>\n    <output>{}</output>\n  </memory>\n",
+                    attrs,
+                    d.timestamp.to_rfc3339(),
+                    self.sanitize_xml(&d.tool),
+                    self.sanitize_xml(&d.input),
+                    self.sanitize_xml(&d.output)
+                )
+            }
+            Node::Goal(_, d) => {
+                format!(
+                    "  <goal {} priority='{:.2}' due='{}' status='{}'>\n    <description>{}</description>\n  </goal>\n",
+                    attrs,
+                    d.priority,
+                    d.due,
+                    if d.completed { "completed" } else { "open" },
+                    self.sanitize_xml(&d.description)
+                )
+            }
+            Node::Skill(_, d) => {
+                let steps: String = d.steps.iter()
+                    .map(|step| format!("      <step>{}</step>\n", self.sanitize_xml(step)))
+                    .collect();
+                format!(
+                    "  <skill {} attempts='{}' success_rate='{:.2}'>\n    <name>{}</name>\n    <description>{}</description>\n    <steps>\n{}    </steps>\n  </skill>\n",
+                    attrs,
+                    d.attempts,
+                    d.success_rate(),
+                    self.sanitize_xml(&d.name),
+                    self.sanitize_xml(&d.description),
+                    steps
+                )
+            }
+            Node::Entity(_, d) => {
+                let attributes: String = d.attributes.iter()
+                    .map(|(k, v)| format!("    <attribute key='{}'>{}</attribute>\n", self.sanitize_xml(k), self.sanitize_xml(v)))
+                    .collect();
+                format!(
+                    "  <entity {} kind='{}'>\n    <name>{}</name>\n{}  </entity>\n",
+                    attrs,
+                    d.kind,
+                    self.sanitize_xml(&d.name),
+                    attributes
+                )
+            }
+            Node::Custom(_, d) => {
+                format!(
+                    "  <custom {} type='{}'>{}</custom>\n",
+                    attrs,
+                    self.sanitize_xml(&d.type_name),
+                    self.sanitize_xml(&d.payload.to_string())
+                )
             }
         }
-        buffer.push_str("</active_memories>");
-        buffer
     }
     
     // Persistência CLI
     pub fn save_to_file(&mut self, filepath: &str) -> std::io::Result<()> {
-        self.last_saved = Some(Utc::now());
+        let started = Utc::now();
+        self.last_saved = Some(self.now());
         let file = File::create(filepath)?;
         let writer = BufWriter::new(file);
         serde_json::to_writer_pretty(writer, &self)?;
+        self.mark_clean();
+        self.record_timing(OpKind::Save, started);
         Ok(())
     }
 
     pub fn load_from_file(filepath: &str) -> std::io::Result<Self> {
         let file = File::open(filepath)?;
-        let reader = BufReader::new(file);
-        let brain = serde_json::from_reader(reader)?;
+        let mut json = String::new();
+        BufReader::new(file).read_to_string(&mut json)?;
+        let brain = Self::from_backup_json(&json)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
         Ok(brain)
     }
 }
\ No newline at end of file
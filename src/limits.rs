@@ -0,0 +1,78 @@
+//! # Graceful Degradation
+//! Bounds on how much work a single search or stimulation may do, so an
+//! oversize query cannot stall the (WASM) main thread. Past the limit the
+//! operation stops early and reports `truncated: true` with partial results.
+//!
+//! Thresholds live in `config.limits`; `None` means unbounded.
+
+use crate::{LoomGraph, NodeId, NodeKind, OpKind};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchOutcome {
+    pub hits: Vec<(NodeId, f32)>,
+    /// The candidate limit was hit; `hits` covers only the candidates examined.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RippleReport {
+    /// Nodes whose activation was updated (the source included).
+    pub touched: usize,
+    /// The cascade limit was hit before the ripple died out on its own.
+    pub truncated: bool,
+}
+
+impl LoomGraph {
+    /// `search_kinds` that stops collecting after `config.limits.max_search_candidates`.
+    pub fn search_bounded(&self, query: &str, kinds: &[NodeKind]) -> SearchOutcome {
+        let clean = query.trim().to_lowercase();
+        if clean.is_empty() {
+            return SearchOutcome::default();
+        }
+        let limit = self.config.limits.max_search_candidates.unwrap_or(usize::MAX);
+
+        let mut candidates = HashSet::new();
+        let mut truncated = false;
+        'scan: for (key, ids) in &self.index {
+            if !key.contains(&clean) {
+                continue;
+            }
+            for id in ids {
+                if candidates.len() >= limit && !candidates.contains(id) {
+                    truncated = true;
+                    break 'scan;
+                }
+                candidates.insert(*id);
+            }
+        }
+
+        let tick = self.live_tick();
+        let mut hits: Vec<(NodeId, f32)> = candidates.into_iter()
+            .filter_map(|id| self.nodes.get(&id).map(|node| (id, node)))
+            .filter(|(id, node)| (kinds.is_empty() || kinds.contains(&node.kind())) && !self.tombstones.contains_key(id))
+            // Cálculo PROJETADO (Sem mutar o estado)
+            .map(|(id, node)| (id, self.projected_activation(node.meta(), tick)))
+            .collect();
+        hits.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        SearchOutcome { hits, truncated }
+    }
+
+    /// Like `search_native`, but also reports whether the candidate limit cut the scan short.
+    pub fn search_with_outcome(&self, query: &str) -> SearchOutcome {
+        let started = Utc::now();
+        let outcome = self.search_bounded(query, &[]);
+        self.record_timing(OpKind::Search, started);
+        outcome
+    }
+
+    /// Like `stimulate`, returning how far the ripple spread. `None` if the node does not exist.
+    pub fn stimulate_with_report(&mut self, id: NodeId, force: f32) -> Option<RippleReport> {
+        if !self.nodes.contains_key(&id) {
+            return None;
+        }
+        Some(self.boost_node(id, force, 3))
+    }
+}
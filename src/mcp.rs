@@ -0,0 +1,208 @@
+//! # MCP Server
+//! Exposes a brain as long-term memory over the Model Context Protocol
+//! (feature `mcp`): newline-delimited JSON-RPC 2.0 on stdio, as spoken by
+//! Claude Desktop and other MCP clients.
+//!
+//! Tools: `memory_search`, `memory_add_episode`, `memory_get_context` and
+//! `memory_stimulate`. Tool failures (unknown id, bad timestamp) come back as
+//! `isError` results so the model can see them; malformed requests get
+//! JSON-RPC errors.
+
+use crate::{ContextFormat, LoomGraph, NodeId};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+
+/// Protocol revision answered when the client doesn't ask for one.
+pub const MCP_PROTOCOL_VERSION: &str = "2024-11-05";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+pub struct McpServer {
+    pub graph: LoomGraph,
+}
+
+impl McpServer {
+    pub fn new(graph: LoomGraph) -> Self {
+        Self { graph }
+    }
+
+    /// Answers requests from `input` until EOF. `persist` runs after every
+    /// message that left the graph dirty (e.g. to save the brain file).
+    pub fn serve(
+        &mut self,
+        input: impl BufRead,
+        mut output: impl Write,
+        mut persist: impl FnMut(&mut LoomGraph) -> io::Result<()>,
+    ) -> io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle(&message),
+                Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+            };
+            if self.graph.is_dirty() {
+                persist(&mut self.graph)?;
+            }
+            if let Some(response) = response {
+                writeln!(output, "{}", response)?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles one JSON-RPC message. Notifications (no `id`) get no response.
+    pub fn handle(&mut self, message: &Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            return Some(error_response(id.unwrap_or(Value::Null), INVALID_REQUEST, "missing method"));
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": params.get("protocolVersion").and_then(Value::as_str).unwrap_or(MCP_PROTOCOL_VERSION),
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "loom", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&params),
+            _ if method.starts_with("notifications/") => return None,
+            _ => Err((METHOD_NOT_FOUND, format!("unknown method '{}'", method))),
+        };
+        // Notificações nunca recebem resposta, nem de erro
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    fn call_tool(&mut self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params.get("name").and_then(Value::as_str)
+            .ok_or((INVALID_PARAMS, "missing tool name".to_string()))?;
+        let args = params.get("arguments").cloned().unwrap_or(json!({}));
+        let outcome = match name {
+            "memory_search" => self.tool_search(&args),
+            "memory_add_episode" => self.tool_add_episode(&args),
+            "memory_get_context" => self.tool_get_context(&args),
+            "memory_stimulate" => self.tool_stimulate(&args),
+            other => return Err((INVALID_PARAMS, format!("unknown tool '{}'", other))),
+        };
+        let (text, is_error) = match outcome {
+            Ok(text) => (text, false),
+            Err(text) => (text, true),
+        };
+        Ok(json!({ "content": [{ "type": "text", "text": text }], "isError": is_error }))
+    }
+
+    fn tool_search(&mut self, args: &Value) -> Result<String, String> {
+        let query = string_arg(args, "query")?;
+        let limit = args.get("limit").and_then(Value::as_u64).unwrap_or(10) as usize;
+        let hits: Vec<Value> = self.graph.search_native(query).into_iter()
+            .take(limit)
+            .filter_map(|(id, activation)| {
+                let node = self.graph.get_node(id)?;
+                Some(json!({ "id": id, "kind": node.kind(), "label": node.label(), "activation": activation }))
+            })
+            .collect();
+        Ok(Value::Array(hits).to_string())
+    }
+
+    fn tool_add_episode(&mut self, args: &Value) -> Result<String, String> {
+        let summary = string_arg(args, "summary")?.to_string();
+        let id = match args.get("timestamp").and_then(Value::as_str) {
+            Some(time) => self.graph.add_episode_at(summary, time).map_err(|e| e.to_string())?,
+            None => self.graph.add_episode(summary),
+        };
+        Ok(id.to_string())
+    }
+
+    fn tool_get_context(&mut self, args: &Value) -> Result<String, String> {
+        let min_activation = args.get("min_activation").and_then(Value::as_f64).unwrap_or(0.3) as f32;
+        let format = match args.get("format").and_then(Value::as_str) {
+            None | Some("markdown") => ContextFormat::Markdown,
+            Some("xml") => ContextFormat::Xml,
+            Some("json") => ContextFormat::Json,
+            Some(other) => return Err(format!("unknown format '{}' (xml, json, markdown)", other)),
+        };
+        Ok(self.graph.get_context_as(format, min_activation))
+    }
+
+    fn tool_stimulate(&mut self, args: &Value) -> Result<String, String> {
+        let raw = string_arg(args, "id")?;
+        let id: NodeId = raw.parse().map_err(|_| format!("invalid id '{}'", raw))?;
+        let force = args.get("force").and_then(Value::as_f64).unwrap_or(0.5) as f32;
+        if !self.graph.stimulate(id, force) {
+            return Err(format!("no memory with id {}", id));
+        }
+        let activation = self.graph.get_node(id).map(|n| n.meta().activation).unwrap_or_default();
+        Ok(format!("stimulated {} (activation {:.2})", id, activation))
+    }
+}
+
+fn string_arg<'a>(args: &'a Value, key: &str) -> Result<&'a str, String> {
+    args.get(key).and_then(Value::as_str).ok_or_else(|| format!("missing string argument '{}'", key))
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "memory_search",
+            "description": "Search long-term memory. Returns matching memories, most active first.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 1, "default": 10 },
+                },
+                "required": ["query"],
+            },
+        },
+        {
+            "name": "memory_add_episode",
+            "description": "Remember something that happened. Returns the new memory's id.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "summary": { "type": "string" },
+                    "timestamp": { "type": "string", "description": "RFC 3339; defaults to now" },
+                },
+                "required": ["summary"],
+            },
+        },
+        {
+            "name": "memory_get_context",
+            "description": "Currently active memories, formatted for a prompt.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "min_activation": { "type": "number", "default": 0.3 },
+                    "format": { "type": "string", "enum": ["markdown", "xml", "json"] },
+                },
+            },
+        },
+        {
+            "name": "memory_stimulate",
+            "description": "Reinforce a memory (and, more weakly, its neighbors) so it stays active.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "force": { "type": "number", "default": 0.5 },
+                },
+                "required": ["id"],
+            },
+        },
+    ])
+}
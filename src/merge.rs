@@ -0,0 +1,187 @@
+//! # Merging
+//! Unions another brain into this one (e.g. syncing an agent across devices).
+//! Nodes are matched by id first, then concepts by normalized name; matched
+//! nodes are combined according to a `MergePolicy`, everything else is added.
+//! Nodes the other graph has forgotten are not brought back: they are left
+//! out, and forgotten here too if this graph has them under the same id. Its
+//! archive records are kept for ids this graph doesn't hold.
+//!
+//! Inserted nodes and edges go through the event log like any other insert,
+//! but in-place updates of matched nodes don't (and the undo journal is
+//! cleared), so save after merging.
+
+use crate::{tokenize, Connection, LoomGraph, Node, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MergePolicy {
+    /// Matched nodes keep the higher activation and the higher stability
+    /// (independently); edges keep the higher weight. Content stays as is.
+    #[default]
+    KeepMax,
+    /// Matched nodes keep this graph's version.
+    PreferSelf,
+    /// Matched nodes take the other graph's version (content, metadata and edge weights).
+    PreferOther,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MergeReport {
+    pub inserted: usize,
+    /// Nodes present in both graphs under the same id.
+    pub updated: usize,
+    /// Incoming concepts folded into an existing concept with the same name.
+    pub concepts_merged: usize,
+    pub edges_inserted: usize,
+    /// Incoming id -> id in this graph.
+    pub id_map: HashMap<NodeId, NodeId>,
+}
+
+impl LoomGraph {
+    pub fn merge(&mut self, other: LoomGraph, policy: MergePolicy) -> MergeReport {
+        let mut report = MergeReport::default();
+        let tick = self.current_tick;
+
+        let mut inserted = Vec::new();
+        let mut concepts_by_name: HashMap<Vec<String>, NodeId> = HashMap::new();
+        for (id, node) in &self.nodes {
+            if let Node::Concept(_, data) = node {
+                // Com nomes repetidos, o menor id vence (determinístico)
+                let entry = concepts_by_name.entry(tokenize(&data.name)).or_insert(*id);
+                *entry = (*entry).min(*id);
+            }
+        }
+
+        let mut incoming: Vec<(NodeId, Node)> = other.nodes.iter()
+            .filter(|(id, _)| !other.tombstones.contains_key(id))
+            .map(|(id, node)| {
+                // Decaimento pendente é assentado no relógio do outro grafo
+                let mut node = node.clone();
+                let meta = node.meta_mut();
+                meta.activation = other.projected_activation(meta, other.live_tick());
+                meta.last_tick = tick;
+                (*id, node)
+            })
+            .collect();
+        incoming.sort_by_key(|(id, _)| *id);
+
+        for (old_id, node) in incoming {
+            let by_name = match &node {
+                Node::Concept(_, data) => concepts_by_name.get(&tokenize(&data.name)).copied(),
+                _ => None,
+            };
+            let target = if self.nodes.contains_key(&old_id) { Some(old_id) } else { by_name };
+
+            match target {
+                Some(existing) => {
+                    self.merge_node(existing, node, policy);
+                    if existing == old_id {
+                        report.updated += 1;
+                    } else {
+                        report.concepts_merged += 1;
+                    }
+                    report.id_map.insert(old_id, existing);
+                }
+                None => {
+                    if let Node::Concept(_, data) = &node {
+                        concepts_by_name.insert(tokenize(&data.name), old_id);
+                    }
+                    self.add_node_internal(node);
+                    inserted.push(old_id);
+                    report.inserted += 1;
+                    report.id_map.insert(old_id, old_id);
+                }
+            }
+        }
+
+        // Esquecido lá, esquecido aqui (o tombstone viaja junto)
+        let mut forgotten: Vec<NodeId> = other.tombstones.keys()
+            .filter(|id| self.nodes.contains_key(id))
+            .copied()
+            .collect();
+        forgotten.sort();
+        for id in forgotten {
+            self.forget(id);
+        }
+        for (id, record) in other.archived {
+            if !self.nodes.contains_key(&id) {
+                self.archived.entry(id).or_insert(record);
+            }
+        }
+
+        let mut sources: Vec<_> = other.adjacency.into_iter().collect();
+        sources.sort_by_key(|(id, _)| *id);
+        for (old_source, edges) in sources {
+            let Some(&source) = report.id_map.get(&old_source) else { continue };
+            for edge in edges {
+                let Some(&target) = report.id_map.get(&edge.target) else { continue };
+                // Conceitos fundidos podem transformar a aresta em laço
+                if source == target {
+                    continue;
+                }
+                let existing = self.adjacency.get(&source)
+                    .and_then(|list| list.iter().find(|c| c.target == target && c.edge_type == edge.edge_type))
+                    .map(|c| c.weight);
+                match existing {
+                    Some(weight) => {
+                        let merged = match policy {
+                            MergePolicy::KeepMax => weight.max(edge.weight),
+                            MergePolicy::PreferOther => edge.weight,
+                            MergePolicy::PreferSelf => weight,
+                        };
+                        self.reweight_edge(source, target, &edge.edge_type, merged);
+                    }
+                    None => {
+                        self.insert_edge(source, Connection { target, ..edge });
+                        report.edges_inserted += 1;
+                    }
+                }
+            }
+        }
+
+        self.rebuild_index();
+        self.journal.clear();
+        self.dirty += 1;
+        self.enforce_capacity(&inserted);
+        self.maybe_autosave();
+        report
+    }
+
+    /// Combines `incoming` into the existing node `id` according to `policy`.
+    fn merge_node(&mut self, id: NodeId, incoming: Node, policy: MergePolicy) {
+        let tick = self.current_tick;
+        let Some(current) = self.nodes.get(&id) else { return };
+        let settled = self.projected_activation(current.meta(), tick);
+        let Some(node) = self.nodes.get_mut(&id) else { return };
+        node.meta_mut().activation = settled;
+        node.meta_mut().last_tick = tick;
+
+        match policy {
+            MergePolicy::KeepMax => {
+                let theirs = incoming.meta();
+                let meta = node.meta_mut();
+                meta.activation = meta.activation.max(theirs.activation);
+                meta.stability = meta.stability.max(theirs.stability);
+                meta.access_count = meta.access_count.max(theirs.access_count);
+                meta.last_accessed = meta.last_accessed.max(theirs.last_accessed);
+                for (key, value) in &theirs.tags {
+                    meta.tags.entry(key.clone()).or_insert_with(|| value.clone());
+                }
+            }
+            MergePolicy::PreferSelf => {}
+            MergePolicy::PreferOther => {
+                // Mantém id e ordem de inserção locais; o resto vem do outro grafo
+                let seq = node.meta().seq;
+                let mut replacement = incoming;
+                let meta = replacement.meta_mut();
+                meta.id = id;
+                meta.seq = seq;
+                *node = replacement;
+            }
+        }
+    }
+}
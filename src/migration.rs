@@ -0,0 +1,139 @@
+//! # Backup Migrations
+//! Every backup carries a `schema_version`. Loading an older backup runs the
+//! registered migrations one step at a time (v1 -> v2 -> ...) over the raw
+//! JSON tree before deserializing, so graphs written by earlier crate versions
+//! are upgraded instead of silently discarded.
+
+use crate::LoomGraph;
+use serde_json::Value;
+use std::fmt;
+
+/// Schema written by this crate version.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Backups written before versioning existed have no `schema_version` field.
+pub(crate) fn legacy_schema_version() -> u32 {
+    1
+}
+
+pub(crate) fn current_schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    Malformed(String),
+    FutureVersion { found: u32, supported: u32 },
+    MissingStep { from: u32 },
+    StepFailed { from: u32, reason: String },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Malformed(e) => write!(f, "backup is not a valid LoomDB graph: {}", e),
+            MigrationError::FutureVersion { found, supported } => write!(
+                f, "backup schema v{} is newer than this build supports (v{})", found, supported
+            ),
+            MigrationError::MissingStep { from } => write!(f, "no migration registered from schema v{}", from),
+            MigrationError::StepFailed { from, reason } => write!(f, "migration from schema v{} failed: {}", from, reason),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// One upgrade step, from `source_version()` to `source_version() + 1`.
+pub trait Migration {
+    fn source_version(&self) -> u32;
+    fn migrate(&self, backup: &mut Value) -> Result<(), String>;
+}
+
+/// v1 -> v2: namespaces, decay overrides, config and the event log were added,
+/// all of which deserialize from defaults. Only the version stamp changes.
+struct StampSchemaVersion;
+
+impl Migration for StampSchemaVersion {
+    fn source_version(&self) -> u32 {
+        1
+    }
+
+    fn migrate(&self, _backup: &mut Value) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+pub struct MigrationRegistry {
+    migrations: Vec<Box<dyn Migration>>,
+}
+
+impl Default for MigrationRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(StampSchemaVersion);
+        registry
+    }
+}
+
+impl MigrationRegistry {
+    pub fn empty() -> Self {
+        Self { migrations: Vec::new() }
+    }
+
+    pub fn register(&mut self, migration: impl Migration + 'static) {
+        self.migrations.push(Box::new(migration));
+    }
+
+    /// Upgrades a raw backup tree to `SCHEMA_VERSION` in place.
+    pub fn upgrade(&self, backup: &mut Value) -> Result<(), MigrationError> {
+        let obj = backup.as_object().ok_or_else(|| MigrationError::Malformed("expected a JSON object".into()))?;
+        let mut version = match obj.get("schema_version") {
+            None => legacy_schema_version(),
+            Some(v) => v.as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| MigrationError::Malformed("schema_version is not an integer".into()))?,
+        };
+
+        if version > SCHEMA_VERSION {
+            return Err(MigrationError::FutureVersion { found: version, supported: SCHEMA_VERSION });
+        }
+
+        while version < SCHEMA_VERSION {
+            let step = self.migrations.iter()
+                .find(|m| m.source_version() == version)
+                .ok_or(MigrationError::MissingStep { from: version })?;
+            step.migrate(backup).map_err(|reason| MigrationError::StepFailed { from: version, reason })?;
+            version += 1;
+            backup["schema_version"] = Value::from(version);
+        }
+        Ok(())
+    }
+
+    /// Uses the directly decoded graph when it is already current; otherwise
+    /// migrates the raw tree and decodes that.
+    pub(crate) fn load(
+        &self,
+        direct: Option<LoomGraph>,
+        raw: impl FnOnce() -> Result<Value, String>,
+    ) -> Result<LoomGraph, MigrationError> {
+        if let Some(graph) = direct.filter(|g| g.schema_version == SCHEMA_VERSION) {
+            return Ok(graph);
+        }
+        let mut tree = raw().map_err(MigrationError::Malformed)?;
+        self.upgrade(&mut tree)?;
+        serde_json::from_value(tree).map_err(|e| MigrationError::Malformed(e.to_string()))
+    }
+}
+
+impl LoomGraph {
+    pub fn from_backup_json(json: &str) -> Result<Self, MigrationError> {
+        Self::from_backup_json_with(json, &MigrationRegistry::default())
+    }
+
+    pub fn from_backup_json_with(json: &str, registry: &MigrationRegistry) -> Result<Self, MigrationError> {
+        registry.load(
+            serde_json::from_str(json).ok(),
+            || serde_json::from_str(json).map_err(|e| e.to_string()),
+        )
+    }
+}
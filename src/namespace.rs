@@ -0,0 +1,87 @@
+//! # Namespaces
+//! Partitions within one graph (e.g. one per user). New nodes go into the
+//! active namespace; search, context and pruning can be scoped to one, and
+//! auto-linking never crosses namespace boundaries.
+
+use crate::{ContextFormat, GraphEvent, LoomGraph, NodeId};
+use std::collections::BTreeSet;
+
+impl LoomGraph {
+    /// Namespace given to nodes created from now on (`None` = global).
+    pub fn set_active_namespace(&mut self, namespace: Option<String>) {
+        self.active_namespace = namespace;
+    }
+
+    /// Runs `f` with `namespace` active, restoring the previous one afterwards.
+    pub fn in_namespace<R>(&mut self, namespace: &str, f: impl FnOnce(&mut Self) -> R) -> R {
+        let previous = self.active_namespace.replace(namespace.to_string());
+        let result = f(self);
+        self.active_namespace = previous;
+        result
+    }
+
+    /// Moves a node to another namespace. Returns false if it doesn't exist.
+    pub fn set_namespace(&mut self, id: NodeId, namespace: Option<String>) -> bool {
+        match self.nodes.get_mut(&id) {
+            Some(node) => {
+                node.meta_mut().namespace = namespace.clone();
+                self.record(GraphEvent::NamespaceSet { id, namespace });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Namespaces in use, sorted.
+    pub fn namespaces(&self) -> Vec<String> {
+        let names: BTreeSet<&String> = self.nodes.values().filter_map(|n| n.meta().namespace.as_ref()).collect();
+        names.into_iter().cloned().collect()
+    }
+
+    /// Ids in `namespace`, sorted.
+    pub fn namespace_nodes(&self, namespace: &str) -> Vec<NodeId> {
+        let mut ids: Vec<NodeId> = self.nodes.iter()
+            .filter(|(_, n)| n.meta().namespace.as_deref() == Some(namespace))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// `search_native` restricted to `namespace`.
+    pub fn search_in_namespace(&self, namespace: &str, query: &str) -> Vec<(NodeId, f32)> {
+        let mut hits = self.search_native(query);
+        hits.retain(|(id, _)| self.nodes[id].meta().namespace.as_deref() == Some(namespace));
+        hits
+    }
+
+    /// `get_context_as` restricted to `namespace`.
+    pub fn get_context_in_namespace(&self, namespace: &str, format: ContextFormat, min_activation: f32) -> String {
+        self.context_builder().namespace(namespace).min_activation(min_activation).format(format).build()
+    }
+
+    /// `prune_low_stability` applied only to `namespace`.
+    pub fn prune_namespace(&mut self, namespace: &str, threshold: f32) -> usize {
+        let scoped: Vec<NodeId> = self.prune_candidates(threshold).into_iter()
+            .filter(|id| self.nodes[id].meta().namespace.as_deref() == Some(namespace))
+            .collect();
+        if scoped.is_empty() {
+            return 0;
+        }
+        self.discard_nodes(&scoped);
+        self.maybe_autosave();
+        scoped.len()
+    }
+
+    /// Deletes every node in `namespace` (through the archive tier and journal,
+    /// like any prune). Returns how many were removed.
+    pub fn drop_namespace(&mut self, namespace: &str) -> usize {
+        let ids = self.namespace_nodes(namespace);
+        if ids.is_empty() {
+            return 0;
+        }
+        self.discard_nodes(&ids);
+        self.maybe_autosave();
+        ids.len()
+    }
+}
@@ -0,0 +1,90 @@
+//! # Observers
+//! Callbacks for memory dynamics: nodes and edges being added, boosts,
+//! memories fading below `config.decay_alert_threshold`, and pruning.
+//! Observers are runtime-only: not persisted, and a cloned graph starts
+//! without them (like the autosave handle).
+//!
+//! Decay is lazy, so fading is detected on `tick()` / `fast_forward()` by
+//! comparing each node's projected activation before and after the step.
+//! That scan only runs while at least one observer is registered.
+
+use crate::{Connection, LoomGraph, Node, NodeId};
+use std::sync::Arc;
+
+/// Every method has a no-op default; implement the ones you need.
+/// Observers get `&self`, so use interior mutability to collect state.
+pub trait Observer: Send + Sync {
+    fn on_node_added(&self, _node: &Node) {}
+    fn on_edge_added(&self, _source: NodeId, _edge: &Connection) {}
+    /// `activation` is the boosted node's value after the boost.
+    fn on_boost(&self, _id: NodeId, _amount: f32, _activation: f32) {}
+    /// The node's projected activation just dropped below the alert threshold.
+    fn on_decay_below(&self, _id: NodeId, _activation: f32) {}
+    fn on_pruned(&self, _ids: &[NodeId]) {}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(pub u64);
+
+#[derive(Default)]
+pub struct Observers {
+    next_id: u64,
+    list: Vec<(ObserverId, Arc<dyn Observer>)>,
+}
+
+impl Clone for Observers {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl Observers {
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+}
+
+impl LoomGraph {
+    pub fn add_observer(&mut self, observer: Arc<dyn Observer>) -> ObserverId {
+        let id = ObserverId(self.observers.next_id);
+        self.observers.next_id += 1;
+        self.observers.list.push((id, observer));
+        id
+    }
+
+    pub fn remove_observer(&mut self, id: ObserverId) -> bool {
+        let before = self.observers.list.len();
+        self.observers.list.retain(|(existing, _)| *existing != id);
+        self.observers.list.len() != before
+    }
+
+    pub(crate) fn notify(&self, event: impl Fn(&dyn Observer)) {
+        for (_, observer) in &self.observers.list {
+            event(observer.as_ref());
+        }
+    }
+
+    /// Fires `on_decay_below` for nodes that crossed the threshold between two ticks.
+    pub(crate) fn notify_decay(&self, from_tick: u64, to_tick: u64) {
+        if self.observers.is_empty() || to_tick <= from_tick {
+            return;
+        }
+        let threshold = self.config.decay_alert_threshold;
+        let mut faded: Vec<(NodeId, f32)> = self.nodes.values()
+            .filter_map(|node| {
+                let meta = node.meta();
+                let before = self.projected_activation(meta, from_tick);
+                let after = self.projected_activation(meta, to_tick);
+                (before >= threshold && after < threshold).then_some((meta.id, after))
+            })
+            .collect();
+        faded.sort_by_key(|(id, _)| *id);
+        for (id, activation) in faded {
+            self.notify(|o| o.on_decay_below(id, activation));
+        }
+    }
+}
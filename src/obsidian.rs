@@ -0,0 +1,180 @@
+//! # Markdown / Obsidian Vault Import
+//! Bootstraps a brain from a folder of notes (feature `obsidian`):
+//!
+//! - every `*.md` file becomes a concept: the front-matter `title` (or the
+//!   file name) is the name, the body the definition, and front-matter
+//!   `aliases` become concept aliases;
+//! - `[[wikilinks]]` (`[[Note]]`, `[[Note|label]]`, `[[Note#Heading]]`)
+//!   become `Associated` edges between the notes' concepts;
+//! - front-matter dates (`date`, `created`, `updated`, `modified`) become
+//!   episodes at that time with a `Mentioned` edge to the note.
+//!
+//! Hidden folders (`.obsidian`, `.git`, `.trash`) are skipped. Links to
+//! notes that don't exist are counted, not created.
+
+use crate::{parse_timestamp, Connection, LoomGraph, NodeId, ASSOCIATED_EDGE, MENTIONED_EDGE};
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Front-matter keys read as dates, in the order their episodes are added.
+const DATE_KEYS: [&str; 4] = ["date", "created", "updated", "modified"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VaultReport {
+    /// Note title to concept id.
+    pub notes: BTreeMap<String, NodeId>,
+    pub links: usize,
+    /// Wikilinks to notes that aren't in the vault.
+    pub unresolved_links: usize,
+    pub episodes: usize,
+}
+
+impl LoomGraph {
+    /// Imports every Markdown note under `dir` (recursively, in path order).
+    pub fn import_markdown_vault(&mut self, dir: impl AsRef<Path>) -> io::Result<VaultReport> {
+        let mut paths = Vec::new();
+        collect_markdown(dir.as_ref(), &mut paths)?;
+        paths.sort();
+
+        let mut notes = Vec::with_capacity(paths.len());
+        for path in &paths {
+            let text = fs::read_to_string(path)?;
+            let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+            notes.push((stem, parse_note(&text)));
+        }
+
+        let mut report = VaultReport::default();
+        // Links resolvem pelo nome do arquivo, título ou apelido (sem caixa)
+        let mut by_name: HashMap<String, NodeId> = HashMap::new();
+        let mut ids = Vec::with_capacity(notes.len());
+        for (stem, note) in &notes {
+            let title = note.title.clone().unwrap_or_else(|| stem.clone());
+            let id = self.add_concept(title.clone(), note.body.trim().to_string());
+            for alias in &note.aliases {
+                self.add_alias(id, alias);
+            }
+            for name in std::iter::once(stem).chain(note.title.iter()).chain(&note.aliases) {
+                by_name.entry(name.to_lowercase()).or_insert(id);
+            }
+            report.notes.insert(title, id);
+            ids.push(id);
+        }
+
+        let wikilink = Regex::new(r"\[\[([^\]|#]+)(?:#[^\]|]*)?(?:\|[^\]]*)?\]\]").expect("valid wikilink pattern");
+        for ((_, note), source) in notes.iter().zip(&ids) {
+            let mut linked = Vec::new();
+            for capture in wikilink.captures_iter(&note.body) {
+                let target_name = capture[1].trim();
+                // `[[pasta/Nota]]` aponta para o arquivo `Nota`
+                let target_name = target_name.rsplit('/').next().unwrap_or(target_name).to_lowercase();
+                match by_name.get(&target_name) {
+                    Some(target) if target != source && !linked.contains(target) => {
+                        linked.push(*target);
+                        self.insert_edge(*source, Connection {
+                            target: *target,
+                            weight: 0.5,
+                            edge_type: ASSOCIATED_EDGE.to_string(),
+                            inferred: false,
+                        });
+                        report.links += 1;
+                    }
+                    Some(_) => {}
+                    None => report.unresolved_links += 1,
+                }
+            }
+
+            for (key, date) in &note.dates {
+                let name = self.get_node(*source).map(|n| n.label()).unwrap_or_default();
+                let Ok(episode) = self.add_episode_with_time(format!("Note \"{}\" {}", name, key), *date) else { continue };
+                self.insert_edge(episode, Connection {
+                    target: *source,
+                    weight: 1.0,
+                    edge_type: MENTIONED_EDGE.to_string(),
+                    inferred: false,
+                });
+                report.episodes += 1;
+            }
+        }
+        Ok(report)
+    }
+}
+
+struct Note {
+    title: Option<String>,
+    body: String,
+    aliases: Vec<String>,
+    dates: Vec<(String, DateTime<Utc>)>,
+}
+
+fn collect_markdown(dir: &Path, out: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_markdown(&path, out)?;
+        } else if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("md")) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Splits the YAML front matter (only `key: value` and simple lists are
+/// understood) from the body.
+fn parse_note(text: &str) -> Note {
+    let mut note = Note { title: None, body: text.to_string(), aliases: Vec::new(), dates: Vec::new() };
+    let Some(rest) = text.strip_prefix("---\n").or_else(|| text.strip_prefix("---\r\n")) else { return note };
+    let Some(end) = rest.find("\n---") else { return note };
+    let (front, body) = (&rest[..end], &rest[end + 4..]);
+    note.body = body.trim_start_matches(['\r', '\n']).to_string();
+
+    let mut current_key = String::new();
+    for line in front.lines() {
+        if let Some(item) = line.trim_start().strip_prefix("- ") {
+            // Item de lista em bloco (`aliases:` seguido de `- x`)
+            if current_key == "aliases" {
+                note.aliases.push(unquote(item));
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else { continue };
+        current_key = key.trim().to_lowercase();
+        let value = value.trim();
+        match current_key.as_str() {
+            "title" if !value.is_empty() => note.title = Some(unquote(value)),
+            "aliases" | "alias" => {
+                let list = value.trim_start_matches('[').trim_end_matches(']');
+                note.aliases.extend(list.split(',').map(unquote).filter(|a| !a.is_empty()));
+                current_key = "aliases".to_string();
+            }
+            key if DATE_KEYS.contains(&key) => {
+                if let Some(date) = parse_date(&unquote(value)) {
+                    note.dates.push((key.to_string(), date));
+                }
+            }
+            _ => {}
+        }
+    }
+    note.dates.sort_by_key(|(key, _)| DATE_KEYS.iter().position(|k| k == key));
+    note
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches(|c| c == '"' || c == '\'').trim().to_string()
+}
+
+/// RFC 3339, or a bare `YYYY-MM-DD` (midnight UTC).
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    parse_timestamp(value).ok().or_else(|| {
+        let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+        Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+    })
+}
@@ -0,0 +1,166 @@
+//! # Memory Packs
+//! Shareable knowledge bundles (concepts + relations with preset stability)
+//! that can be installed into any brain via `graft`.
+//!
+//! With the `signing` feature, packs carry an Ed25519 signature over their
+//! canonical JSON payload and `install_signed_pack` refuses tampered bundles.
+
+use crate::{ConceptData, GraftReport, IdPolicy, LoomGraph, Node, NodeKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub name: String,
+    pub author: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackConcept {
+    pub name: String,
+    pub definition: String,
+    #[serde(default = "default_stability")]
+    pub stability: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackRelation {
+    pub from: String,
+    pub to: String,
+    pub weight: f32,
+}
+
+fn default_stability() -> f32 {
+    1.0
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryPack {
+    pub manifest: PackManifest,
+    pub concepts: Vec<PackConcept>,
+    #[serde(default)]
+    pub relations: Vec<PackRelation>,
+    /// Hex-encoded Ed25519 signature over `signed_payload()`.
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackError {
+    UnknownConcept(String),
+    MissingSignature,
+    InvalidSignature,
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackError::UnknownConcept(name) => write!(f, "relation references unknown concept '{}'", name),
+            PackError::MissingSignature => write!(f, "pack is not signed"),
+            PackError::InvalidSignature => write!(f, "pack signature does not match its contents"),
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
+impl MemoryPack {
+    pub fn new(manifest: PackManifest) -> Self {
+        Self { manifest, concepts: Vec::new(), relations: Vec::new(), signature: None }
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or("{}".to_string())
+    }
+
+    /// Canonical bytes covered by the signature (everything except the signature itself).
+    pub fn signed_payload(&self) -> Vec<u8> {
+        serde_json::to_vec(&(&self.manifest, &self.concepts, &self.relations)).unwrap_or_default()
+    }
+
+    /// Builds the pack as a standalone graph, ready to be grafted.
+    pub fn to_graph(&self) -> Result<LoomGraph, PackError> {
+        let mut graph = LoomGraph::new(0.95);
+        let mut by_name = HashMap::new();
+
+        for concept in &self.concepts {
+            let mut meta = graph.new_meta(NodeKind::Concept);
+            meta.stability = concept.stability;
+            let node = Node::Concept(meta, ConceptData {
+                name: concept.name.clone(),
+                definition: concept.definition.clone(),
+                aliases: Vec::new(),
+                confidence: None,
+            });
+            by_name.insert(concept.name.clone(), graph.add_node_internal(node));
+        }
+
+        for rel in &self.relations {
+            let from = by_name.get(&rel.from).ok_or_else(|| PackError::UnknownConcept(rel.from.clone()))?;
+            let to = by_name.get(&rel.to).ok_or_else(|| PackError::UnknownConcept(rel.to.clone()))?;
+            graph.connect(*from, *to, rel.weight);
+        }
+
+        Ok(graph)
+    }
+
+    #[cfg(feature = "signing")]
+    pub fn sign(&mut self, key: &ed25519_dalek::SigningKey) {
+        use ed25519_dalek::Signer;
+        let signature = key.sign(&self.signed_payload());
+        self.signature = Some(to_hex(&signature.to_bytes()));
+    }
+
+    #[cfg(feature = "signing")]
+    pub fn verify(&self, key: &ed25519_dalek::VerifyingKey) -> Result<(), PackError> {
+        use ed25519_dalek::{Signature, Verifier};
+        let hex = self.signature.as_ref().ok_or(PackError::MissingSignature)?;
+        let bytes: [u8; 64] = from_hex(hex)
+            .and_then(|b| b.try_into().ok())
+            .ok_or(PackError::InvalidSignature)?;
+        key.verify(&self.signed_payload(), &Signature::from_bytes(&bytes))
+            .map_err(|_| PackError::InvalidSignature)
+    }
+}
+
+impl LoomGraph {
+    /// Installs a pack under a namespace named after the pack, with fresh ids.
+    pub fn install_pack(&mut self, pack: &MemoryPack) -> Result<GraftReport, PackError> {
+        let subgraph = pack.to_graph()?;
+        Ok(self.graft(subgraph, &pack.manifest.name, IdPolicy::Regenerate))
+    }
+
+    #[cfg(feature = "signing")]
+    pub fn install_signed_pack(
+        &mut self,
+        pack: &MemoryPack,
+        trusted_key: &ed25519_dalek::VerifyingKey,
+    ) -> Result<GraftReport, PackError> {
+        pack.verify(trusted_key)?;
+        self.install_pack(pack)
+    }
+}
+
+#[cfg(feature = "signing")]
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(feature = "signing")]
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
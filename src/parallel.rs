@@ -0,0 +1,113 @@
+//! # Sweeps
+//! Whole-graph passes: settling lazy decay, ranking nodes for context, and
+//! boosting many nodes at once. With the `parallel` feature (native only)
+//! they run on rayon's thread pool; otherwise sequentially. Results are the
+//! same either way.
+//!
+//! Compare with `cargo bench --bench sweeps` with and without `--features parallel`.
+
+use crate::{GraphEvent, Inverse, LoomGraph, Node, NodeId, OpKind};
+use chrono::Utc;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+fn by_activation_desc(a: &(&Node, f32), b: &(&Node, f32)) -> Ordering {
+    b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal)
+}
+
+impl LoomGraph {
+    /// Writes every node's projected activation back (materializes lazy decay
+    /// up to the current tick), so stored activations are current. Returns
+    /// the number of nodes visited.
+    pub fn settle_decay(&mut self) -> usize {
+        self.catch_up_clock();
+        let (tick, rates) = (self.current_tick, self.decay_rates());
+        let settle = |node: &mut Node| {
+            let rate = rates.of(node.kind());
+            let meta = node.meta_mut();
+            if meta.last_tick < tick {
+                meta.activation = Self::decay_projection(meta, tick, rate);
+                meta.last_tick = tick;
+            }
+        };
+
+        #[cfg(feature = "parallel")]
+        self.nodes.par_iter_mut().for_each(|(_, node)| settle(node));
+        #[cfg(not(feature = "parallel"))]
+        self.nodes.iter_mut().for_each(|(_, node)| settle(node));
+
+        if self.config.history.enabled {
+            let ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+            self.sample_activation(ids);
+        }
+        self.dirty += 1;
+        self.nodes.len()
+    }
+
+    /// Nodes passing `keep` (called with the activation projected to
+    /// `live_tick`), highest projected activation first.
+    pub(crate) fn ranked_nodes(&self, keep: impl Fn(&Node, f32) -> bool + Sync) -> Vec<&Node> {
+        let (tick, rates) = (self.live_tick(), self.decay_rates());
+        let project = |n: &Node| Self::decay_projection(n.meta(), tick, rates.of(n.kind()));
+        #[cfg(feature = "parallel")]
+        let mut ranked: Vec<(&Node, f32)> = self.nodes.par_iter().map(|(_, n)| (n, project(n))).filter(|(n, a)| keep(n, *a)).collect();
+        #[cfg(not(feature = "parallel"))]
+        let mut ranked: Vec<(&Node, f32)> = self.nodes.values().map(|n| (n, project(n))).filter(|(n, a)| keep(n, *a)).collect();
+
+        #[cfg(feature = "parallel")]
+        ranked.par_sort_by(by_activation_desc);
+        #[cfg(not(feature = "parallel"))]
+        ranked.sort_by(by_activation_desc);
+        ranked.into_iter().map(|(n, _)| n).collect()
+    }
+
+    /// Raises each node's own activation and stability in one sweep. Unlike
+    /// `boost_node`, there is no ripple, upward leak, inhibition or access
+    /// counting. Returns how many nodes were boosted.
+    pub fn boost_batch(&mut self, ids: &[NodeId], amount: f32) -> usize {
+        let started = Utc::now();
+        let targets: HashSet<NodeId> = ids.iter().copied().filter(|id| self.nodes.contains_key(id)).collect();
+        let mut ordered: Vec<NodeId> = targets.iter().copied().collect();
+        ordered.sort();
+        if !ordered.is_empty() {
+            self.record(GraphEvent::BatchBoosted { ids: ordered.clone(), amount });
+            self.journal.begin_boost();
+            for id in &ordered {
+                self.journal.remember(self.nodes[id].meta());
+            }
+            let before = self.journal.end_boost();
+            self.journal_push(vec![GraphEvent::BatchBoosted { ids: ordered, amount }], Inverse::RestoreMeta(before));
+        }
+
+        let (tick, rates) = (self.current_tick, self.decay_rates());
+        self.sample_activation(targets.iter().copied());
+        let boost = |(id, node): (&NodeId, &mut Node)| {
+            if targets.contains(id) {
+                let rate = rates.of(node.kind());
+                Self::boost_meta(node.meta_mut(), amount, tick, rate);
+            }
+        };
+
+        #[cfg(feature = "parallel")]
+        self.nodes.par_iter_mut().for_each(boost);
+        #[cfg(not(feature = "parallel"))]
+        self.nodes.iter_mut().for_each(boost);
+
+        self.sample_activation(targets.iter().copied());
+        if !self.observers.is_empty() {
+            let mut boosted: Vec<(NodeId, f32)> = targets.iter()
+                .filter_map(|id| self.nodes.get(id).map(|n| (*id, n.meta().activation)))
+                .collect();
+            boosted.sort_by_key(|(id, _)| *id);
+            for (id, activation) in boosted {
+                self.notify(|o| o.on_boost(id, amount, activation));
+            }
+        }
+        self.record_timing(OpKind::Boost, started);
+        self.maybe_autosave();
+        targets.len()
+    }
+}
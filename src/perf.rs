@@ -0,0 +1,132 @@
+//! # Performance Stats
+//! Wall-clock timing of the hot operations (search, boost, prune, save) with
+//! rolling percentiles, plus a bounded log of operations slower than
+//! `config.perf.slow_op_threshold_ms`. Lets integrators notice when graph
+//! growth starts degrading latency.
+//!
+//! Timing uses `chrono::Utc::now()`, which also works under WASM (millisecond
+//! resolution there).
+
+use crate::LoomGraph;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, PoisonError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OpKind {
+    Search,
+    Boost,
+    Prune,
+    Save,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlowOp {
+    pub op: OpKind,
+    pub duration_ms: f64,
+    pub tick: u64,
+    pub at: DateTime<Utc>,
+}
+
+/// Percentiles over the most recent `config.perf.window` samples of one operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpStats {
+    /// Total calls since the stats were last cleared (not limited to the window).
+    pub count: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PerfStats {
+    pub ops: HashMap<OpKind, OpStats>,
+    pub slow_ops: Vec<SlowOp>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct OpSamples {
+    count: u64,
+    recent: VecDeque<f64>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct PerfBuffers {
+    samples: HashMap<OpKind, OpSamples>,
+    slow: VecDeque<SlowOp>,
+}
+
+/// Runtime-only timing buffers (never persisted). Interior-mutable so
+/// read-only queries (`&self`) can still be timed.
+#[derive(Debug, Default)]
+pub struct PerfMonitor(Mutex<PerfBuffers>);
+
+impl Clone for PerfMonitor {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.buffers().clone()))
+    }
+}
+
+impl PerfMonitor {
+    fn buffers(&self) -> std::sync::MutexGuard<'_, PerfBuffers> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
+impl LoomGraph {
+    pub(crate) fn record_timing(&self, op: OpKind, started: DateTime<Utc>) {
+        let cfg = &self.config.perf;
+        if !cfg.enabled {
+            return;
+        }
+        let now = Utc::now();
+        let duration_ms = (now - started).num_microseconds().unwrap_or(i64::MAX) as f64 / 1000.0;
+
+        let mut buffers = self.perf.buffers();
+        let samples = buffers.samples.entry(op).or_default();
+        samples.count += 1;
+        samples.recent.push_back(duration_ms);
+        while samples.recent.len() > cfg.window.max(1) {
+            samples.recent.pop_front();
+        }
+
+        if cfg.slow_op_threshold_ms.is_some_and(|limit| duration_ms >= limit) {
+            buffers.slow.push_back(SlowOp { op, duration_ms, tick: self.current_tick, at: now });
+            while buffers.slow.len() > cfg.slow_log_capacity {
+                buffers.slow.pop_front();
+            }
+        }
+    }
+
+    pub fn perf_stats(&self) -> PerfStats {
+        let buffers = self.perf.buffers();
+        let ops = buffers.samples.iter()
+            .map(|(op, samples)| {
+                let mut sorted: Vec<f64> = samples.recent.iter().copied().collect();
+                sorted.sort_by(f64::total_cmp);
+                (*op, OpStats {
+                    count: samples.count,
+                    p50_ms: percentile(&sorted, 50.0),
+                    p95_ms: percentile(&sorted, 95.0),
+                    p99_ms: percentile(&sorted, 99.0),
+                    max_ms: sorted.last().copied().unwrap_or(0.0),
+                })
+            })
+            .collect();
+        PerfStats { ops, slow_ops: buffers.slow.iter().cloned().collect() }
+    }
+
+    pub fn clear_perf_stats(&mut self) {
+        self.perf = PerfMonitor::default();
+    }
+}
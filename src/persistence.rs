@@ -0,0 +1,197 @@
+//! # Binary Persistence
+//! Compact alternative to the JSON backups for large brains.
+//!
+//! Layout: `b"LOOM"` magic, one format-version byte, then the graph encoded
+//! as MessagePack (self-describing, so `#[serde(default)]` fields keep working
+//! across crate versions).
+//!
+//! Compressed backups are plain gzip'd JSON. With the `encryption` feature,
+//! `save_encrypted` wraps that gzip stream in AES-256-GCM:
+//! `b"LOOE"`, version byte, 12-byte nonce, ciphertext (tag appended).
+//!
+//! Every format serializes the graph's maps in canonical order (sorted keys,
+//! sorted edge and index lists), so identical graphs produce byte-identical
+//! backups and content hashes / diffs stay stable.
+
+use crate::{Connection, LoomGraph, MigrationRegistry, NodeId, OpKind};
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+pub const BINARY_MAGIC: &[u8; 4] = b"LOOM";
+pub const BINARY_VERSION: u8 = 1;
+#[cfg(feature = "encryption")]
+pub const ENCRYPTED_MAGIC: &[u8; 4] = b"LOOE";
+#[cfg(feature = "encryption")]
+const ENCRYPTED_VERSION: u8 = 1;
+#[cfg(feature = "encryption")]
+const NONCE_LEN: usize = 12;
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+// --- Ordem canônica (usada pelos `serialize_with` de LoomGraph) ---
+
+pub(crate) fn sorted_map<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+pub(crate) fn canonical_edge_order(a: &Connection, b: &Connection) -> Ordering {
+    a.target.cmp(&b.target)
+        .then_with(|| a.edge_type.cmp(&b.edge_type))
+        .then_with(|| a.weight.total_cmp(&b.weight))
+}
+
+pub(crate) fn sorted_adjacency<S: Serializer>(
+    adjacency: &HashMap<NodeId, Vec<Connection>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let sorted: BTreeMap<_, _> = adjacency.iter().collect();
+    let mut map = serializer.serialize_map(Some(sorted.len()))?;
+    for (source, edges) in sorted {
+        let mut edges: Vec<&Connection> = edges.iter().collect();
+        edges.sort_by(|a, b| canonical_edge_order(a, b));
+        map.serialize_entry(source, &edges)?;
+    }
+    map.end()
+}
+
+pub(crate) fn sorted_index<S: Serializer>(
+    index: &HashMap<String, Vec<NodeId>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let sorted: BTreeMap<_, _> = index.iter().collect();
+    let mut map = serializer.serialize_map(Some(sorted.len()))?;
+    for (token, ids) in sorted {
+        let mut ids = ids.clone();
+        ids.sort();
+        map.serialize_entry(token, &ids)?;
+    }
+    map.end()
+}
+
+impl LoomGraph {
+    pub fn to_binary(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(self.nodes.len() * 128);
+        bytes.extend_from_slice(BINARY_MAGIC);
+        bytes.push(BINARY_VERSION);
+        rmp_serde::encode::write_named(&mut bytes, self).map_err(io::Error::other)?;
+        Ok(bytes)
+    }
+
+    pub fn from_binary(bytes: &[u8]) -> io::Result<Self> {
+        if bytes.len() < 5 || &bytes[..4] != BINARY_MAGIC {
+            return Err(invalid("not a LoomDB binary file"));
+        }
+        if bytes[4] != BINARY_VERSION {
+            return Err(invalid(format!("unsupported binary format version {}", bytes[4])));
+        }
+        let payload = &bytes[5..];
+        MigrationRegistry::default()
+            .load(
+                rmp_serde::from_slice(payload).ok(),
+                || rmp_serde::from_slice(payload).map_err(|e| e.to_string()),
+            )
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save_binary(&mut self, filepath: &str) -> io::Result<()> {
+        let started = chrono::Utc::now();
+        self.last_saved = Some(self.now());
+        let mut writer = BufWriter::new(File::create(filepath)?);
+        writer.write_all(&self.to_binary()?)?;
+        writer.flush()?;
+        self.mark_clean();
+        self.record_timing(OpKind::Save, started);
+        Ok(())
+    }
+
+    pub fn load_binary(filepath: &str) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        BufReader::new(File::open(filepath)?).read_to_end(&mut bytes)?;
+        Self::from_binary(&bytes)
+    }
+
+    pub fn export_backup_compressed(&self) -> io::Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        serde_json::to_writer(&mut encoder, self)?;
+        encoder.finish()
+    }
+
+    pub fn import_backup_compressed(bytes: &[u8]) -> io::Result<Self> {
+        let mut json = String::new();
+        GzDecoder::new(bytes).read_to_string(&mut json)?;
+        Self::from_backup_json(&json).map_err(|e| invalid(e.to_string()))
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn to_encrypted(&self, key: &[u8; 32]) -> io::Result<Vec<u8>> {
+        use aes_gcm::aead::{Aead, KeyInit};
+        use aes_gcm::{Aes256Gcm, Nonce};
+
+        let mut nonce = [0u8; NONCE_LEN];
+        getrandom::getrandom(&mut nonce).map_err(|e| io::Error::other(e.to_string()))?;
+
+        let cipher = Aes256Gcm::new(key.into());
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), self.export_backup_compressed()?.as_slice())
+            .map_err(|_| io::Error::other("encryption failed"))?;
+
+        let mut bytes = Vec::with_capacity(5 + NONCE_LEN + ciphertext.len());
+        bytes.extend_from_slice(ENCRYPTED_MAGIC);
+        bytes.push(ENCRYPTED_VERSION);
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&ciphertext);
+        Ok(bytes)
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn from_encrypted(bytes: &[u8], key: &[u8; 32]) -> io::Result<Self> {
+        use aes_gcm::aead::{Aead, KeyInit};
+        use aes_gcm::{Aes256Gcm, Nonce};
+
+        if bytes.len() < 5 + NONCE_LEN || &bytes[..4] != ENCRYPTED_MAGIC {
+            return Err(invalid("not an encrypted LoomDB backup"));
+        }
+        if bytes[4] != ENCRYPTED_VERSION {
+            return Err(invalid(format!("unsupported encrypted format version {}", bytes[4])));
+        }
+        let (nonce, ciphertext) = bytes[5..].split_at(NONCE_LEN);
+        let cipher = Aes256Gcm::new(key.into());
+        let compressed = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| invalid("wrong key or corrupted backup"))?;
+        Self::import_backup_compressed(&compressed)
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn save_encrypted(&mut self, filepath: &str, key: &[u8; 32]) -> io::Result<()> {
+        let started = chrono::Utc::now();
+        self.last_saved = Some(self.now());
+        let mut writer = BufWriter::new(File::create(filepath)?);
+        writer.write_all(&self.to_encrypted(key)?)?;
+        writer.flush()?;
+        self.mark_clean();
+        self.record_timing(OpKind::Save, started);
+        Ok(())
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn load_encrypted(filepath: &str, key: &[u8; 32]) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        BufReader::new(File::open(filepath)?).read_to_end(&mut bytes)?;
+        Self::from_encrypted(&bytes, key)
+    }
+}
@@ -0,0 +1,66 @@
+//! # Pinned Memories
+//! Core memories (agent identity, user preferences) can be pinned: their
+//! activation never decays below a floor (`config.pin_floor` at pin time) and
+//! pruning, eviction and importance trimming always skip them. Unlike
+//! `always_in_context`, pinning doesn't force a node into every context; it
+//! only keeps it alive and active enough to surface.
+
+use crate::{GraphEvent, LoomGraph, NodeId};
+
+impl LoomGraph {
+    /// Pins a node at `config.pin_floor`, raising its activation to the floor
+    /// if it already faded below it. Returns false if the node doesn't exist.
+    pub fn pin(&mut self, id: NodeId) -> bool {
+        self.pin_at(id, self.config.pin_floor)
+    }
+
+    pub(crate) fn pin_at(&mut self, id: NodeId, floor: f32) -> bool {
+        let tick = self.current_tick;
+        let settled = match self.nodes.get(&id) {
+            Some(node) => self.projected_activation(node.meta(), tick),
+            None => return false,
+        };
+        if let Some(node) = self.nodes.get_mut(&id) {
+            let meta = node.meta_mut();
+            meta.activation = settled.max(floor);
+            meta.last_tick = tick;
+            meta.pin_floor = Some(floor);
+        }
+        self.sample_activation([id]);
+        self.record(GraphEvent::Pinned { id, floor: Some(floor) });
+        true
+    }
+
+    /// Lifts the pin; the node decays and can be pruned normally again.
+    /// Returns false if the node doesn't exist.
+    pub fn unpin(&mut self, id: NodeId) -> bool {
+        let tick = self.current_tick;
+        let settled = match self.nodes.get(&id) {
+            Some(node) => self.projected_activation(node.meta(), tick),
+            None => return false,
+        };
+        if let Some(node) = self.nodes.get_mut(&id) {
+            let meta = node.meta_mut();
+            // Fecha o decaimento com o piso antes de retirá-lo
+            meta.activation = settled;
+            meta.last_tick = tick;
+            meta.pin_floor = None;
+        }
+        self.record(GraphEvent::Pinned { id, floor: None });
+        true
+    }
+
+    pub fn is_pinned(&self, id: NodeId) -> bool {
+        self.nodes.get(&id).is_some_and(|node| node.meta().is_pinned())
+    }
+
+    /// Every pinned node, in id order.
+    pub fn pinned(&self) -> Vec<NodeId> {
+        let mut ids: Vec<NodeId> = self.nodes.values()
+            .filter(|node| node.meta().is_pinned())
+            .map(|node| node.meta().id)
+            .collect();
+        ids.sort();
+        ids
+    }
+}
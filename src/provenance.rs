@@ -0,0 +1,79 @@
+//! # Provenance
+//! Where a memory came from: the user, a tool, the model's own inference or
+//! an import. The `Source` lives in node metadata, so it shows up in node
+//! JSON, and `config.cite_sources` adds it to rendered contexts so agents
+//! can cite it.
+
+use crate::{LoomGraph, NodeId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SourceOrigin {
+    UserMessage,
+    ToolOutput,
+    LlmInference,
+    Import,
+}
+
+impl fmt::Display for SourceOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SourceOrigin::UserMessage => "user_message",
+            SourceOrigin::ToolOutput => "tool_output",
+            SourceOrigin::LlmInference => "llm_inference",
+            SourceOrigin::Import => "import",
+        };
+        f.write_str(label)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Source {
+    pub origin: SourceOrigin,
+    /// Message id, tool name, file path, URL...
+    pub reference: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl LoomGraph {
+    /// Attributes `id` to `origin`/`reference`, stamped with the graph clock.
+    /// Returns false if the node doesn't exist.
+    pub fn set_source(&mut self, id: NodeId, origin: SourceOrigin, reference: String) -> bool {
+        let created_at = self.now();
+        match self.nodes.get_mut(&id) {
+            Some(node) => {
+                node.meta_mut().source = Some(Source { origin, reference, created_at });
+                self.record_rewrite(id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn source(&self, id: NodeId) -> Option<&Source> {
+        self.nodes.get(&id)?.meta().source.as_ref()
+    }
+
+    pub fn clear_source(&mut self, id: NodeId) -> Option<Source> {
+        let removed = self.nodes.get_mut(&id)?.meta_mut().source.take();
+        if removed.is_some() {
+            self.record_rewrite(id);
+        }
+        removed
+    }
+
+    /// Nodes attributed to `origin`, most active first.
+    pub fn nodes_from(&self, origin: SourceOrigin) -> Vec<NodeId> {
+        let mut found: Vec<(NodeId, f32)> = self.nodes.iter()
+            .filter(|(id, node)| !self.is_forgotten(**id) && node.meta().source.as_ref().is_some_and(|s| s.origin == origin))
+            .map(|(id, node)| (*id, node.meta().activation))
+            .collect();
+        found.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        found.into_iter().map(|(id, _)| id).collect()
+    }
+}
@@ -0,0 +1,67 @@
+//! # Rehearsal Scheduling
+//! Spaced repetition for important memories (stability >=
+//! `config.rehearsal.min_stability`, or pinned in context). Solving the decay
+//! model for the moment activation hits `config.rehearsal.retention` gives
+//! each memory its next due tick:
+//!
+//! `due = last_tick + stability * ln(retention / activation) / ln(rate)`
+//!
+//! `rehearse` boosts the memory and multiplies its stability by `ease`
+//! (SM-2 style), so every successful rehearsal pushes the next one further out.
+
+use crate::{DecayOverride, LoomGraph, NodeId, NodeMetadata};
+
+impl LoomGraph {
+    fn due_tick(&self, meta: &NodeMetadata) -> Option<u64> {
+        let rate = match meta.decay_override {
+            Some(DecayOverride::Never) => return None,
+            Some(DecayOverride::Rate(rate)) => rate,
+            None => self.nodes.get(&meta.id).map_or(self.decay_rate, |n| self.kind_decay_rate(n.kind())),
+        };
+        if !(rate > 0.0 && rate < 1.0) {
+            return None;
+        }
+        let retention = self.config.rehearsal.retention;
+        if meta.activation <= retention {
+            return Some(meta.last_tick);
+        }
+        let ticks = meta.stability * (retention / meta.activation).ln() / rate.ln();
+        Some(meta.last_tick.saturating_add(ticks.floor().max(0.0) as u64))
+    }
+
+    fn worth_rehearsing(&self, meta: &NodeMetadata) -> bool {
+        meta.always_in_context || meta.stability >= self.config.rehearsal.min_stability
+    }
+
+    /// Tick at which `id` drops to the retention threshold (None if it never decays).
+    pub fn next_rehearsal(&self, id: NodeId) -> Option<u64> {
+        self.due_tick(self.nodes.get(&id)?.meta())
+    }
+
+    /// Important memories due at or before tick `now`, as `(id, due tick)`, most overdue first.
+    pub fn due_for_rehearsal(&self, now: u64) -> Vec<(NodeId, u64)> {
+        let mut due: Vec<(NodeId, u64)> = self.nodes.values()
+            .map(|node| node.meta())
+            .filter(|meta| self.worth_rehearsing(meta))
+            .filter_map(|meta| self.due_tick(meta).filter(|tick| *tick <= now).map(|tick| (meta.id, tick)))
+            .collect();
+        due.sort_by_key(|(id, tick)| (*tick, *id));
+        due
+    }
+
+    /// Boosts `id` (no ripple), grows its stability by `ease` and returns the
+    /// next due tick. None if the node doesn't exist or never decays.
+    pub fn rehearse(&mut self, id: NodeId) -> Option<u64> {
+        if !self.nodes.contains_key(&id) {
+            return None;
+        }
+        let cfg = self.config.rehearsal.clone();
+        self.boost_node(id, cfg.boost, 1);
+        if let Some(node) = self.nodes.get_mut(&id) {
+            let meta = node.meta_mut();
+            meta.stability = (meta.stability * cfg.ease).min(cfg.max_stability).max(meta.stability);
+        }
+        self.record_rewrite(id);
+        self.next_rehearsal(id)
+    }
+}
@@ -0,0 +1,102 @@
+//! # Retrieval
+//! Search variants that return more than a flat ranked list.
+
+use crate::{LoomGraph, Node, NodeId, NodeKind, PRECEDED_EDGE};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+const MAX_RELATIONS_PER_HIT: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HitRelation {
+    pub target: NodeId,
+    pub weight: f32,
+    pub edge_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedHit {
+    pub id: NodeId,
+    pub activation: f32,
+    /// Strongest outgoing edges from this hit to other hits of the same query.
+    pub relations: Vec<HitRelation>,
+}
+
+/// A search hit with the context needed to stitch it into a passage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkHit {
+    pub id: NodeId,
+    pub activation: f32,
+    /// Scene episode this chunk is `PartOf`, if any.
+    pub parent: Option<NodeId>,
+    /// Neighbours along the `Preceded` chain.
+    pub prev: Option<NodeId>,
+    pub next: Option<NodeId>,
+}
+
+impl LoomGraph {
+    /// Top `k` search hits, each annotated with its strongest links to the
+    /// other hits, so callers can present connected clusters of memory.
+    pub fn search_with_relations(&self, query: &str, k: usize) -> Vec<RelatedHit> {
+        let hits: Vec<(NodeId, f32)> = self.search_kinds(query, &[]).into_iter().take(k).collect();
+        let hit_ids: HashSet<NodeId> = hits.iter().map(|(id, _)| *id).collect();
+
+        hits.into_iter()
+            .map(|(id, activation)| {
+                let mut relations: Vec<HitRelation> = self.adjacency.get(&id)
+                    .into_iter()
+                    .flatten()
+                    .filter(|conn| conn.target != id && hit_ids.contains(&conn.target))
+                    .map(|conn| HitRelation {
+                        target: conn.target,
+                        weight: conn.weight,
+                        edge_type: conn.edge_type.clone(),
+                    })
+                    .collect();
+                relations.sort_by(|a, b| b.weight.abs().partial_cmp(&a.weight.abs()).unwrap_or(std::cmp::Ordering::Equal));
+                relations.truncate(MAX_RELATIONS_PER_HIT);
+                RelatedHit { id, activation, relations }
+            })
+            .collect()
+    }
+
+    /// Top `k` episode hits with their parent scene and `Preceded` neighbours.
+    pub fn search_chunks(&self, query: &str, k: usize) -> Vec<ChunkHit> {
+        let hits: Vec<(NodeId, f32)> = self.search_kinds(query, &[NodeKind::Episode]).into_iter().take(k).collect();
+        if hits.is_empty() {
+            return Vec::new();
+        }
+        let mut prev_of = HashMap::new();
+        for (source, edges) in &self.adjacency {
+            for edge in edges.iter().filter(|e| e.edge_type == PRECEDED_EDGE) {
+                prev_of.entry(edge.target).or_insert(*source);
+            }
+        }
+        let next_of = |id: NodeId| self.adjacency.get(&id)?.iter()
+            .find(|e| e.edge_type == PRECEDED_EDGE && self.nodes.contains_key(&e.target))
+            .map(|e| e.target);
+
+        hits.into_iter()
+            .map(|(id, activation)| ChunkHit {
+                id,
+                activation,
+                parent: self.chunk_parent(id),
+                prev: prev_of.get(&id).copied().filter(|p| self.nodes.contains_key(p)),
+                next: next_of(id),
+            })
+            .collect()
+    }
+
+    /// Text of `prev`, the hit and `next`, joined in order: a passage
+    /// instead of an orphan fragment.
+    pub fn stitch_passage(&self, hit: &ChunkHit) -> String {
+        [hit.prev, Some(hit.id), hit.next].into_iter()
+            .flatten()
+            .filter_map(|id| match self.nodes.get(&id) {
+                Some(Node::Episode(_, d)) => Some(d.summary.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
@@ -0,0 +1,115 @@
+//! # Scheduled Events
+//! Actions queued for a future tick (delayed stimuli, maintenance passes).
+//! `tick()` runs whatever falls due; `fast_forward(ticks)` jumps straight to
+//! each due event in order and then to the target tick, so simulations can
+//! skip millions of idle ticks in O(events) instead of looping `tick()`.
+//! Decay is lazy, so skipping ticks needs no per-tick work.
+
+use crate::{GraphEvent, LoomGraph, NodeId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScheduledAction {
+    /// `stimulate(id, force)`; skipped if the node is gone by then.
+    Stimulate { id: NodeId, force: f32 },
+    Dream,
+    Prune { threshold: f32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledEvent {
+    pub at_tick: u64,
+    pub action: ScheduledAction,
+}
+
+impl LoomGraph {
+    /// Queues `action` for `at_tick` (runs on the next tick if already past).
+    /// Events on the same tick run in the order they were scheduled.
+    pub fn schedule_at(&mut self, at_tick: u64, action: ScheduledAction) {
+        let event = ScheduledEvent { at_tick, action };
+        self.insert_scheduled(event.clone());
+        self.record(GraphEvent::Scheduled { event });
+    }
+
+    pub(crate) fn insert_scheduled(&mut self, event: ScheduledEvent) {
+        let pos = self.scheduled.partition_point(|e| e.at_tick <= event.at_tick);
+        self.scheduled.insert(pos, event);
+    }
+
+    /// Queues `action` `delay` ticks from now.
+    pub fn schedule_in(&mut self, delay: u64, action: ScheduledAction) {
+        self.schedule_at(self.current_tick.saturating_add(delay), action);
+    }
+
+    /// Pending events, soonest first.
+    pub fn scheduled_events(&self) -> &[ScheduledEvent] {
+        &self.scheduled
+    }
+
+    pub fn clear_schedule(&mut self) {
+        if !self.scheduled.is_empty() {
+            self.scheduled.clear();
+            self.record(GraphEvent::ScheduleCleared);
+        }
+    }
+
+    /// Same end state as calling `tick()` `ticks` times, without the loop.
+    /// Scheduled dreams still advance the clock by their 8h on top of `ticks`.
+    /// Returns how many scheduled events ran.
+    pub fn fast_forward(&mut self, ticks: u64) -> usize {
+        if ticks == 0 {
+            return 0;
+        }
+        let mut remaining = ticks;
+        let mut ran = 0;
+        // Evento já vencido roda no próximo tick, como faria o tick()
+        while let Some(at) = self.scheduled.first().map(|e| e.at_tick.max(self.current_tick + 1)) {
+            let step = at - self.current_tick;
+            if step > remaining {
+                break;
+            }
+            remaining -= step;
+            let from = self.current_tick;
+            // Tombstones vencidos antes de `at` somem antes dos eventos
+            self.current_tick = at - 1;
+            self.expire_tombstones();
+            self.current_tick = at;
+            self.notify_decay(from, at);
+            ran += self.run_due_events();
+        }
+        // Sonhos agendados já adiantaram o relógio: conta só o que falta
+        let from = self.current_tick;
+        self.current_tick = from.saturating_add(remaining);
+        self.notify_decay(from, self.current_tick);
+        self.expire_tombstones();
+        self.refresh_goals();
+        self.append_wal(&GraphEvent::Ticked);
+        self.maybe_compact_wal();
+        self.maybe_autosave();
+        ran
+    }
+
+    /// Runs every event due at or before the current tick.
+    pub(crate) fn run_due_events(&mut self) -> usize {
+        let due = self.scheduled.partition_point(|e| e.at_tick <= self.current_tick);
+        if due == 0 {
+            return 0;
+        }
+        let events: Vec<ScheduledEvent> = self.scheduled.drain(..due).collect();
+        self.record(GraphEvent::ScheduledRan { count: due });
+        for event in &events {
+            match event.action {
+                ScheduledAction::Stimulate { id, force } => {
+                    self.stimulate(id, force);
+                }
+                ScheduledAction::Dream => {
+                    self.dream_pass();
+                }
+                ScheduledAction::Prune { threshold } => {
+                    self.prune_low_stability(threshold);
+                }
+            }
+        }
+        events.len()
+    }
+}
@@ -0,0 +1,61 @@
+//! # Background Ticking
+//! `TickScheduler` (feature `scheduler`) advances a `SharedLoom` from a
+//! background thread on a wall-clock interval, so long-running daemons get
+//! realistic time passage without a manual tick loop. Every wake-up calls
+//! `tick_until(now)`, so late or missed wake-ups (a suspended machine, a busy
+//! lock) still land on the right tick count instead of drifting.
+//!
+//! Stopping (or dropping) the scheduler ends the thread promptly.
+
+use crate::SharedLoom;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+pub struct TickScheduler {
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl TickScheduler {
+    /// Starts ticking `brain` every `interval` (pick it near
+    /// `config.dynamics.seconds_per_tick`; shorter just syncs more often).
+    pub fn start(brain: SharedLoom, interval: Duration) -> Self {
+        let (stop, stopped) = mpsc::channel();
+        let handle = thread::Builder::new()
+            .name("loom-ticker".to_string())
+            .spawn(move || {
+                // Qualquer outra coisa (parada ou handle descartado) encerra
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    brain.with_write(|graph| {
+                        let now = graph.now();
+                        graph.tick_until(now);
+                    });
+                }
+            })
+            .expect("failed to spawn the tick scheduler thread");
+        Self { stop: Some(stop), handle: Some(handle) }
+    }
+
+    /// Stops the thread and waits for an in-flight tick to finish.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|h| !h.is_finished())
+    }
+
+    fn shutdown(&mut self) {
+        drop(self.stop.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for TickScheduler {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -0,0 +1,280 @@
+//! # HTTP Server
+//! REST API around a `SharedLoom` (feature `server`), so agents written in
+//! any language can use a brain over the network:
+//!
+//! - `POST /episodes` `{"summary", "timestamp"?}` → `201 {"id"}`
+//! - `GET /search?q=&limit=` → `[{"id", "kind", "label", "activation"}]`
+//! - `GET /context?min_activation=&format=` → the prompt context
+//!   (`xml`, `markdown`, `json` or `openai`; JSON formats as `application/json`)
+//! - `POST /tick` `{"ticks"?}` → `{"tick"}` (at most `MAX_TICKS_PER_REQUEST`)
+//! - `GET /nodes/{id}` → `{"node", "edges"}`
+//! - `GET /events` (WebSocket) → one `ServerEvent` JSON text frame per
+//!   node added, boost, fade below `config.decay_alert_threshold` or prune
+//!
+//! Errors are `{"error": message}` with 400 (bad input), 404 (unknown node),
+//! 415 (missing JSON content type) or 422 (rejected timestamp or body).
+//!
+//! Events are fanned out through a bounded broadcast channel: a client that
+//! falls more than `EVENT_BUFFER` events behind skips the oldest ones.
+
+use crate::{AsyncLoom, Connection, ContextFormat, Node, NodeId, NodeKind, Observer, SharedLoom};
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{FromRef, Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::io;
+use std::sync::Arc;
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tokio::sync::broadcast;
+
+/// Events a `/events` subscriber may lag behind before missing some.
+pub const EVENT_BUFFER: usize = 1024;
+
+/// Largest `ticks` a single `POST /tick` may ask for.
+pub const MAX_TICKS_PER_REQUEST: u64 = 100_000;
+
+/// Pushed to `/events` subscribers, tagged by `"event"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ServerEvent {
+    NodeAdded { id: NodeId, kind: NodeKind, label: String },
+    Boosted { id: NodeId, amount: f32, activation: f32 },
+    DecayedBelowThreshold { id: NodeId, activation: f32 },
+    Pruned { ids: Vec<NodeId> },
+}
+
+/// Forwards graph callbacks into the broadcast channel.
+struct EventBroadcaster(broadcast::Sender<ServerEvent>);
+
+impl EventBroadcaster {
+    fn send(&self, event: ServerEvent) {
+        // Sem assinantes o envio falha, e tudo bem
+        let _ = self.0.send(event);
+    }
+}
+
+impl Observer for EventBroadcaster {
+    fn on_node_added(&self, node: &Node) {
+        self.send(ServerEvent::NodeAdded { id: node.meta().id, kind: node.kind(), label: node.label() });
+    }
+
+    fn on_boost(&self, id: NodeId, amount: f32, activation: f32) {
+        self.send(ServerEvent::Boosted { id, amount, activation });
+    }
+
+    fn on_decay_below(&self, id: NodeId, activation: f32) {
+        self.send(ServerEvent::DecayedBelowThreshold { id, activation });
+    }
+
+    fn on_pruned(&self, ids: &[NodeId]) {
+        self.send(ServerEvent::Pruned { ids: ids.to_vec() });
+    }
+}
+
+#[derive(Clone)]
+struct AppState {
+    brain: SharedLoom,
+    events: broadcast::Sender<ServerEvent>,
+}
+
+impl FromRef<AppState> for SharedLoom {
+    fn from_ref(state: &AppState) -> Self {
+        state.brain.clone()
+    }
+}
+
+/// Error body plus status code.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+// Rejeições do axum viram o mesmo corpo `{"error"}` das outras falhas
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::new(rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::new(rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        Self::new(rejection.status(), rejection.body_text())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NewEpisode {
+    summary: String,
+    /// RFC 3339; defaults to now.
+    timestamp: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchParams {
+    q: String,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContextParams {
+    min_activation: Option<f32>,
+    format: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TickRequest {
+    ticks: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct SearchHit {
+    id: NodeId,
+    kind: crate::NodeKind,
+    label: String,
+    activation: f32,
+}
+
+#[derive(Debug, Serialize)]
+struct NodeView {
+    node: Node,
+    edges: Vec<Connection>,
+}
+
+/// The REST routes over `brain`; merge or nest it into a larger app as needed.
+/// Registers an observer on `brain` to feed `/events`.
+pub fn http_router(brain: SharedLoom) -> Router {
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    brain.write().add_observer(Arc::new(EventBroadcaster(events.clone())));
+    Router::new()
+        .route("/episodes", post(add_episode))
+        .route("/search", get(search))
+        .route("/context", get(context))
+        .route("/tick", post(tick))
+        .route("/nodes/{id}", get(get_node))
+        .route("/events", get(subscribe_events))
+        .with_state(AppState { brain, events })
+}
+
+/// Binds `addr` and serves `http_router(brain)` until the task is dropped.
+pub async fn serve_http(brain: SharedLoom, addr: impl ToSocketAddrs) -> io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    axum::serve(listener, http_router(brain)).await
+}
+
+async fn add_episode(State(brain): State<SharedLoom>, body: Result<Json<NewEpisode>, JsonRejection>) -> Result<Response, ApiError> {
+    let Json(body) = body?;
+    if body.summary.trim().is_empty() {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "summary must not be empty"));
+    }
+    let id = match body.timestamp {
+        Some(time) => brain.write().add_episode_at(body.summary, &time)
+            .map_err(|e| ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))?,
+        None => brain.add_episode(body.summary),
+    };
+    Ok((StatusCode::CREATED, Json(json!({ "id": id }))).into_response())
+}
+
+async fn search(State(brain): State<SharedLoom>, params: Result<Query<SearchParams>, QueryRejection>) -> Result<Json<Vec<SearchHit>>, ApiError> {
+    let Query(params) = params?;
+    let graph = brain.read();
+    let hits = graph.search_native(&params.q).into_iter()
+        .take(params.limit.unwrap_or(10))
+        .filter_map(|(id, activation)| {
+            let node = graph.get_node(id)?;
+            Some(SearchHit { id, kind: node.kind(), label: node.label(), activation })
+        })
+        .collect();
+    Ok(Json(hits))
+}
+
+async fn context(State(brain): State<SharedLoom>, params: Result<Query<ContextParams>, QueryRejection>) -> Result<Response, ApiError> {
+    let Query(params) = params?;
+    let format = match params.format.as_deref() {
+        None | Some("xml") => ContextFormat::Xml,
+        Some("markdown") | Some("md") => ContextFormat::Markdown,
+        Some("json") => ContextFormat::Json,
+        Some("openai") => ContextFormat::OpenAiMessages,
+        Some(other) => return Err(ApiError::new(StatusCode::BAD_REQUEST, format!("unknown context format '{}'", other))),
+    };
+    let body = brain.get_context_as(format, params.min_activation.unwrap_or(0.3));
+    let content_type = match format {
+        ContextFormat::Json | ContextFormat::OpenAiMessages => "application/json",
+        ContextFormat::Markdown => "text/markdown; charset=utf-8",
+        ContextFormat::Xml => "application/xml; charset=utf-8",
+    };
+    Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+}
+
+async fn tick(State(brain): State<SharedLoom>, body: Result<Option<Json<TickRequest>>, JsonRejection>) -> Result<Json<serde_json::Value>, ApiError> {
+    let ticks = body?.map(|Json(b)| b).unwrap_or_default().ticks.unwrap_or(1);
+    if ticks > MAX_TICKS_PER_REQUEST {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, format!("ticks must be at most {}", MAX_TICKS_PER_REQUEST)));
+    }
+    // Fora das threads do runtime: um salto longo ainda custa O(eventos agendados)
+    let current = AsyncLoom::from(brain).write(move |graph| {
+        graph.fast_forward(ticks);
+        graph.current_tick
+    }).await;
+    Ok(Json(json!({ "tick": current })))
+}
+
+async fn get_node(State(brain): State<SharedLoom>, raw: Result<Path<String>, PathRejection>) -> Result<Json<NodeView>, ApiError> {
+    let Path(raw) = raw?;
+    let id: NodeId = raw.parse().map_err(|_| ApiError::new(StatusCode::BAD_REQUEST, format!("invalid id '{}'", raw)))?;
+    let graph = brain.read();
+    let node = graph.get_node(id)
+        .filter(|_| !graph.is_forgotten(id))
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, format!("no memory with id {}", id)))?;
+    Ok(Json(NodeView { node: node.clone(), edges: graph.adjacency.get(&id).cloned().unwrap_or_default() }))
+}
+
+async fn subscribe_events(State(state): State<AppState>, upgrade: WebSocketUpgrade) -> Response {
+    let receiver = state.events.subscribe();
+    upgrade.on_upgrade(move |socket| stream_events(socket, receiver))
+}
+
+async fn stream_events(mut socket: WebSocket, mut receiver: broadcast::Receiver<ServerEvent>) {
+    loop {
+        tokio::select! {
+            event = receiver.recv() => match event {
+                Ok(event) => {
+                    let text = serde_json::to_string(&event).unwrap_or_default();
+                    if socket.send(Message::Text(text.into())).await.is_err() {
+                        break;
+                    }
+                }
+                // Cliente lento: pula o que perdeu e continua
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! # Sessions
+//! `begin_session` / `end_session` bracket a stretch of interaction (a chat,
+//! a task run). Episodes added in between are tagged with the session id and
+//! chained with `Preceded` edges in arrival order; `get_session` returns that
+//! timeline. The open session is persisted, so a brain saved mid-session
+//! resumes it after loading.
+
+use crate::{Connection, LoomGraph, Node, NodeId, NodeKind, NodeMetadata, PRECEDED_EDGE};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SessionId(Uuid);
+
+impl SessionId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Default for SessionId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SessionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for SessionId {
+    type Err = uuid::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s).map(Self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveSession {
+    pub id: SessionId,
+    /// Last episode of the session, where the next one gets chained.
+    pub last_episode: Option<NodeId>,
+}
+
+impl LoomGraph {
+    /// Opens a new session (ending the current one, if any).
+    pub fn begin_session(&mut self) -> SessionId {
+        self.end_session();
+        let id = SessionId(self.new_uuid());
+        self.active_session = Some(ActiveSession { id, last_episode: None });
+        self.dirty += 1;
+        id
+    }
+
+    /// Closes the open session and returns its id.
+    pub fn end_session(&mut self) -> Option<SessionId> {
+        let ended = self.active_session.take()?;
+        self.dirty += 1;
+        Some(ended.id)
+    }
+
+    pub fn current_session(&self) -> Option<SessionId> {
+        self.active_session.as_ref().map(|s| s.id)
+    }
+
+    /// Episodes of a session, oldest first (ties keep insertion order).
+    pub fn get_session(&self, id: SessionId) -> Vec<NodeId> {
+        let mut episodes: Vec<_> = self.nodes.values()
+            .filter_map(|node| match node {
+                Node::Episode(meta, data) if meta.session == Some(id) => Some((data.timestamp, meta.seq, meta.id)),
+                _ => None,
+            })
+            .collect();
+        episodes.sort();
+        episodes.into_iter().map(|(_, _, id)| id).collect()
+    }
+
+    /// Metadata for a new episode, tagged with the open session.
+    pub(crate) fn episode_meta(&self) -> NodeMetadata {
+        let mut meta = self.new_meta(NodeKind::Episode);
+        meta.session = self.current_session();
+        meta
+    }
+
+    /// Chains freshly stored session episodes after the session's last one.
+    /// Links that already exist (e.g. conversation turns) are not duplicated.
+    pub(crate) fn chain_session(&mut self, ids: &[NodeId]) {
+        let Some(mut last) = self.active_session.as_ref().map(|s| s.last_episode) else { return };
+        for id in ids {
+            if let Some(prev) = last.filter(|prev| self.nodes.contains_key(prev)) {
+                let linked = self.adjacency.get(&prev)
+                    .is_some_and(|edges| edges.iter().any(|e| e.target == *id && e.edge_type == PRECEDED_EDGE));
+                if !linked {
+                    self.store_edge(prev, Connection {
+                        target: *id,
+                        weight: self.config.conversation.chain_weight,
+                        edge_type: PRECEDED_EDGE.to_string(),
+                        inferred: false,
+                    });
+                }
+            }
+            last = Some(*id);
+        }
+        if let Some(session) = self.active_session.as_mut() {
+            session.last_episode = last;
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! # Shared Graph
+//! `SharedLoom` lets many threads (e.g. request handlers of an agent server)
+//! use one brain. Queries take a read lock and run concurrently; mutations
+//! take the write lock. Cloning the handle is cheap and shares the graph.
+//!
+//! A poisoned lock (a panic while writing) is recovered rather than
+//! propagated: the graph is always structurally valid between calls.
+
+use crate::{ContextFormat, LoomGraph, Node, NodeId};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[derive(Clone)]
+pub struct SharedLoom {
+    inner: Arc<RwLock<LoomGraph>>,
+}
+
+impl From<LoomGraph> for SharedLoom {
+    fn from(graph: LoomGraph) -> Self {
+        Self::new(graph)
+    }
+}
+
+impl SharedLoom {
+    pub fn new(graph: LoomGraph) -> Self {
+        Self { inner: Arc::new(RwLock::new(graph)) }
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, LoomGraph> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, LoomGraph> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Runs `f` under the read lock.
+    pub fn with_read<T>(&self, f: impl FnOnce(&LoomGraph) -> T) -> T {
+        f(&self.read())
+    }
+
+    /// Runs `f` under the write lock.
+    pub fn with_write<T>(&self, f: impl FnOnce(&mut LoomGraph) -> T) -> T {
+        f(&mut self.write())
+    }
+
+    // --- Consultas (read lock) ---
+
+    pub fn search(&self, query: &str) -> Vec<(NodeId, f32)> {
+        self.read().search_native(query)
+    }
+
+    pub fn get_context(&self, min_activation: f32) -> String {
+        self.read().get_context_prompt(min_activation)
+    }
+
+    pub fn get_context_as(&self, format: ContextFormat, min_activation: f32) -> String {
+        self.read().get_context_as(format, min_activation)
+    }
+
+    pub fn peek_activation(&self, id: NodeId) -> Option<f32> {
+        self.read().peek_activation(id)
+    }
+
+    pub fn get_node(&self, id: NodeId) -> Option<Node> {
+        self.read().get_node(id).cloned()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.read().node_count()
+    }
+
+    // --- Mutações (write lock) ---
+
+    /// Search that counts hits as accesses (takes the write lock).
+    pub fn recall(&self, query: &str) -> Vec<(NodeId, f32)> {
+        self.write().recall(query)
+    }
+
+    pub fn add_concept(&self, name: String, definition: String) -> NodeId {
+        self.write().add_concept(name, definition)
+    }
+
+    pub fn add_episode(&self, summary: String) -> NodeId {
+        self.write().add_episode(summary)
+    }
+
+    pub fn connect(&self, source: NodeId, target: NodeId, weight: f32) -> bool {
+        self.write().connect(source, target, weight)
+    }
+
+    pub fn stimulate(&self, id: NodeId, force: f32) -> bool {
+        self.write().stimulate(id, force)
+    }
+
+    pub fn tick(&self) {
+        self.write().tick();
+    }
+
+    /// Takes the graph back out if this is the last handle.
+    pub fn into_inner(self) -> Result<LoomGraph, SharedLoom> {
+        Arc::try_unwrap(self.inner)
+            .map(|lock| lock.into_inner().unwrap_or_else(PoisonError::into_inner))
+            .map_err(|inner| SharedLoom { inner })
+    }
+}
@@ -0,0 +1,67 @@
+//! # Skills (Procedural Memory)
+//! What the agent knows how to *do*. Every use is recorded with
+//! `record_skill_use`: any use refreshes the skill (a local boost), and
+//! successful uses also make it more stable, so practiced skills outlast
+//! abandoned ones. Attempts and successes are kept on the node.
+
+use crate::{GraphEvent, LoomGraph, Node, NodeId, NodeKind, SkillData};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SkillConfig {
+    /// Activation boost for every use, successful or not.
+    pub use_boost: f32,
+    /// Fraction of the remaining headroom (up to stability 100) gained per success.
+    pub success_stability_gain: f32,
+}
+
+impl Default for SkillConfig {
+    fn default() -> Self {
+        Self {
+            use_boost: 0.3,
+            success_stability_gain: 0.05,
+        }
+    }
+}
+
+impl SkillData {
+    /// Successes over attempts (0 before the first attempt).
+    pub fn success_rate(&self) -> f32 {
+        if self.attempts == 0 { 0.0 } else { self.successes as f32 / self.attempts as f32 }
+    }
+}
+
+impl LoomGraph {
+    pub fn add_skill(&mut self, name: String, description: String, steps: Vec<String>) -> NodeId {
+        let node = Node::Skill(self.new_meta(NodeKind::Skill), SkillData { name, description, steps, attempts: 0, successes: 0 });
+        let id = self.add_node_internal(node);
+        self.enforce_capacity(&[id]);
+        id
+    }
+
+    /// Records one use of a skill. Returns the new success rate, or None if
+    /// `id` isn't a skill.
+    pub fn record_skill_use(&mut self, id: NodeId, success: bool) -> Option<f32> {
+        let Some(Node::Skill(..)) = self.nodes.get(&id) else { return None };
+        self.boost_node(id, self.config.skills.use_boost, 1);
+        let rate = self.tally_skill_use(id, success);
+        self.maybe_autosave();
+        rate
+    }
+
+    /// Counts the attempt (and success) without the boost.
+    pub(crate) fn tally_skill_use(&mut self, id: NodeId, success: bool) -> Option<f32> {
+        let gain = self.config.skills.success_stability_gain;
+        let cap = self.config.dynamics.stability_cap;
+        let Some(Node::Skill(meta, data)) = self.nodes.get_mut(&id) else { return None };
+        data.attempts += 1;
+        if success {
+            data.successes += 1;
+            meta.stability = (meta.stability + (cap - meta.stability).max(0.0) * gain).min(cap);
+        }
+        let rate = data.success_rate();
+        self.record(GraphEvent::SkillUsed { id, success });
+        Some(rate)
+    }
+}
@@ -0,0 +1,491 @@
+//! # Storage Backends
+//! Durable, queryable persistence that does not require the whole graph in RAM.
+//!
+//! A `StorageBackend` stores nodes, outgoing edge lists, a token index and a
+//! small key/value header. `LoomGraph` can be saved into / loaded from any
+//! backend wholesale, or opened lazily: only the header is read, and nodes are
+//! hydrated on demand (`hydrate`, `search_backend`) and written back with
+//! `flush_node`.
+//!
+//! `MemoryBackend` is the default; `SqliteBackend` requires the `sqlite` feature.
+
+use crate::persistence;
+use crate::{ActiveSession, ArchivedNode, Connection, LoomConfig, LoomGraph, Node, NodeId, OpKind, ScheduledEvent, SyncState};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+
+const HEADER_KEY: &str = "graph_header";
+
+pub trait StorageBackend {
+    fn put_node(&mut self, node: &Node) -> io::Result<()>;
+    fn get_node(&self, id: NodeId) -> io::Result<Option<Node>>;
+    fn remove_node(&mut self, id: NodeId) -> io::Result<()>;
+    fn node_ids(&self) -> io::Result<Vec<NodeId>>;
+
+    /// Replaces the full outgoing edge list of `source`.
+    fn put_edges(&mut self, source: NodeId, edges: &[Connection]) -> io::Result<()>;
+    fn get_edges(&self, source: NodeId) -> io::Result<Vec<Connection>>;
+
+    /// Ids of nodes with an index token containing `fragment` (same semantics as `search_native`).
+    fn lookup_token(&self, fragment: &str) -> io::Result<Vec<NodeId>>;
+
+    fn put_meta(&mut self, key: &str, value: &str) -> io::Result<()>;
+    fn get_meta(&self, key: &str) -> io::Result<Option<String>>;
+
+    /// Starts a batch of writes that `commit` applies at once (no-op by default).
+    fn begin(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn commit(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Discards the writes since `begin`, where the backend can.
+    fn rollback(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Graph-level fields persisted alongside the nodes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GraphHeader {
+    schema_version: u32,
+    current_tick: u64,
+    decay_rate: f32,
+    last_saved: Option<DateTime<Utc>>,
+    #[serde(default)]
+    next_seq: u64,
+    #[serde(default)]
+    active_session: Option<ActiveSession>,
+    #[serde(default)]
+    scheduled: Vec<ScheduledEvent>,
+    #[serde(default, serialize_with = "persistence::sorted_map")]
+    archived: HashMap<NodeId, ArchivedNode>,
+    #[serde(default, serialize_with = "persistence::sorted_map")]
+    tombstones: HashMap<NodeId, u64>,
+    #[serde(default)]
+    sync: SyncState,
+    #[serde(default)]
+    config: LoomConfig,
+    #[serde(default)]
+    wal_seq: u64,
+    #[serde(default)]
+    clock_synced_at: Option<DateTime<Utc>>,
+}
+
+// ----------------------------------------------------------------------------
+// Backend em memória
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBackend {
+    nodes: HashMap<NodeId, Node>,
+    edges: HashMap<NodeId, Vec<Connection>>,
+    tokens: HashMap<String, Vec<NodeId>>,
+    meta: HashMap<String, String>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn unindex(&mut self, id: NodeId) {
+        for ids in self.tokens.values_mut() {
+            ids.retain(|n| *n != id);
+        }
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn put_node(&mut self, node: &Node) -> io::Result<()> {
+        let id = node.meta().id;
+        self.unindex(id);
+        for token in node.tokens() {
+            self.tokens.entry(token).or_default().push(id);
+        }
+        self.nodes.insert(id, node.clone());
+        Ok(())
+    }
+
+    fn get_node(&self, id: NodeId) -> io::Result<Option<Node>> {
+        Ok(self.nodes.get(&id).cloned())
+    }
+
+    fn remove_node(&mut self, id: NodeId) -> io::Result<()> {
+        self.unindex(id);
+        self.nodes.remove(&id);
+        self.edges.remove(&id);
+        Ok(())
+    }
+
+    fn node_ids(&self) -> io::Result<Vec<NodeId>> {
+        Ok(self.nodes.keys().copied().collect())
+    }
+
+    fn put_edges(&mut self, source: NodeId, edges: &[Connection]) -> io::Result<()> {
+        if edges.is_empty() {
+            self.edges.remove(&source);
+        } else {
+            self.edges.insert(source, edges.to_vec());
+        }
+        Ok(())
+    }
+
+    fn get_edges(&self, source: NodeId) -> io::Result<Vec<Connection>> {
+        Ok(self.edges.get(&source).cloned().unwrap_or_default())
+    }
+
+    fn lookup_token(&self, fragment: &str) -> io::Result<Vec<NodeId>> {
+        let mut ids: Vec<NodeId> = self.tokens.iter()
+            .filter(|(token, _)| token.contains(fragment))
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        Ok(ids)
+    }
+
+    fn put_meta(&mut self, key: &str, value: &str) -> io::Result<()> {
+        self.meta.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn get_meta(&self, key: &str) -> io::Result<Option<String>> {
+        Ok(self.meta.get(key).cloned())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Backend SQLite (feature = "sqlite")
+// ----------------------------------------------------------------------------
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteBackend;
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::*;
+    use rusqlite::{params, Connection as SqlConnection, OptionalExtension};
+
+    fn sql_err(e: rusqlite::Error) -> io::Error {
+        io::Error::other(e)
+    }
+
+    fn json_err(e: serde_json::Error) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+
+    fn parse_id(raw: String) -> io::Result<NodeId> {
+        raw.parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub struct SqliteBackend {
+        conn: SqlConnection,
+    }
+
+    impl SqliteBackend {
+        pub fn open(path: &str) -> io::Result<Self> {
+            Self::init(SqlConnection::open(path).map_err(sql_err)?)
+        }
+
+        pub fn open_in_memory() -> io::Result<Self> {
+            Self::init(SqlConnection::open_in_memory().map_err(sql_err)?)
+        }
+
+        fn init(conn: SqlConnection) -> io::Result<Self> {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS nodes (id TEXT PRIMARY KEY, kind TEXT NOT NULL, data TEXT NOT NULL);
+                 CREATE TABLE IF NOT EXISTS edges (source TEXT NOT NULL, ordinal INTEGER NOT NULL, target TEXT NOT NULL,
+                                                   weight REAL NOT NULL, edge_type TEXT NOT NULL,
+                                                   inferred INTEGER NOT NULL DEFAULT 0, PRIMARY KEY (source, ordinal));
+                 CREATE TABLE IF NOT EXISTS tokens (token TEXT NOT NULL, node_id TEXT NOT NULL);
+                 CREATE INDEX IF NOT EXISTS tokens_by_token ON tokens (token);
+                 CREATE INDEX IF NOT EXISTS tokens_by_node ON tokens (node_id);
+                 CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);",
+            )
+            .map_err(sql_err)?;
+            // Bancos anteriores às regras de inferência não têm a coluna `inferred`
+            let has_inferred: bool = conn
+                .query_row("SELECT COUNT(*) > 0 FROM pragma_table_info('edges') WHERE name = 'inferred'", [], |row| row.get(0))
+                .map_err(sql_err)?;
+            if !has_inferred {
+                conn.execute_batch("ALTER TABLE edges ADD COLUMN inferred INTEGER NOT NULL DEFAULT 0").map_err(sql_err)?;
+            }
+            Ok(Self { conn })
+        }
+
+        /// Runs `f` inside a single transaction (much faster for bulk saves).
+        pub fn transaction<T>(&mut self, f: impl FnOnce(&mut Self) -> io::Result<T>) -> io::Result<T> {
+            self.begin()?;
+            match f(self) {
+                Ok(value) => {
+                    self.commit()?;
+                    Ok(value)
+                }
+                Err(e) => {
+                    let _ = self.rollback();
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    impl StorageBackend for SqliteBackend {
+        fn put_node(&mut self, node: &Node) -> io::Result<()> {
+            let id = node.meta().id.to_string();
+            let data = serde_json::to_string(node).map_err(json_err)?;
+            self.conn
+                .execute(
+                    "INSERT INTO nodes (id, kind, data) VALUES (?1, ?2, ?3)
+                     ON CONFLICT(id) DO UPDATE SET kind = excluded.kind, data = excluded.data",
+                    params![id, format!("{:?}", node.kind()), data],
+                )
+                .map_err(sql_err)?;
+            self.conn.execute("DELETE FROM tokens WHERE node_id = ?1", params![id]).map_err(sql_err)?;
+            let mut insert = self.conn
+                .prepare_cached("INSERT INTO tokens (token, node_id) VALUES (?1, ?2)")
+                .map_err(sql_err)?;
+            for token in node.tokens() {
+                insert.execute(params![token, id]).map_err(sql_err)?;
+            }
+            Ok(())
+        }
+
+        fn get_node(&self, id: NodeId) -> io::Result<Option<Node>> {
+            let data: Option<String> = self.conn
+                .query_row("SELECT data FROM nodes WHERE id = ?1", params![id.to_string()], |row| row.get(0))
+                .optional()
+                .map_err(sql_err)?;
+            data.map(|d| serde_json::from_str(&d).map_err(json_err)).transpose()
+        }
+
+        fn remove_node(&mut self, id: NodeId) -> io::Result<()> {
+            let id = id.to_string();
+            self.conn.execute("DELETE FROM nodes WHERE id = ?1", params![id]).map_err(sql_err)?;
+            self.conn.execute("DELETE FROM tokens WHERE node_id = ?1", params![id]).map_err(sql_err)?;
+            self.conn.execute("DELETE FROM edges WHERE source = ?1", params![id]).map_err(sql_err)?;
+            Ok(())
+        }
+
+        fn node_ids(&self) -> io::Result<Vec<NodeId>> {
+            let mut stmt = self.conn.prepare("SELECT id FROM nodes").map_err(sql_err)?;
+            let rows = stmt.query_map([], |row| row.get::<_, String>(0)).map_err(sql_err)?;
+            rows.map(|r| r.map_err(sql_err).and_then(parse_id)).collect()
+        }
+
+        fn put_edges(&mut self, source: NodeId, edges: &[Connection]) -> io::Result<()> {
+            let source = source.to_string();
+            self.conn.execute("DELETE FROM edges WHERE source = ?1", params![source]).map_err(sql_err)?;
+            let mut insert = self.conn
+                .prepare_cached("INSERT INTO edges (source, ordinal, target, weight, edge_type, inferred) VALUES (?1, ?2, ?3, ?4, ?5, ?6)")
+                .map_err(sql_err)?;
+            for (ordinal, edge) in edges.iter().enumerate() {
+                insert
+                    .execute(params![source, ordinal as i64, edge.target.to_string(), edge.weight, edge.edge_type, edge.inferred])
+                    .map_err(sql_err)?;
+            }
+            Ok(())
+        }
+
+        fn get_edges(&self, source: NodeId) -> io::Result<Vec<Connection>> {
+            let mut stmt = self.conn
+                .prepare_cached("SELECT target, weight, edge_type, inferred FROM edges WHERE source = ?1 ORDER BY ordinal")
+                .map_err(sql_err)?;
+            let rows = stmt
+                .query_map(params![source.to_string()], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, f32>(1)?, row.get::<_, String>(2)?, row.get::<_, bool>(3)?))
+                })
+                .map_err(sql_err)?;
+            rows.map(|r| {
+                let (target, weight, edge_type, inferred) = r.map_err(sql_err)?;
+                Ok(Connection { target: parse_id(target)?, weight, edge_type, inferred })
+            })
+            .collect()
+        }
+
+        fn lookup_token(&self, fragment: &str) -> io::Result<Vec<NodeId>> {
+            let mut stmt = self.conn
+                .prepare_cached("SELECT DISTINCT node_id FROM tokens WHERE instr(token, ?1) > 0 ORDER BY node_id")
+                .map_err(sql_err)?;
+            let rows = stmt.query_map(params![fragment], |row| row.get::<_, String>(0)).map_err(sql_err)?;
+            rows.map(|r| r.map_err(sql_err).and_then(parse_id)).collect()
+        }
+
+        fn put_meta(&mut self, key: &str, value: &str) -> io::Result<()> {
+            self.conn
+                .execute(
+                    "INSERT INTO meta (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+                    params![key, value],
+                )
+                .map_err(sql_err)?;
+            Ok(())
+        }
+
+        fn get_meta(&self, key: &str) -> io::Result<Option<String>> {
+            self.conn
+                .query_row("SELECT value FROM meta WHERE key = ?1", params![key], |row| row.get(0))
+                .optional()
+                .map_err(sql_err)
+        }
+
+        fn begin(&mut self) -> io::Result<()> {
+            self.conn.execute_batch("BEGIN").map_err(sql_err)
+        }
+
+        fn commit(&mut self) -> io::Result<()> {
+            self.conn.execute_batch("COMMIT").map_err(sql_err)
+        }
+
+        fn rollback(&mut self) -> io::Result<()> {
+            self.conn.execute_batch("ROLLBACK").map_err(sql_err)
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Integração com LoomGraph
+// ----------------------------------------------------------------------------
+
+impl LoomGraph {
+    fn header(&self) -> GraphHeader {
+        GraphHeader {
+            schema_version: self.schema_version,
+            current_tick: self.current_tick,
+            decay_rate: self.decay_rate,
+            last_saved: self.last_saved,
+            next_seq: self.next_seq,
+            active_session: self.active_session.clone(),
+            scheduled: self.scheduled.clone(),
+            archived: self.archived.clone(),
+            tombstones: self.tombstones.clone(),
+            sync: self.sync.clone(),
+            config: self.config.clone(),
+            wal_seq: self.wal_seq,
+            clock_synced_at: self.clock_synced_at,
+        }
+    }
+
+    pub fn save_header(&self, backend: &mut impl StorageBackend) -> io::Result<()> {
+        let header = serde_json::to_string(&self.header()).map_err(io::Error::other)?;
+        backend.put_meta(HEADER_KEY, &header)
+    }
+
+    /// Writes every node, edge list and the header into `backend` in one
+    /// transaction, and deletes the nodes removed since the last such save.
+    pub fn save_to_backend(&mut self, backend: &mut impl StorageBackend) -> io::Result<()> {
+        let started = Utc::now();
+        let previous = self.last_saved.replace(self.now());
+        backend.begin()?;
+        if let Err(e) = self.write_to_backend(backend).and_then(|_| backend.commit()) {
+            let _ = backend.rollback();
+            self.last_saved = previous;
+            return Err(e);
+        }
+        self.removed_since_save.clear();
+        self.mark_clean();
+        self.record_timing(OpKind::Save, started);
+        Ok(())
+    }
+
+    fn write_to_backend(&self, backend: &mut impl StorageBackend) -> io::Result<()> {
+        // Nós podados ou fundidos desde o último save (e que não voltaram)
+        for id in &self.removed_since_save {
+            if !self.nodes.contains_key(id) {
+                backend.remove_node(*id)?;
+            }
+        }
+        for node in self.nodes.values() {
+            let id = node.meta().id;
+            backend.put_node(node)?;
+            backend.put_edges(id, self.adjacency.get(&id).map(Vec::as_slice).unwrap_or(&[]))?;
+        }
+        self.save_header(backend)
+    }
+
+    /// Opens a graph with only its header loaded; nodes are hydrated on demand.
+    pub fn open_lazy(backend: &impl StorageBackend) -> io::Result<Self> {
+        let mut graph = LoomGraph::new(0.95);
+        if let Some(raw) = backend.get_meta(HEADER_KEY)? {
+            let header: GraphHeader = serde_json::from_str(&raw)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            graph.schema_version = header.schema_version;
+            graph.current_tick = header.current_tick;
+            graph.decay_rate = header.decay_rate;
+            graph.last_saved = header.last_saved;
+            graph.next_seq = header.next_seq;
+            graph.active_session = header.active_session;
+            graph.scheduled = header.scheduled;
+            graph.archived = header.archived;
+            graph.tombstones = header.tombstones;
+            graph.sync = header.sync;
+            graph.config = header.config;
+            graph.wal_seq = header.wal_seq;
+            graph.clock_synced_at = header.clock_synced_at;
+        }
+        Ok(graph)
+    }
+
+    /// Loads the whole graph from `backend`.
+    pub fn load_from_backend(backend: &impl StorageBackend) -> io::Result<Self> {
+        let mut graph = Self::open_lazy(backend)?;
+        for id in backend.node_ids()? {
+            graph.hydrate(backend, id)?;
+        }
+        Ok(graph)
+    }
+
+    /// Brings one node (and its outgoing edges) into memory. Returns false if
+    /// the backend does not have it. Already-resident nodes are left untouched.
+    pub fn hydrate(&mut self, backend: &impl StorageBackend, id: NodeId) -> io::Result<bool> {
+        if self.nodes.contains_key(&id) {
+            return Ok(true);
+        }
+        let Some(node) = backend.get_node(id)? else { return Ok(false) };
+        self.index_node(&node);
+        self.nodes.insert(id, node);
+        let edges = backend.get_edges(id)?;
+        if !edges.is_empty() {
+            self.adjacency.insert(id, edges);
+        }
+        Ok(true)
+    }
+
+    /// Searches the backend's index, hydrating matching nodes before ranking.
+    pub fn search_backend(&mut self, backend: &impl StorageBackend, query: &str) -> io::Result<Vec<(NodeId, f32)>> {
+        let clean = query.trim().to_lowercase();
+        if clean.is_empty() {
+            return Ok(Vec::new());
+        }
+        for id in backend.lookup_token(&clean)? {
+            self.hydrate(backend, id)?;
+        }
+        Ok(self.search_kinds(&clean, &[]))
+    }
+
+    /// Writes one resident node and its edge list back to the backend.
+    pub fn flush_node(&self, backend: &mut impl StorageBackend, id: NodeId) -> io::Result<bool> {
+        let Some(node) = self.nodes.get(&id) else { return Ok(false) };
+        backend.put_node(node)?;
+        backend.put_edges(id, self.adjacency.get(&id).map(Vec::as_slice).unwrap_or(&[]))?;
+        Ok(true)
+    }
+
+    /// Drops a resident node from memory without touching the backend.
+    pub fn evict_resident(&mut self, id: NodeId) {
+        if let Some(node) = self.nodes.remove(&id) {
+            for token in node.tokens() {
+                if let Some(list) = self.index.get_mut(&token) {
+                    list.retain(|n| *n != id);
+                    if list.is_empty() {
+                        self.index.remove(&token);
+                    }
+                }
+            }
+        }
+        self.adjacency.remove(&id);
+    }
+}
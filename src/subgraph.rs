@@ -0,0 +1,65 @@
+//! # Subgraph Extraction
+//! The ego-graph around a node: everything within `radius` hops (edges
+//! followed in both directions, skipping edges weaker than `min_weight`),
+//! returned as a standalone `LoomGraph`. Small enough to prompt on directly
+//! or ship to a client, and can be merged back elsewhere with `graft`.
+
+use crate::{LoomGraph, NodeId};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+impl LoomGraph {
+    /// Nodes keep their ids, activation and metadata; only edges between
+    /// included nodes with `|weight| >= min_weight` are kept. Empty graph if
+    /// `center` does not exist.
+    pub fn subgraph(&self, center: NodeId, radius: usize, min_weight: f32) -> LoomGraph {
+        let mut sub = LoomGraph::new(self.decay_rate);
+        sub.current_tick = self.current_tick;
+        sub.config = self.config.clone();
+        if !self.nodes.contains_key(&center) {
+            return sub;
+        }
+
+        // Vizinhança não-direcionada: arestas de entrada também contam
+        let strong = |weight: f32| weight.abs() >= min_weight;
+        let mut incoming: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for (source, edges) in &self.adjacency {
+            for edge in edges.iter().filter(|e| strong(e.weight)) {
+                incoming.entry(edge.target).or_default().push(*source);
+            }
+        }
+
+        let mut included: HashSet<NodeId> = HashSet::from([center]);
+        let mut queue = VecDeque::from([(center, 0usize)]);
+        while let Some((id, depth)) = queue.pop_front() {
+            if depth == radius {
+                continue;
+            }
+            let outgoing = self.adjacency.get(&id).into_iter().flatten()
+                .filter(|e| strong(e.weight))
+                .map(|e| e.target);
+            let neighbours: Vec<NodeId> = outgoing
+                .chain(incoming.get(&id).into_iter().flatten().copied())
+                .collect();
+            for next in neighbours {
+                if self.nodes.contains_key(&next) && included.insert(next) {
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+
+        for id in &included {
+            sub.nodes.insert(*id, self.nodes[id].clone());
+            let edges: Vec<_> = self.adjacency.get(id).into_iter().flatten()
+                .filter(|e| strong(e.weight) && included.contains(&e.target))
+                .cloned()
+                .collect();
+            if !edges.is_empty() {
+                sub.adjacency.insert(*id, edges);
+            }
+        }
+        sub.next_seq = self.next_seq;
+        sub.rebuild_index();
+        sub.mark_clean();
+        sub
+    }
+}
@@ -0,0 +1,255 @@
+//! # Sync (CRDT)
+//! Continuous, conflict-free sync between replicas of one brain (e.g. two
+//! browsers). Each replica stamps its changes with a Lamport clock:
+//!
+//! - Local edits are detected lazily. Whenever a changeset is requested, every
+//!   node (with its outgoing edges) is compared with a digest from the
+//!   previous scan, so no mutation path needs to know about sync.
+//! - Node content and metadata are last-writer-wins on `(lamport, replica)`.
+//! - Edges are a set union keyed by `(source, target, edge_type)`; the
+//!   weight is the max seen.
+//! - Deletions travel as stamped tombstones and beat older versions.
+//!
+//! Protocol: the receiver sends its `sync_clock()`, the sender answers with
+//! `changes_since(clock)` and the receiver calls `apply_changes`. Sync goes
+//! both ways by swapping roles. Soft `forget` state is local; a forgotten node
+//! syncs as deleted once its tombstone expires.
+
+use crate::persistence::{self, canonical_edge_order};
+use crate::{Connection, LoomGraph, Node, NodeId};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Version of a node: Lamport time plus the replica that wrote it (tie-break).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Stamp {
+    pub lamport: u64,
+    pub replica: Uuid,
+}
+
+/// Highest Lamport time seen from each replica.
+pub type VectorClock = BTreeMap<Uuid, u64>;
+
+/// Per-replica bookkeeping (persisted with the graph).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    replica: Option<Uuid>,
+    lamport: u64,
+    #[serde(serialize_with = "persistence::sorted_map")]
+    stamps: HashMap<NodeId, Stamp>,
+    #[serde(serialize_with = "persistence::sorted_map")]
+    digests: HashMap<NodeId, u64>,
+    #[serde(serialize_with = "persistence::sorted_map")]
+    removed: HashMap<NodeId, Stamp>,
+    clock: VectorClock,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeChange {
+    pub stamp: Stamp,
+    pub node: Node,
+    /// The node's full outgoing edge list.
+    pub edges: Vec<Connection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeSet {
+    pub replica: Uuid,
+    /// Sender's clock once these changes are applied.
+    pub clock: VectorClock,
+    pub nodes: Vec<NodeChange>,
+    pub removed: Vec<(NodeId, Stamp)>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub inserted: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub edges_added: usize,
+    /// Incoming versions older than what this replica already has.
+    pub stale: usize,
+}
+
+impl SyncState {
+    /// Same history under a fresh replica id (assigned on first use), so a
+    /// fork never writes stamps that collide with its origin's.
+    pub(crate) fn forked(&self) -> Self {
+        Self { replica: None, ..self.clone() }
+    }
+}
+
+impl LoomGraph {
+    /// This replica's id, assigned on first use.
+    pub fn replica_id(&mut self) -> Uuid {
+        match self.sync.replica {
+            Some(replica) => replica,
+            None => {
+                let replica = self.new_uuid();
+                self.sync.replica = Some(replica);
+                self.dirty += 1;
+                replica
+            }
+        }
+    }
+
+    /// What this replica has seen; send it to a peer to ask for `changes_since`.
+    pub fn sync_clock(&mut self) -> VectorClock {
+        self.stamp_local_changes();
+        self.sync.clock.clone()
+    }
+
+    /// Every node version and deletion the holder of `since` hasn't seen.
+    pub fn changes_since(&mut self, since: &VectorClock) -> ChangeSet {
+        self.stamp_local_changes();
+        let unseen = |stamp: &Stamp| stamp.lamport > since.get(&stamp.replica).copied().unwrap_or(0);
+
+        let mut nodes: Vec<NodeChange> = self.sync.stamps.iter()
+            .filter(|(_, stamp)| unseen(stamp))
+            .filter_map(|(id, stamp)| {
+                Some(NodeChange {
+                    stamp: *stamp,
+                    node: self.nodes.get(id)?.clone(),
+                    edges: self.adjacency.get(id).cloned().unwrap_or_default(),
+                })
+            })
+            .collect();
+        nodes.sort_by_key(|change| change.node.meta().id);
+        let mut removed: Vec<(NodeId, Stamp)> = self.sync.removed.iter()
+            .filter(|(_, stamp)| unseen(stamp))
+            .map(|(id, stamp)| (*id, *stamp))
+            .collect();
+        removed.sort();
+
+        ChangeSet { replica: self.replica_id(), clock: self.sync.clock.clone(), nodes, removed }
+    }
+
+    /// Integrates a peer's changeset. Applying the same changes twice, or
+    /// changesets in any order, converges to the same graph.
+    pub fn apply_changes(&mut self, changes: ChangeSet) -> SyncReport {
+        // Edições locais pendentes precisam de carimbo antes de competir com as remotas
+        self.stamp_local_changes();
+        let mut report = SyncReport::default();
+
+        for (id, stamp) in changes.removed {
+            if self.sync.stamps.get(&id).is_some_and(|local| *local > stamp)
+                || self.sync.removed.get(&id).is_some_and(|known| *known >= stamp)
+            {
+                report.stale += 1;
+                continue;
+            }
+            if self.nodes.contains_key(&id) {
+                self.discard_nodes(&[id]);
+                report.removed += 1;
+            }
+            self.sync.stamps.remove(&id);
+            self.sync.removed.insert(id, stamp);
+        }
+
+        let mut incoming_edges = Vec::new();
+        for change in changes.nodes {
+            let id = change.node.meta().id;
+            if self.sync.removed.get(&id).is_some_and(|known| *known > change.stamp) {
+                report.stale += 1;
+                continue;
+            }
+            let local = self.sync.stamps.get(&id).copied();
+            if !self.nodes.contains_key(&id) {
+                self.add_node_internal(change.node);
+                report.inserted += 1;
+            } else if local.is_some_and(|local| local >= change.stamp) {
+                report.stale += 1;
+            } else {
+                let seq = self.nodes[&id].meta().seq;
+                let mut node = change.node;
+                node.meta_mut().seq = seq;
+                self.rewrite_node(id, |n| *n = node);
+                report.updated += 1;
+            }
+            if local.is_none_or(|local| local < change.stamp) {
+                self.sync.stamps.insert(id, change.stamp);
+                self.sync.removed.remove(&id);
+            }
+            incoming_edges.push((id, change.edges));
+        }
+
+        // Arestas: união de conjuntos, peso máximo
+        for (source, edges) in incoming_edges {
+            for edge in edges {
+                if !self.nodes.contains_key(&edge.target) {
+                    continue;
+                }
+                let existing = self.adjacency.get(&source)
+                    .and_then(|list| list.iter().find(|c| c.target == edge.target && c.edge_type == edge.edge_type))
+                    .map(|c| c.weight);
+                match existing {
+                    Some(weight) => {
+                        self.reweight_edge(source, edge.target, &edge.edge_type, weight.max(edge.weight));
+                    }
+                    None => {
+                        self.insert_edge(source, edge);
+                        report.edges_added += 1;
+                    }
+                }
+            }
+        }
+
+        for (replica, lamport) in changes.clock {
+            let seen = self.sync.clock.entry(replica).or_insert(0);
+            *seen = (*seen).max(lamport);
+            self.sync.lamport = self.sync.lamport.max(lamport);
+        }
+
+        // Tudo que mudou aqui veio do par: atualiza os digests sem gerar carimbos novos
+        self.sync.digests = self.nodes.keys().map(|id| (*id, self.sync_digest(*id))).collect();
+        self.journal.clear();
+        self.dirty += 1;
+        self.maybe_autosave();
+        report
+    }
+
+    /// Stamps nodes created, edited or deleted since the previous scan.
+    fn stamp_local_changes(&mut self) {
+        let replica = self.replica_id();
+        let changed: Vec<(NodeId, u64)> = self.nodes.keys()
+            .map(|id| (*id, self.sync_digest(*id)))
+            .filter(|(id, digest)| self.sync.digests.get(id) != Some(digest))
+            .collect();
+        let gone: Vec<NodeId> = self.sync.digests.keys()
+            .filter(|id| !self.nodes.contains_key(id))
+            .copied()
+            .collect();
+        if changed.is_empty() && gone.is_empty() {
+            return;
+        }
+
+        self.sync.lamport += 1;
+        let stamp = Stamp { lamport: self.sync.lamport, replica };
+        for (id, digest) in changed {
+            self.sync.digests.insert(id, digest);
+            self.sync.stamps.insert(id, stamp);
+            self.sync.removed.remove(&id);
+        }
+        for id in gone {
+            self.sync.digests.remove(&id);
+            self.sync.stamps.remove(&id);
+            self.sync.removed.insert(id, stamp);
+        }
+        self.sync.clock.insert(replica, self.sync.lamport);
+        self.dirty += 1;
+    }
+
+    /// Content digest of a node plus its outgoing edges. Persisted, so it is
+    /// taken over canonical bytes with a hash that doesn't vary across builds.
+    fn sync_digest(&self, id: NodeId) -> u64 {
+        // `Value` ordena as chaves dos objetos (tags incluídas)
+        let node = self.nodes.get(&id).and_then(|node| serde_json::to_value(node).ok());
+        let mut edges: Vec<&Connection> = self.adjacency.get(&id).map(|list| list.iter().collect()).unwrap_or_default();
+        edges.sort_by(|a, b| canonical_edge_order(a, b));
+        let bytes = serde_json::to_vec(&(node, edges)).unwrap_or_default();
+        let hash = Sha256::digest(&bytes);
+        u64::from_le_bytes(hash[..8].try_into().unwrap_or_default())
+    }
+}
@@ -0,0 +1,42 @@
+//! # Tags
+//! Key-value labels on node metadata (source, user id, topic...). Tags are
+//! not indexed for text search; look them up with `search_by_tag`.
+
+use crate::{GraphEvent, LoomGraph, NodeId};
+
+impl LoomGraph {
+    /// Sets (or replaces) a tag. Returns false if the node doesn't exist.
+    pub fn set_tag(&mut self, id: NodeId, key: String, value: String) -> bool {
+        match self.nodes.get_mut(&id) {
+            Some(node) => {
+                node.meta_mut().tags.insert(key.clone(), value.clone());
+                self.record(GraphEvent::TagSet { id, key, value });
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get_tag(&self, id: NodeId, key: &str) -> Option<&str> {
+        self.nodes.get(&id)?.meta().tags.get(key).map(String::as_str)
+    }
+
+    /// Removes a tag, returning its old value.
+    pub fn remove_tag(&mut self, id: NodeId, key: &str) -> Option<String> {
+        let removed = self.nodes.get_mut(&id)?.meta_mut().tags.remove(key);
+        if removed.is_some() {
+            self.record(GraphEvent::TagRemoved { id, key: key.to_string() });
+        }
+        removed
+    }
+
+    /// Nodes tagged `key=value`, most active first.
+    pub fn search_by_tag(&self, key: &str, value: &str) -> Vec<NodeId> {
+        let mut found: Vec<(NodeId, f32)> = self.nodes.iter()
+            .filter(|(id, node)| !self.is_forgotten(**id) && node.meta().tags.get(key).is_some_and(|v| v == value))
+            .map(|(id, node)| (*id, node.meta().activation))
+            .collect();
+        found.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        found.into_iter().map(|(id, _)| id).collect()
+    }
+}
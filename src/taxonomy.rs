@@ -0,0 +1,137 @@
+//! # Taxonomy
+//! Ontology edges pointing from the specific to the general: `IsA`
+//! ("Border Collie" -> "Dog") and `PartOf` ("wheel" -> "car"). Cycles are
+//! rejected. Boosting a node leaks activation *upward* through these edges
+//! (`config.taxonomy.upward_leak` per level, however deep the hierarchy),
+//! never down, instead of following the regular ripple.
+
+use crate::{Connection, LoomGraph, NodeId, RippleReport, PART_OF_EDGE};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub const IS_A_EDGE: &str = "IsA";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaxonomyConfig {
+    /// Fraction of a boost passed to each parent, compounding per level; 0 disables the leak.
+    pub upward_leak: f32,
+}
+
+impl Default for TaxonomyConfig {
+    fn default() -> Self {
+        Self { upward_leak: 0.3 }
+    }
+}
+
+pub(crate) fn is_taxonomy_edge(edge_type: &str) -> bool {
+    edge_type == IS_A_EDGE || edge_type == PART_OF_EDGE
+}
+
+impl LoomGraph {
+    /// `child` is a kind of `parent`. Returns false if either node is missing,
+    /// the edge exists or it would close a cycle.
+    pub fn add_is_a(&mut self, child: NodeId, parent: NodeId) -> bool {
+        self.add_taxonomy_edge(child, parent, IS_A_EDGE)
+    }
+
+    /// `part` is a component of `whole`. Same rules as `add_is_a`.
+    pub fn add_part_of(&mut self, part: NodeId, whole: NodeId) -> bool {
+        self.add_taxonomy_edge(part, whole, PART_OF_EDGE)
+    }
+
+    /// Every node above `id` via `IsA`/`PartOf`, nearest first.
+    pub fn ancestors(&self, id: NodeId) -> Vec<NodeId> {
+        let mut parents: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for (source, edges) in &self.adjacency {
+            for edge in edges.iter().filter(|e| is_taxonomy_edge(&e.edge_type)) {
+                parents.entry(*source).or_default().push(edge.target);
+            }
+        }
+        walk_levels(id, &parents)
+    }
+
+    /// Every node below `id` via `IsA`/`PartOf`, nearest first.
+    pub fn descendants(&self, id: NodeId) -> Vec<NodeId> {
+        let mut children: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for (source, edges) in &self.adjacency {
+            for edge in edges.iter().filter(|e| is_taxonomy_edge(&e.edge_type)) {
+                children.entry(edge.target).or_default().push(*source);
+            }
+        }
+        walk_levels(id, &children)
+    }
+
+    fn add_taxonomy_edge(&mut self, child: NodeId, parent: NodeId, edge_type: &str) -> bool {
+        if child == parent || !self.nodes.contains_key(&child) || !self.nodes.contains_key(&parent) {
+            return false;
+        }
+        let exists = self.adjacency.get(&child).into_iter().flatten()
+            .any(|e| e.target == parent && e.edge_type == edge_type);
+        if exists || self.ancestors(parent).contains(&child) {
+            return false;
+        }
+        self.insert_edge(child, Connection { target: parent, weight: 1.0, edge_type: edge_type.to_string(), inferred: false });
+        true
+    }
+
+    /// Spreads a boost on `id` to its ancestors, `upward_leak` compounding per level.
+    pub(crate) fn leak_upward(&mut self, id: NodeId, amount: f32, budget: usize, report: &mut RippleReport) {
+        let leak = self.config.taxonomy.upward_leak;
+        if leak <= 0.0 || !self.adjacency.get(&id).into_iter().flatten().any(|e| is_taxonomy_edge(&e.edge_type)) {
+            return;
+        }
+        let (tick, rates) = (self.current_tick, self.decay_rates());
+        let mut level = vec![id];
+        let mut seen: HashSet<NodeId> = HashSet::from([id]);
+        let mut force = amount;
+        loop {
+            force *= leak;
+            if force.abs() <= 0.01 {
+                return;
+            }
+            let mut next: Vec<NodeId> = level.iter()
+                .flat_map(|n| self.adjacency.get(n).into_iter().flatten())
+                .filter(|e| is_taxonomy_edge(&e.edge_type) && self.nodes.contains_key(&e.target))
+                .map(|e| e.target)
+                .filter(|t| seen.insert(*t))
+                .collect();
+            if next.is_empty() {
+                return;
+            }
+            next.sort();
+            for ancestor in &next {
+                if report.touched >= budget {
+                    report.truncated = true;
+                    return;
+                }
+                self.sample_activation([*ancestor]);
+                if let Some(node) = self.nodes.get_mut(ancestor) {
+                    self.journal.remember(node.meta());
+                    let rate = rates.of(node.kind());
+                    Self::boost_meta(node.meta_mut(), force, tick, rate);
+                    report.touched += 1;
+                }
+                self.sample_activation([*ancestor]);
+            }
+            level = next;
+        }
+    }
+}
+
+/// Breadth-first walk over `links`, level by level (each level sorted).
+fn walk_levels(start: NodeId, links: &HashMap<NodeId, Vec<NodeId>>) -> Vec<NodeId> {
+    let mut seen: HashSet<NodeId> = HashSet::from([start]);
+    let mut found = Vec::new();
+    let mut level = vec![start];
+    while !level.is_empty() {
+        let mut next: Vec<NodeId> = level.iter()
+            .flat_map(|n| links.get(n).into_iter().flatten().copied())
+            .filter(|n| seen.insert(*n))
+            .collect();
+        next.sort();
+        found.extend(&next);
+        level = next;
+    }
+    found
+}
@@ -0,0 +1,67 @@
+//! # Episode Time Handling
+//! Episodes with explicit, timezone-aware timestamps (imported history).
+//! Timestamps are normalized to UTC, implausible future dates are rejected,
+//! and identical timestamps keep their insertion order (`NodeMetadata::seq`).
+
+use crate::{LoomGraph, Node, NodeId};
+use chrono::{DateTime, Duration, Utc};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeError {
+    /// Not RFC 3339 / RFC 2822, or missing a UTC offset.
+    Unparseable(String),
+    InFuture { timestamp: DateTime<Utc>, tolerance_secs: u64 },
+}
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeError::Unparseable(input) => write!(
+                f, "'{}' is not a timestamp with a timezone (expected RFC 3339, e.g. 2024-05-01T12:00:00+02:00)", input
+            ),
+            TimeError::InFuture { timestamp, tolerance_secs } => write!(
+                f, "timestamp {} is more than {}s in the future", timestamp.to_rfc3339(), tolerance_secs
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TimeError {}
+
+/// Parses an RFC 3339 (or RFC 2822) timestamp with an explicit offset into UTC.
+pub fn parse_timestamp(input: &str) -> Result<DateTime<Utc>, TimeError> {
+    let input = input.trim();
+    DateTime::parse_from_rfc3339(input)
+        .or_else(|_| DateTime::parse_from_rfc2822(input))
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| TimeError::Unparseable(input.to_string()))
+}
+
+impl LoomGraph {
+    /// Adds an episode that happened at `iso_datetime` (any offset; stored as UTC).
+    pub fn add_episode_at(&mut self, summary: String, iso_datetime: &str) -> Result<NodeId, TimeError> {
+        let timestamp = parse_timestamp(iso_datetime)?;
+        self.add_episode_with_time(summary, timestamp)
+    }
+
+    pub fn add_episode_with_time(&mut self, summary: String, timestamp: DateTime<Utc>) -> Result<NodeId, TimeError> {
+        let tolerance_secs = self.config.future_tolerance_secs;
+        if timestamp > self.now() + Duration::seconds(tolerance_secs as i64) {
+            return Err(TimeError::InFuture { timestamp, tolerance_secs });
+        }
+        Ok(self.insert_episode(summary, timestamp))
+    }
+
+    /// Episodes oldest first; identical timestamps keep insertion order.
+    pub fn episodes_chronological(&self) -> Vec<NodeId> {
+        let mut episodes: Vec<(&DateTime<Utc>, u64, NodeId)> = self.nodes.values()
+            .filter_map(|node| match node {
+                Node::Episode(meta, data) => Some((&data.timestamp, meta.seq, meta.id)),
+                _ => None,
+            })
+            .collect();
+        episodes.sort();
+        episodes.into_iter().map(|(_, _, id)| id).collect()
+    }
+}
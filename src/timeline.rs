@@ -0,0 +1,301 @@
+//! # Timeline (Time-Travel Debugging)
+//! Opt-in mutation log that lets the graph be replayed as it existed at a past tick.
+//!
+//! When enabled, the graph snapshots its current state as the replay base and
+//! records every mutation (nodes added, rewritten or pruned, edges created,
+//! reweighted or removed, boosts, dreams, consolidations, metadata edits,
+//! schedule changes and configuration changes) with the tick at which it happened. Replaying is
+//! deterministic because boosts and dreams are pure functions of graph state
+//! and tick, and edits that depend on the wall clock record their result.
+//!
+//! The log lives in memory only (it is not saved with the graph) and keeps the
+//! last `config.event_log_capacity` events: older ones are folded into the
+//! base, so `reconstruct_at` can no longer reach the ticks before them.
+
+use crate::{Connection, ConsolidationConfig, DecayOverride, DynamicsConfig, Inverse, LoomConfig, LoomGraph, Node, NodeId, ScheduledEvent};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GraphEvent {
+    NodeAdded { node: Box<Node> },
+    Connected { source: NodeId, edge: Connection },
+    Boosted { id: NodeId, amount: f32, depth: u8 },
+    BatchBoosted { ids: Vec<NodeId>, amount: f32 },
+    Dreamed,
+    Consolidated { config: ConsolidationConfig },
+    Pruned { ids: Vec<NodeId> },
+    /// Node content replaced in place (aliases, attachments, sources, merges).
+    Rewritten { node: Box<Node> },
+    EdgeReweighted { source: NodeId, target: NodeId, edge_type: String, weight: f32 },
+    Disconnected { source: NodeId, target: NodeId, edge_type: String },
+    InferredEdgesRemoved,
+    DecayOverridden { id: NodeId, decay: Option<DecayOverride> },
+    AlwaysInContext { id: NodeId, pinned: bool },
+    /// `Some(floor)` pins, `None` unpins.
+    Pinned { id: NodeId, floor: Option<f32> },
+    TagSet { id: NodeId, key: String, value: String },
+    TagRemoved { id: NodeId, key: String },
+    NamespaceSet { id: NodeId, namespace: Option<String> },
+    ConfidenceSet { id: NodeId, value: f32 },
+    Forgotten { id: NodeId },
+    Unforgotten { id: NodeId },
+    GoalCompleted { id: NodeId },
+    /// The tally half of `record_skill_use`; its boost is logged separately.
+    SkillUsed { id: NodeId, success: bool },
+    DecayRateSet { rate: f32 },
+    ConfigSet { config: Box<LoomConfig> },
+    /// An `undo`, replayed by applying the same inverse.
+    Undone { inverse: Inverse },
+    /// The clock advanced (WAL only; replay adopts the entry's tick).
+    Ticked,
+    Scheduled { event: ScheduledEvent },
+    ScheduleCleared,
+    /// The first `count` scheduled events fell due; their effects follow as
+    /// their own events, so replay only dequeues them.
+    ScheduledRan { count: usize },
+    /// Access statistics bumped at wall-clock time `at`.
+    Accessed { ids: Vec<NodeId>, at: DateTime<Utc> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimedEvent {
+    pub tick: u64,
+    pub event: GraphEvent,
+}
+
+#[derive(Clone, Default)]
+pub struct EventLog {
+    enabled: bool,
+    base: Option<Box<LoomGraph>>,
+    events: VecDeque<TimedEvent>,
+}
+
+impl EventLog {
+    pub(crate) fn starting_from(base: LoomGraph) -> Self {
+        Self {
+            enabled: true,
+            base: Some(Box::new(base)),
+            events: VecDeque::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn events(&self) -> &VecDeque<TimedEvent> {
+        &self.events
+    }
+
+    pub(crate) fn push(&mut self, tick: u64, event: GraphEvent, capacity: usize) {
+        if !self.enabled {
+            return;
+        }
+        self.events.push_back(TimedEvent { tick, event });
+        while self.events.len() > capacity {
+            let Some(oldest) = self.events.pop_front() else { break };
+            // O evento mais antigo passa a fazer parte da base
+            if let Some(base) = self.base.as_mut() {
+                let clock = std::mem::take(&mut base.config.dynamics.clock);
+                base.current_tick = oldest.tick;
+                base.apply_event(&oldest.event);
+                base.config.dynamics.clock = clock;
+            }
+        }
+    }
+
+    pub(crate) fn reconstruct(&self, tick: u64) -> Option<LoomGraph> {
+        let base = self.base.as_ref()?;
+        if tick < base.current_tick {
+            return None; // Antes do início da gravação
+        }
+
+        let mut graph = (**base).clone();
+        // Relógio real parado: os ticks vêm dos eventos gravados
+        let clock = std::mem::take(&mut graph.config.dynamics.clock);
+        for timed in self.events.iter().take_while(|e| e.tick <= tick) {
+            graph.current_tick = timed.tick;
+            graph.apply_event(&timed.event);
+        }
+        graph.config.dynamics.clock = clock;
+        graph.current_tick = graph.current_tick.max(tick);
+        graph.refresh_goals();
+        graph.journal.clear();
+        Some(graph)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivationChange {
+    pub id: NodeId,
+    pub before: f32,
+    pub after: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryComparison {
+    pub from_tick: u64,
+    pub to_tick: u64,
+    pub added: Vec<NodeId>,
+    pub removed: Vec<NodeId>,
+    /// Surviving nodes whose projected activation moved, biggest drop first.
+    pub changed: Vec<ActivationChange>,
+    pub edges_added: usize,
+    pub edges_removed: usize,
+}
+
+impl MemoryComparison {
+    pub(crate) fn between(before: &LoomGraph, after: &LoomGraph) -> Self {
+        let mut added: Vec<NodeId> = after.nodes.keys()
+            .filter(|id| !before.nodes.contains_key(id))
+            .copied()
+            .collect();
+        let mut removed: Vec<NodeId> = before.nodes.keys()
+            .filter(|id| !after.nodes.contains_key(id))
+            .copied()
+            .collect();
+        added.sort();
+        removed.sort();
+
+        let mut changed: Vec<ActivationChange> = before.nodes.iter()
+            .filter_map(|(id, old)| {
+                let new = after.nodes.get(id)?;
+                let a = before.projected_activation(old.meta(), before.current_tick);
+                let b = after.projected_activation(new.meta(), after.current_tick);
+                ((b - a).abs() > 1e-4).then_some(ActivationChange { id: *id, before: a, after: b })
+            })
+            .collect();
+        changed.sort_by(|x, y| {
+            (x.after - x.before).partial_cmp(&(y.after - y.before)).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let edges_before = edge_set(before);
+        let edges_after = edge_set(after);
+
+        Self {
+            from_tick: before.current_tick,
+            to_tick: after.current_tick,
+            added,
+            removed,
+            changed,
+            edges_added: edges_after.difference(&edges_before).count(),
+            edges_removed: edges_before.difference(&edges_after).count(),
+        }
+    }
+}
+
+fn edge_set(graph: &LoomGraph) -> HashSet<(NodeId, NodeId)> {
+    graph.adjacency.iter()
+        .flat_map(|(source, edges)| edges.iter().map(move |c| (*source, c.target)))
+        .collect()
+}
+
+impl LoomGraph {
+    pub(crate) fn apply_event(&mut self, event: &GraphEvent) {
+        match event {
+            GraphEvent::NodeAdded { node } => {
+                self.add_node_internal((**node).clone());
+            }
+            GraphEvent::Connected { source, edge } => {
+                self.insert_edge(*source, edge.clone());
+            }
+            GraphEvent::Boosted { id, amount, depth } => {
+                self.boost(*id, *amount, *depth, false);
+            }
+            GraphEvent::BatchBoosted { ids, amount } => {
+                self.boost_batch(ids, *amount);
+            }
+            GraphEvent::Dreamed => {
+                self.dream_cycle();
+            }
+            GraphEvent::Consolidated { config } => {
+                self.consolidation_pass(config);
+            }
+            GraphEvent::Pruned { ids } => {
+                self.remove_nodes(ids);
+            }
+            GraphEvent::Rewritten { node } => {
+                let node = (**node).clone();
+                self.rewrite_node(node.meta().id, |n| *n = node);
+            }
+            GraphEvent::EdgeReweighted { source, target, edge_type, weight } => {
+                self.reweight_edge(*source, *target, edge_type, *weight);
+            }
+            GraphEvent::Disconnected { source, target, edge_type } => {
+                self.disconnect_edge(*source, *target, edge_type);
+            }
+            GraphEvent::InferredEdgesRemoved => {
+                self.remove_inferred_edges();
+            }
+            GraphEvent::DecayOverridden { id, decay } => {
+                self.set_decay_override(*id, *decay);
+            }
+            GraphEvent::AlwaysInContext { id, pinned } => {
+                self.set_always_in_context(*id, *pinned);
+            }
+            GraphEvent::Pinned { id, floor } => match floor {
+                Some(floor) => {
+                    self.pin_at(*id, *floor);
+                }
+                None => {
+                    self.unpin(*id);
+                }
+            },
+            GraphEvent::TagSet { id, key, value } => {
+                self.set_tag(*id, key.clone(), value.clone());
+            }
+            GraphEvent::TagRemoved { id, key } => {
+                self.remove_tag(*id, key);
+            }
+            GraphEvent::NamespaceSet { id, namespace } => {
+                self.set_namespace(*id, namespace.clone());
+            }
+            GraphEvent::ConfidenceSet { id, value } => {
+                self.set_confidence(*id, *value);
+            }
+            GraphEvent::Forgotten { id } => {
+                self.forget(*id);
+            }
+            GraphEvent::Unforgotten { id } => {
+                self.undo_forget(*id);
+            }
+            GraphEvent::GoalCompleted { id } => {
+                self.complete_goal(*id);
+            }
+            GraphEvent::SkillUsed { id, success } => {
+                self.tally_skill_use(*id, *success);
+            }
+            GraphEvent::DecayRateSet { rate } => {
+                let _ = self.set_decay_rate(*rate);
+            }
+            GraphEvent::ConfigSet { config } => {
+                // O modo do relógio fica como está durante o replay
+                let clock = self.config.dynamics.clock;
+                let config = LoomConfig {
+                    dynamics: DynamicsConfig { clock, ..config.dynamics.clone() },
+                    ..(**config).clone()
+                };
+                let _ = self.set_config(config);
+            }
+            GraphEvent::Undone { inverse } => {
+                self.apply_inverse(inverse);
+            }
+            GraphEvent::Ticked => {}
+            GraphEvent::Scheduled { event } => {
+                self.insert_scheduled(event.clone());
+            }
+            GraphEvent::ScheduleCleared => {
+                self.scheduled.clear();
+            }
+            GraphEvent::Accessed { ids, at } => {
+                self.apply_access(ids, *at);
+            }
+            GraphEvent::ScheduledRan { count } => {
+                let count = (*count).min(self.scheduled.len());
+                self.scheduled.drain(..count);
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! # Soft Delete
+//! `forget` hides a memory from search and context without destroying it:
+//! the node gets a tombstone and stays in the graph for
+//! `config.forget_grace_ticks`, during which `undo_forget` brings it back
+//! untouched. Once the grace period has passed, the next tick hard-deletes it
+//! (through the archive tier, like any other prune).
+
+use crate::{GraphEvent, LoomGraph, NodeId};
+
+impl LoomGraph {
+    /// Tombstones a node. Returns false if it doesn't exist or is already forgotten.
+    pub fn forget(&mut self, id: NodeId) -> bool {
+        if !self.nodes.contains_key(&id) || self.tombstones.contains_key(&id) {
+            return false;
+        }
+        self.tombstones.insert(id, self.current_tick);
+        self.record(GraphEvent::Forgotten { id });
+        self.maybe_autosave();
+        true
+    }
+
+    /// Lifts a tombstone before it expires. Returns false if the node isn't forgotten.
+    pub fn undo_forget(&mut self, id: NodeId) -> bool {
+        if self.tombstones.remove(&id).is_none() {
+            return false;
+        }
+        self.record(GraphEvent::Unforgotten { id });
+        self.maybe_autosave();
+        true
+    }
+
+    pub fn is_forgotten(&self, id: NodeId) -> bool {
+        self.tombstones.contains_key(&id)
+    }
+
+    /// Forgotten nodes as `(id, tick at which they'll be hard-deleted)`, soonest first.
+    pub fn forgotten(&self) -> Vec<(NodeId, u64)> {
+        let grace = self.config.forget_grace_ticks;
+        let mut pending: Vec<(NodeId, u64)> = self.tombstones.iter()
+            .map(|(id, at)| (*id, at.saturating_add(grace)))
+            .collect();
+        pending.sort_by_key(|(id, expires)| (*expires, *id));
+        pending
+    }
+
+    /// Hard-deletes tombstoned nodes whose grace period has passed.
+    pub(crate) fn expire_tombstones(&mut self) -> usize {
+        if self.tombstones.is_empty() {
+            return 0;
+        }
+        let grace = self.config.forget_grace_ticks;
+        let mut expired: Vec<NodeId> = self.tombstones.iter()
+            .filter(|(_, at)| at.saturating_add(grace) <= self.current_tick)
+            .map(|(id, _)| *id)
+            .collect();
+        if expired.is_empty() {
+            return 0;
+        }
+        expired.sort();
+        self.discard_nodes(&expired);
+        expired.len()
+    }
+}
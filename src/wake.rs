@@ -0,0 +1,148 @@
+//! # Waking Up
+//! Maps wall-clock time away (since `last_saved`) onto ticks, optionally
+//! sleeps through part of it (dreams), and reports what changed so hosts can
+//! show a "while you were gone" summary.
+//!
+//! With `ClockMode::RealTime` the same mapping runs continuously: every
+//! mutation first calls `catch_up_clock`, which advances the ticks elapsed
+//! since the last sync (the sub-tick remainder carries over), and `live_tick`
+//! lets read-only queries project decay to the present.
+
+use crate::{ClockMode, LoomGraph, NodeId, ScheduledEvent};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Wall-clock length of one dream (`dream_cycle` advances 8h).
+pub(crate) const DREAM_SECS: u64 = 8 * 3600;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WakeReport {
+    /// Ticks the clock advanced, dreams included.
+    pub ticks_elapsed: u64,
+    /// Nodes that crossed below `config.wake.fade_threshold` while away.
+    pub faded: Vec<NodeId>,
+    pub dreams: u32,
+    pub consolidated: usize,
+    pub pruned: usize,
+    #[serde(default)]
+    pub due: DueReminders,
+}
+
+/// Reminders that came due while away.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DueReminders {
+    /// Open goals whose deadline passed during the absence, as `due_goals` orders them.
+    pub goals: Vec<NodeId>,
+    /// Scheduled events that fired during the catch-up, in firing order.
+    pub scheduled: Vec<ScheduledEvent>,
+}
+
+impl LoomGraph {
+    /// Advances the clock by the time elapsed since the last save (one tick
+    /// per `config.dynamics.seconds_per_tick`, through `fast_forward`, so
+    /// scheduled events fire) and reports the effects. With `config.wake.dream_on_wake`, part of the
+    /// absence is spent dreaming (one dream per 8h, at most `max_dreams`).
+    pub fn wake_up(&mut self) -> WakeReport {
+        let now = self.now();
+        let (elapsed, anchor) = match self.config.dynamics.clock {
+            ClockMode::RealTime => self.pending_ticks(now),
+            ClockMode::Ticks => match self.last_saved {
+                Some(last) => (self.ticks_between(last, now), now),
+                None => (0, now),
+            },
+        };
+        let ticks_per_dream = self.config.dynamics.ticks_for_secs(DREAM_SECS);
+        self.last_saved = Some(now);
+        self.clock_synced_at = Some(anchor);
+
+        let mut report = WakeReport { ticks_elapsed: elapsed, ..WakeReport::default() };
+        if elapsed == 0 {
+            return report;
+        }
+
+        let threshold = self.config.wake.fade_threshold;
+        let before: HashMap<NodeId, f32> = self.nodes.iter()
+            .map(|(id, n)| (*id, self.projected_activation(n.meta(), self.current_tick)))
+            .filter(|(_, activation)| *activation >= threshold)
+            .collect();
+        let already_due = self.due_goals();
+        let pending = self.scheduled.clone();
+
+        let dreams = if self.config.wake.dream_on_wake {
+            (elapsed / ticks_per_dream).min(self.config.wake.max_dreams as u64)
+        } else {
+            0
+        };
+        // Tempo acordado primeiro (com os eventos agendados); cada sonho avança as 8h restantes
+        let fired = self.fast_forward(elapsed - dreams * ticks_per_dream);
+        for _ in 0..dreams {
+            let (consolidated, pruned) = self.dream_pass();
+            report.consolidated += consolidated;
+            report.pruned += pruned;
+        }
+        report.dreams = dreams as u32;
+        self.expire_tombstones();
+        self.refresh_goals();
+
+        let tick = self.current_tick;
+        report.faded = before.into_keys()
+            .filter(|id| {
+                self.nodes.get(id)
+                    .is_some_and(|n| self.projected_activation(n.meta(), tick) < threshold)
+            })
+            .collect();
+        report.faded.sort();
+        // A fila só esvazia pela frente: os `fired` primeiros são os que rodaram
+        report.due.scheduled = pending.into_iter().take(fired).collect();
+        report.due.goals = self.due_goals().into_iter().filter(|id| !already_due.contains(id)).collect();
+        report
+    }
+
+    /// The current tick as the wall clock sees it: `current_tick` plus, in
+    /// `ClockMode::RealTime`, the ticks elapsed since the last sync.
+    pub fn live_tick(&self) -> u64 {
+        match self.config.dynamics.clock {
+            ClockMode::Ticks => self.current_tick,
+            ClockMode::RealTime => self.current_tick.saturating_add(self.pending_ticks(self.now()).0),
+        }
+    }
+
+    /// In `ClockMode::RealTime`, advances the clock to the present (same
+    /// effects as `fast_forward`). Mutations call it themselves; returns the
+    /// ticks advanced (always 0 in `ClockMode::Ticks`).
+    pub fn catch_up_clock(&mut self) -> u64 {
+        if self.config.dynamics.clock != ClockMode::RealTime {
+            return 0;
+        }
+        let now = self.now();
+        self.tick_until(now)
+    }
+
+    /// Advances (like `fast_forward`) by the whole ticks between the last
+    /// clock sync (or save) and `now`, whatever the `ClockMode`. The first
+    /// call on a graph with neither only anchors the clock. Returns the
+    /// ticks advanced.
+    pub fn tick_until(&mut self, now: DateTime<Utc>) -> u64 {
+        let (ticks, anchor) = self.pending_ticks(now);
+        // Âncora primeiro: o fast_forward pode reentrar aqui via eventos agendados
+        self.clock_synced_at = Some(anchor);
+        if ticks > 0 {
+            self.fast_forward(ticks);
+        }
+        ticks
+    }
+
+    /// Whole ticks between the sync anchor (or the last save) and `now`,
+    /// plus the anchor moved past them. Without any anchor, starts at `now`.
+    fn pending_ticks(&self, now: DateTime<Utc>) -> (u64, DateTime<Utc>) {
+        let Some(anchor) = self.clock_synced_at.or(self.last_saved) else { return (0, now) };
+        let ticks = self.ticks_between(anchor, now);
+        let covered = ticks.saturating_mul(self.config.dynamics.seconds_per_tick.max(1));
+        (ticks, anchor + Duration::seconds(covered as i64))
+    }
+
+    fn ticks_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> u64 {
+        (to - from).num_seconds().max(0) as u64 / self.config.dynamics.seconds_per_tick.max(1)
+    }
+}
@@ -0,0 +1,179 @@
+//! # Write-Ahead Log
+//! Append-only journal of graph mutations for crash recovery between snapshots.
+//!
+//! Each line is a JSON `WalEntry` (sequence number, tick, `GraphEvent`). The
+//! graph stores the last sequence number it reflects (`wal_seq`), so a
+//! snapshot knows which entries it already contains: `recover` loads the
+//! snapshot and replays only newer entries. Ticks are logged too (`Ticked`),
+//! so the clock survives a crash. Compaction writes a fresh snapshot and
+//! truncates the log.
+
+use crate::{GraphEvent, LoomGraph};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalEntry {
+    pub seq: u64,
+    pub tick: u64,
+    pub event: GraphEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalOptions {
+    /// Snapshot written on compaction (and by `attach_wal` if missing).
+    pub snapshot_path: String,
+    /// Compact automatically (on `tick`) once this many entries have accumulated.
+    pub compact_every: Option<u64>,
+}
+
+struct WalState {
+    path: String,
+    writer: BufWriter<File>,
+    options: WalOptions,
+    since_compaction: u64,
+    last_error: Option<io::Error>,
+}
+
+/// Handle to the attached log. Cloning a graph yields a detached handle so two
+/// graphs never interleave writes into the same file.
+#[derive(Default)]
+pub struct WalHandle(Option<Arc<Mutex<WalState>>>);
+
+impl Clone for WalHandle {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl WalHandle {
+    pub fn is_attached(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn append(&self, entry: &WalEntry) {
+        let Some(state) = &self.0 else { return };
+        let Ok(mut state) = state.lock() else { return };
+        let result = serde_json::to_writer(&mut state.writer, entry)
+            .map_err(io::Error::from)
+            .and_then(|_| state.writer.write_all(b"\n"))
+            .and_then(|_| state.writer.flush());
+        match result {
+            Ok(()) => state.since_compaction += 1,
+            Err(e) => state.last_error = Some(e),
+        }
+    }
+}
+
+impl LoomGraph {
+    /// Starts journaling every mutation to `wal_path` (appending to any existing log).
+    /// Writes `options.snapshot_path` first if it doesn't exist yet, so
+    /// `recover` starts from this graph's config and decay rate.
+    pub fn attach_wal(&mut self, wal_path: &str, options: WalOptions) -> io::Result<()> {
+        if !Path::new(&options.snapshot_path).exists() {
+            self.save_to_file(&options.snapshot_path)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(wal_path)?;
+        self.wal = WalHandle(Some(Arc::new(Mutex::new(WalState {
+            path: wal_path.to_string(),
+            writer: BufWriter::new(file),
+            options,
+            since_compaction: 0,
+            last_error: None,
+        }))));
+        Ok(())
+    }
+
+    pub fn detach_wal(&mut self) {
+        self.wal = WalHandle::default();
+    }
+
+    /// Returns (and clears) the last write failure, if any. Appends never
+    /// interrupt the mutation that produced them.
+    pub fn take_wal_error(&mut self) -> Option<io::Error> {
+        let state = self.wal.0.as_ref()?;
+        state.lock().ok()?.last_error.take()
+    }
+
+    pub(crate) fn append_wal(&mut self, event: &GraphEvent) {
+        if !self.wal.is_attached() {
+            return;
+        }
+        self.wal_seq += 1;
+        self.wal.append(&WalEntry { seq: self.wal_seq, tick: self.current_tick, event: event.clone() });
+    }
+
+    /// Writes a fresh snapshot (which records `wal_seq`) and truncates the log.
+    pub fn compact_wal(&mut self) -> io::Result<()> {
+        let Some(state) = self.wal.0.clone() else { return Ok(()) };
+        let snapshot_path = state.lock().map_err(|_| io::Error::other("WAL lock poisoned"))?.options.snapshot_path.clone();
+
+        self.save_to_file(&snapshot_path)?;
+
+        let mut state = state.lock().map_err(|_| io::Error::other("WAL lock poisoned"))?;
+        let file = OpenOptions::new().create(true).write(true).truncate(true).open(&state.path)?;
+        state.writer = BufWriter::new(file);
+        state.since_compaction = 0;
+        Ok(())
+    }
+
+    pub(crate) fn maybe_compact_wal(&mut self) {
+        let due = match &self.wal.0 {
+            Some(state) => state.lock().ok().is_some_and(|s| {
+                s.options.compact_every.is_some_and(|every| s.since_compaction >= every)
+            }),
+            None => false,
+        };
+        if due {
+            if let Err(e) = self.compact_wal() {
+                if let Some(Ok(mut state)) = self.wal.0.as_ref().map(|s| s.lock()) {
+                    state.last_error = Some(e);
+                }
+            }
+        }
+    }
+
+    /// Rebuilds a graph after a crash: loads the snapshot and replays the WAL
+    /// entries it does not yet reflect. A torn final line is ignored.
+    pub fn recover(snapshot_path: &str, wal_path: &str) -> io::Result<LoomGraph> {
+        let mut graph = match File::open(snapshot_path) {
+            Ok(_) => LoomGraph::load_from_file(snapshot_path)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => LoomGraph::new(0.95),
+            Err(e) => return Err(e),
+        };
+
+        let wal = match File::open(wal_path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(graph),
+            Err(e) => return Err(e),
+        };
+
+        // Relógio real parado: os ticks vêm das entradas
+        let clock = std::mem::take(&mut graph.config.dynamics.clock);
+        let mut lines = BufReader::new(wal).lines().peekable();
+        while let Some(line) = lines.next() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: WalEntry = match serde_json::from_str(&line) {
+                Ok(entry) => entry,
+                Err(_) if lines.peek().is_none() => break, // Escrita interrompida pelo crash
+                Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+            };
+            if entry.seq <= graph.wal_seq {
+                continue;
+            }
+            graph.current_tick = graph.current_tick.max(entry.tick);
+            graph.apply_event(&entry.event);
+            graph.wal_seq = entry.seq;
+        }
+        graph.config.dynamics.clock = clock;
+        graph.refresh_goals();
+        graph.journal.clear();
+        Ok(graph)
+    }
+}
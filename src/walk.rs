@@ -0,0 +1,95 @@
+//! # Random Walks
+//! Free-association over the memory graph: each step follows an outgoing edge
+//! chosen with probability proportional to `(weight * activation)^(1/T)`.
+//! Low temperature hugs the strongest, most active paths; high temperature
+//! wanders.
+
+use crate::{LoomGraph, NodeId};
+
+/// SplitMix64: tiny, dependency-free PRNG; good enough for sampling.
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn from_entropy() -> Self {
+        let mut bytes = [0u8; 8];
+        let _ = getrandom::getrandom(&mut bytes);
+        Self(u64::from_le_bytes(bytes))
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+impl LoomGraph {
+    pub fn random_walk(&self, start: NodeId, steps: usize, temperature: f32) -> Vec<NodeId> {
+        self.walk_with(start, steps, temperature, &mut SplitMix64::from_entropy())
+    }
+
+    /// Reproducible variant of `random_walk`.
+    pub fn random_walk_seeded(&self, start: NodeId, steps: usize, temperature: f32, seed: u64) -> Vec<NodeId> {
+        self.walk_with(start, steps, temperature, &mut SplitMix64::new(seed))
+    }
+
+    fn walk_with(&self, start: NodeId, steps: usize, temperature: f32, rng: &mut SplitMix64) -> Vec<NodeId> {
+        if !self.nodes.contains_key(&start) {
+            return Vec::new();
+        }
+
+        let mut path = vec![start];
+        let mut current = start;
+        let tick = self.live_tick();
+
+        for _ in 0..steps {
+            let candidates: Vec<(NodeId, f32)> = self.adjacency.get(&current)
+                .into_iter()
+                .flatten()
+                .filter_map(|conn| {
+                    let node = self.nodes.get(&conn.target)?;
+                    let activation = self.projected_activation(node.meta(), tick);
+                    let score = conn.weight.max(0.0) * (0.1 + activation);
+                    (score > 0.0).then_some((conn.target, score))
+                })
+                .collect();
+
+            let next = if candidates.is_empty() {
+                None
+            } else if temperature <= 0.0 {
+                // Temperatura zero: caminho guloso
+                candidates.iter()
+                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                    .map(|(id, _)| *id)
+            } else {
+                let weights: Vec<f32> = candidates.iter().map(|(_, s)| s.powf(1.0 / temperature)).collect();
+                let total: f32 = weights.iter().sum();
+                let mut pick = rng.next_f32() * total;
+                candidates.iter().zip(&weights)
+                    .find(|(_, w)| { pick -= **w; pick < 0.0 })
+                    .or(candidates.last().zip(weights.last()))
+                    .map(|((id, _), _)| *id)
+            };
+
+            match next {
+                Some(id) => {
+                    path.push(id);
+                    current = id;
+                }
+                None => break,
+            }
+        }
+        path
+    }
+}
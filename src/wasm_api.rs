@@ -0,0 +1,1179 @@
+//! # WASM API
+//! JavaScript bindings (feature = "wasm"). IDs cross the JS boundary as
+//! strings and structured results as JSON strings; every wrapper converts and
+//! delegates to the native API, returning `"{}"`/`"[]"`/`false` on bad input.
+
+use crate::config::merge_json;
+use crate::{parse_timestamp, Attachment, ChangeSet, CharEstimator, ChatMessage, Connection, ConsolidationConfig, ContextFormat, DecayOverride, DotOptions, EdgeKind, EntityKind, GoalDue, GraphDiff, IdPolicy, InferenceRule, LoomConfig, LoomGraph, MemoryPack, MergePolicy, MessageGrouping, Node, NodeId, NodeKind, Observer, ObserverId, ScheduledAction, SessionId, SourceOrigin, VectorClock};
+use serde::Deserialize;
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+
+fn parse_id(id_str: &str) -> Option<NodeId> {
+    id_str.parse().ok()
+}
+
+fn ids_json(ids: &[NodeId]) -> String {
+    serde_json::to_string(ids).unwrap_or("[]".to_string())
+}
+
+#[derive(Deserialize)]
+struct ConceptInput {
+    name: String,
+    definition: String,
+}
+
+#[derive(Deserialize)]
+struct EdgeInput {
+    source: NodeId,
+    target: NodeId,
+    weight: f32,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// A JS function called as `callback(event, payloadJson)`.
+    #[wasm_bindgen(typescript_type = "(event: string, payload: string) => void")]
+    pub type ObserverCallback;
+
+    #[wasm_bindgen(method, catch, js_name = call)]
+    fn call2(this: &ObserverCallback, this_arg: &JsValue, event: &str, payload: &str) -> Result<JsValue, JsValue>;
+}
+
+/// Forwards observer events to a JS callback. Exceptions thrown by the
+/// callback are swallowed so they can't interrupt a graph mutation.
+struct JsObserver(ObserverCallback);
+
+// SAFETY: wasm32 roda em uma única thread; o callback nunca cruza threads
+unsafe impl Send for JsObserver {}
+unsafe impl Sync for JsObserver {}
+
+impl JsObserver {
+    fn emit(&self, event: &str, payload: serde_json::Value) {
+        let _ = self.0.call2(&JsValue::NULL, event, &payload.to_string());
+    }
+}
+
+impl Observer for JsObserver {
+    fn on_node_added(&self, node: &Node) {
+        self.emit("node_added", serde_json::to_value(node).unwrap_or_default());
+    }
+
+    fn on_edge_added(&self, source: NodeId, edge: &Connection) {
+        self.emit("edge_added", serde_json::json!({ "source": source, "target": edge.target, "weight": edge.weight, "kind": edge.edge_type }));
+    }
+
+    fn on_boost(&self, id: NodeId, amount: f32, activation: f32) {
+        self.emit("boost", serde_json::json!({ "id": id, "amount": amount, "activation": activation }));
+    }
+
+    fn on_decay_below(&self, id: NodeId, activation: f32) {
+        self.emit("decay_below", serde_json::json!({ "id": id, "activation": activation }));
+    }
+
+    fn on_pruned(&self, ids: &[NodeId]) {
+        self.emit("pruned", serde_json::json!(ids));
+    }
+}
+
+#[wasm_bindgen]
+impl LoomGraph {
+    #[wasm_bindgen(constructor)]
+    pub fn new_js(decay_rate: f32) -> LoomGraph {
+        LoomGraph::new(decay_rate)
+    }
+
+    // --- INGESTÃO DE DADOS ---
+
+    #[wasm_bindgen(js_name = add_concept)]
+    pub fn add_concept_js(&mut self, name: String, definition: String) -> String {
+        self.add_concept(name, definition).to_string()
+    }
+
+    /// Reuses (and boosts) a concept with the same normalized name; returns its id.
+    #[wasm_bindgen(js_name = upsert_concept)]
+    pub fn upsert_concept_js(&mut self, name: String, definition: String) -> String {
+        self.upsert_concept(name, definition).to_string()
+    }
+
+    #[wasm_bindgen(js_name = add_is_a)]
+    pub fn add_is_a_js(&mut self, child_str: &str, parent_str: &str) -> bool {
+        match (parse_id(child_str), parse_id(parent_str)) {
+            (Some(child), Some(parent)) => self.add_is_a(child, parent),
+            _ => false,
+        }
+    }
+
+    #[wasm_bindgen(js_name = add_part_of)]
+    pub fn add_part_of_js(&mut self, part_str: &str, whole_str: &str) -> bool {
+        match (parse_id(part_str), parse_id(whole_str)) {
+            (Some(part), Some(whole)) => self.add_part_of(part, whole),
+            _ => false,
+        }
+    }
+
+    /// Takes an `InferenceRule` as JSON, e.g.
+    /// `{"name":"..","first":"Mentioned","then":"IsA","derive":"Mentioned","weight":0.2}`.
+    #[wasm_bindgen(js_name = register_rule)]
+    pub fn register_rule_js(&mut self, rule_json: &str) -> Result<(), JsError> {
+        let rule: InferenceRule = serde_json::from_str(rule_json).map_err(|e| JsError::new(&e.to_string()))?;
+        self.register_rule(rule);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = remove_rule)]
+    pub fn remove_rule_js(&mut self, name: &str) -> bool {
+        self.remove_rule(name)
+    }
+
+    #[wasm_bindgen(js_name = remove_inferred_edges)]
+    pub fn remove_inferred_edges_js(&mut self) -> usize {
+        self.remove_inferred_edges()
+    }
+
+    /// Ids above the node via IsA/PartOf, nearest first, as a JSON array.
+    #[wasm_bindgen(js_name = ancestors)]
+    pub fn ancestors_js(&self, id_str: &str) -> String {
+        ids_json(&parse_id(id_str).map(|id| self.ancestors(id)).unwrap_or_default())
+    }
+
+    /// Ids below the node via IsA/PartOf, nearest first, as a JSON array.
+    #[wasm_bindgen(js_name = descendants)]
+    pub fn descendants_js(&self, id_str: &str) -> String {
+        ids_json(&parse_id(id_str).map(|id| self.descendants(id)).unwrap_or_default())
+    }
+
+    #[wasm_bindgen(js_name = add_alias)]
+    pub fn add_alias_js(&mut self, id_str: &str, alias: &str) -> bool {
+        parse_id(id_str).is_some_and(|id| self.add_alias(id, alias))
+    }
+
+    #[wasm_bindgen(js_name = remove_alias)]
+    pub fn remove_alias_js(&mut self, id_str: &str, alias: &str) -> bool {
+        parse_id(id_str).is_some_and(|id| self.remove_alias(id, alias))
+    }
+
+    #[wasm_bindgen(js_name = find_concept_by_name)]
+    pub fn find_concept_by_name_js(&self, name: &str) -> Option<String> {
+        self.find_concept_by_name(name).map(|id| id.to_string())
+    }
+
+    /// Groups of same-named concepts/entities as a JSON array of id arrays (oldest first).
+    #[wasm_bindgen(js_name = find_duplicates)]
+    pub fn find_duplicates_js(&self) -> String {
+        serde_json::to_string(&self.find_duplicates()).unwrap_or("[]".to_string())
+    }
+
+    #[wasm_bindgen(js_name = merge_nodes)]
+    pub fn merge_nodes_js(&mut self, keep_str: &str, absorb_str: &str) -> bool {
+        match (parse_id(keep_str), parse_id(absorb_str)) {
+            (Some(keep), Some(absorb)) => self.merge_nodes(keep, absorb),
+            _ => false,
+        }
+    }
+
+    #[wasm_bindgen(js_name = add_episode)]
+    pub fn add_episode_js(&mut self, summary: String) -> String {
+        self.add_episode(summary).to_string()
+    }
+
+    /// `iso_datetime` must carry an offset (RFC 3339); throws if unparseable or in the future.
+    #[wasm_bindgen(js_name = add_episode_at)]
+    pub fn add_episode_at_js(&mut self, summary: String, iso_datetime: &str) -> Result<String, JsError> {
+        self.add_episode_at(summary, iso_datetime)
+            .map(|id| id.to_string())
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Returns `{"id": .., "linked": [concept ids]}`.
+    #[wasm_bindgen(js_name = add_episode_linked)]
+    pub fn add_episode_linked_js(&mut self, summary: String) -> String {
+        let (id, linked) = self.add_episode_linked(summary);
+        serde_json::json!({ "id": id, "linked": linked }).to_string()
+    }
+
+    #[wasm_bindgen(js_name = begin_session)]
+    pub fn begin_session_js(&mut self) -> String {
+        self.begin_session().to_string()
+    }
+
+    #[wasm_bindgen(js_name = end_session)]
+    pub fn end_session_js(&mut self) -> Option<String> {
+        self.end_session().map(|id| id.to_string())
+    }
+
+    /// Episode ids of the session in timeline order, as a JSON array.
+    #[wasm_bindgen(js_name = get_session)]
+    pub fn get_session_js(&self, session_id: &str) -> String {
+        match session_id.parse::<SessionId>() {
+            Ok(id) => ids_json(&self.get_session(id)),
+            Err(_) => "[]".to_string(),
+        }
+    }
+
+    #[wasm_bindgen(js_name = add_state)]
+    pub fn add_state_js(&mut self, valence: f32, arousal: f32) -> String {
+        self.add_state(valence, arousal).to_string()
+    }
+
+    #[wasm_bindgen(js_name = add_observation)]
+    pub fn add_observation_js(&mut self, tool: String, input: String, output: String) -> String {
+        self.add_observation(tool, input, output).to_string()
+    }
+
+    #[wasm_bindgen(js_name = add_goal_at_tick)]
+    pub fn add_goal_at_tick_js(&mut self, description: String, due_tick: u64, priority: f32) -> String {
+        self.add_goal(description, GoalDue::Tick(due_tick), priority).to_string()
+    }
+
+    /// `due_iso`: RFC 3339 (or `YYYY-MM-DD HH:MM[:SS]`, taken as UTC).
+    #[wasm_bindgen(js_name = add_goal_at)]
+    pub fn add_goal_at_js(&mut self, description: String, due_iso: &str, priority: f32) -> Result<String, JsError> {
+        let due = parse_timestamp(due_iso).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(self.add_goal(description, GoalDue::At(due), priority).to_string())
+    }
+
+    /// Ids of open goals past their deadline, most urgent first.
+    #[wasm_bindgen(js_name = due_goals)]
+    pub fn due_goals_js(&self) -> String {
+        serde_json::to_string(&self.due_goals()).unwrap_or("[]".to_string())
+    }
+
+    #[wasm_bindgen(js_name = complete_goal)]
+    pub fn complete_goal_js(&mut self, id_str: &str) -> bool {
+        parse_id(id_str).is_some_and(|id| self.complete_goal(id))
+    }
+
+    /// `attributes_json`: JSON object of string values (e.g. `{"role": "manager"}`).
+    #[wasm_bindgen(js_name = add_entity)]
+    pub fn add_entity_js(&mut self, name: String, kind: EntityKind, attributes_json: &str) -> Result<String, JsError> {
+        let attributes = serde_json::from_str(attributes_json).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(self.add_entity(name, kind, attributes).to_string())
+    }
+
+    #[wasm_bindgen(js_name = find_entity_by_name)]
+    pub fn find_entity_by_name_js(&self, name: &str) -> Option<String> {
+        self.find_entity_by_name(name).map(|id| id.to_string())
+    }
+
+    /// `payload_json`: any JSON value; stored as-is.
+    #[wasm_bindgen(js_name = add_custom)]
+    pub fn add_custom_js(&mut self, type_name: String, payload_json: &str) -> Result<String, JsError> {
+        let payload = serde_json::from_str(payload_json).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(self.add_custom(type_name, payload).to_string())
+    }
+
+    /// Ids of the custom nodes of `type_name`, most active first, as a JSON array.
+    #[wasm_bindgen(js_name = custom_nodes)]
+    pub fn custom_nodes_js(&self, type_name: &str) -> String {
+        ids_json(&self.custom_nodes(type_name))
+    }
+
+    /// `steps_json`: JSON array of strings (may be empty).
+    #[wasm_bindgen(js_name = add_skill)]
+    pub fn add_skill_js(&mut self, name: String, description: String, steps_json: &str) -> Result<String, JsError> {
+        let steps: Vec<String> = serde_json::from_str(steps_json).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(self.add_skill(name, description, steps).to_string())
+    }
+
+    /// Returns the new success rate, or -1 if `id_str` isn't a skill.
+    #[wasm_bindgen(js_name = record_skill_use)]
+    pub fn record_skill_use_js(&mut self, id_str: &str, success: bool) -> f32 {
+        parse_id(id_str).and_then(|id| self.record_skill_use(id, success)).unwrap_or(-1.0)
+    }
+
+    /// Takes a JSON array of summaries; returns the new ids as a JSON array (same order).
+    #[wasm_bindgen(js_name = add_episodes_batch)]
+    pub fn add_episodes_batch_js(&mut self, summaries_json: &str) -> String {
+        match serde_json::from_str::<Vec<String>>(summaries_json) {
+            Ok(summaries) => ids_json(&self.add_episodes_batch(summaries)),
+            Err(_) => "[]".to_string(),
+        }
+    }
+
+    /// Takes `[{"role": .., "content": .., "timestamp"?: ..}, ...]`; returns the `IngestReport` as JSON.
+    #[wasm_bindgen(js_name = ingest_conversation)]
+    pub fn ingest_conversation_js(&mut self, messages_json: &str) -> Result<String, JsError> {
+        let messages: Vec<ChatMessage> = serde_json::from_str(messages_json).map_err(|e| JsError::new(&e.to_string()))?;
+        let report = self.ingest_conversation(&messages);
+        Ok(serde_json::to_string(&report).unwrap_or("{}".to_string()))
+    }
+
+    /// Takes `[{"name": .., "definition": ..}, ...]`; returns the new ids as a JSON array.
+    #[wasm_bindgen(js_name = add_concepts_batch)]
+    pub fn add_concepts_batch_js(&mut self, concepts_json: &str) -> String {
+        match serde_json::from_str::<Vec<ConceptInput>>(concepts_json) {
+            Ok(concepts) => {
+                let pairs = concepts.into_iter().map(|c| (c.name, c.definition)).collect();
+                ids_json(&self.add_concepts_batch(pairs))
+            }
+            Err(_) => "[]".to_string(),
+        }
+    }
+
+    // --- CONEXÕES ---
+
+    /// Takes `[{"source": id, "target": id, "weight": w}, ...]`; returns how many edges were created.
+    #[wasm_bindgen(js_name = connect_batch)]
+    pub fn connect_batch_js(&mut self, edges_json: &str) -> usize {
+        match serde_json::from_str::<Vec<EdgeInput>>(edges_json) {
+            Ok(edges) => {
+                let edges: Vec<_> = edges.into_iter().map(|e| (e.source, e.target, e.weight)).collect();
+                self.connect_batch(&edges)
+            }
+            Err(_) => 0,
+        }
+    }
+
+    #[wasm_bindgen(js_name = connect)]
+    pub fn connect_js(&mut self, source_id: &str, target_id: &str, weight: f32) -> bool {
+        match (parse_id(source_id), parse_id(target_id)) {
+            (Some(source), Some(target)) => self.connect(source, target, weight),
+            _ => false,
+        }
+    }
+
+    // --- BUSCA & RECUPERAÇÃO ---
+
+    #[wasm_bindgen(js_name = search)]
+    pub fn search_js(&self, query: &str) -> String {
+        let results = self.search_native(query);
+        serde_json::to_string(&results).unwrap_or("[]".to_string())
+    }
+
+    #[wasm_bindgen(js_name = search_in_namespace)]
+    pub fn search_in_namespace_js(&self, namespace: &str, query: &str) -> String {
+        serde_json::to_string(&self.search_in_namespace(namespace, query)).unwrap_or("[]".to_string())
+    }
+
+    /// Namespace for nodes created from now on; `undefined` for global.
+    #[wasm_bindgen(js_name = set_active_namespace)]
+    pub fn set_active_namespace_js(&mut self, namespace: Option<String>) {
+        self.set_active_namespace(namespace);
+    }
+
+    #[wasm_bindgen(js_name = set_namespace)]
+    pub fn set_namespace_js(&mut self, id_str: &str, namespace: Option<String>) -> bool {
+        parse_id(id_str).is_some_and(|id| self.set_namespace(id, namespace))
+    }
+
+    /// Namespaces in use as a sorted JSON array.
+    #[wasm_bindgen(js_name = namespaces)]
+    pub fn namespaces_js(&self) -> String {
+        serde_json::to_string(&self.namespaces()).unwrap_or("[]".to_string())
+    }
+
+    #[wasm_bindgen(js_name = get_context_in_namespace)]
+    pub fn get_context_in_namespace_js(&self, namespace: &str, format: ContextFormat, min_activation: f32) -> String {
+        self.get_context_in_namespace(namespace, format, min_activation)
+    }
+
+    #[wasm_bindgen(js_name = prune_namespace)]
+    pub fn prune_namespace_js(&mut self, namespace: &str, threshold: f32) -> usize {
+        self.prune_namespace(namespace, threshold)
+    }
+
+    #[wasm_bindgen(js_name = drop_namespace)]
+    pub fn drop_namespace_js(&mut self, namespace: &str) -> usize {
+        self.drop_namespace(namespace)
+    }
+
+    /// `[episode id, state id]`
+    #[wasm_bindgen(js_name = add_episode_with_emotion)]
+    pub fn add_episode_with_emotion_js(&mut self, summary: String, valence: f32, arousal: f32) -> String {
+        let (episode, state) = self.add_episode_with_emotion(summary, valence, arousal);
+        serde_json::to_string(&[episode, state]).unwrap_or("[]".to_string())
+    }
+
+    /// `{"valence": v, "arousal": a}`, or `null` when no State is active.
+    #[wasm_bindgen(js_name = current_mood)]
+    pub fn current_mood_js(&self) -> String {
+        let mood = self.current_mood().map(|(valence, arousal)| serde_json::json!({ "valence": valence, "arousal": arousal }));
+        serde_json::to_string(&mood).unwrap_or("null".to_string())
+    }
+
+    /// Like `search`, re-ranked toward memories whose emotional tone matches the current mood.
+    #[wasm_bindgen(js_name = search_mood_congruent)]
+    pub fn search_mood_congruent_js(&self, query: &str) -> String {
+        serde_json::to_string(&self.search_mood_congruent(query)).unwrap_or("[]".to_string())
+    }
+
+    /// Like `search`, but counts every hit as an access.
+    #[wasm_bindgen(js_name = recall)]
+    pub fn recall_js(&mut self, query: &str) -> String {
+        serde_json::to_string(&self.recall(query)).unwrap_or("[]".to_string())
+    }
+
+    /// `[[id, access_count], ...]`, highest first.
+    #[wasm_bindgen(js_name = most_accessed)]
+    pub fn most_accessed_js(&self, k: usize) -> String {
+        serde_json::to_string(&self.most_accessed(k)).unwrap_or("[]".to_string())
+    }
+
+    /// Ids, stalest first.
+    #[wasm_bindgen(js_name = least_recently_used)]
+    pub fn least_recently_used_js(&self, k: usize) -> String {
+        serde_json::to_string(&self.least_recently_used(k)).unwrap_or("[]".to_string())
+    }
+
+    #[wasm_bindgen(js_name = undo)]
+    pub fn undo_js(&mut self, n: usize) -> usize {
+        self.undo(n)
+    }
+
+    #[wasm_bindgen(js_name = redo)]
+    pub fn redo_js(&mut self, n: usize) -> usize {
+        self.redo(n)
+    }
+
+    /// `[{tick, events, inverse}, ...]`, oldest first.
+    #[wasm_bindgen(js_name = journal)]
+    pub fn journal_js(&self) -> String {
+        self.journal_json()
+    }
+
+    #[wasm_bindgen(js_name = forget)]
+    pub fn forget_js(&mut self, id_str: &str) -> bool {
+        parse_id(id_str).is_some_and(|id| self.forget(id))
+    }
+
+    #[wasm_bindgen(js_name = undo_forget)]
+    pub fn undo_forget_js(&mut self, id_str: &str) -> bool {
+        parse_id(id_str).is_some_and(|id| self.undo_forget(id))
+    }
+
+    /// `[[id, expiry tick], ...]`, soonest first.
+    #[wasm_bindgen(js_name = forgotten)]
+    pub fn forgotten_js(&self) -> String {
+        serde_json::to_string(&self.forgotten()).unwrap_or("[]".to_string())
+    }
+
+    /// Archived records (`{node, outgoing, incoming, archived_at_tick}`) matching `query`.
+    #[wasm_bindgen(js_name = search_archive)]
+    pub fn search_archive_js(&self, query: &str) -> Result<String, JsError> {
+        let found = self.search_archive(query).map_err(|e| JsError::new(&e.to_string()))?;
+        serde_json::to_string(&found).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = restore)]
+    pub fn restore_js(&mut self, id_str: &str) -> Result<bool, JsError> {
+        let Some(id) = parse_id(id_str) else { return Ok(false) };
+        self.restore(id).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// `{"hits": [[id, score], ...], "truncated": bool}`
+    #[wasm_bindgen(js_name = search_bounded)]
+    pub fn search_bounded_js(&self, query: &str) -> String {
+        let outcome = self.search_with_outcome(query);
+        serde_json::to_string(&outcome).unwrap_or("{}".to_string())
+    }
+
+    #[wasm_bindgen(js_name = search_with_relations)]
+    pub fn search_with_relations_js(&self, query: &str, k: usize) -> String {
+        let results = self.search_with_relations(query, k);
+        serde_json::to_string(&results).unwrap_or("[]".to_string())
+    }
+
+    /// Episode hits with `parent`, `prev`, `next` and the stitched `passage` text.
+    #[wasm_bindgen(js_name = search_chunks)]
+    pub fn search_chunks_js(&self, query: &str, k: usize) -> String {
+        let results: Vec<serde_json::Value> = self.search_chunks(query, k).iter()
+            .map(|hit| {
+                let mut value = serde_json::to_value(hit).unwrap_or_default();
+                value["passage"] = serde_json::json!(self.stitch_passage(hit));
+                value
+            })
+            .collect();
+        serde_json::to_string(&results).unwrap_or("[]".to_string())
+    }
+
+    /// Returns `{"scene": id, "chunks": [ids]}`.
+    #[wasm_bindgen(js_name = add_long_episode)]
+    pub fn add_long_episode_js(&mut self, title: String, text: &str) -> String {
+        let (scene, chunks) = self.add_long_episode(title, text);
+        serde_json::json!({ "scene": scene, "chunks": chunks }).to_string()
+    }
+
+    /// Ego-graph around `center_id` as a backup JSON (importable with `import_backup` or `graft`).
+    #[wasm_bindgen(js_name = subgraph)]
+    pub fn subgraph_js(&self, center_id: &str, radius: usize, min_weight: f32) -> String {
+        match parse_id(center_id) {
+            Some(id) => self.subgraph(id, radius, min_weight).export_backup(),
+            None => "{}".to_string(),
+        }
+    }
+
+    #[wasm_bindgen(js_name = search_observations)]
+    pub fn search_observations_js(&self, query: &str) -> String {
+        let results = self.search_kinds(query, &[NodeKind::Observation]);
+        serde_json::to_string(&results).unwrap_or("[]".to_string())
+    }
+
+    #[wasm_bindgen(js_name = peek_activation)]
+    pub fn peek_activation_js(&self, id_str: &str) -> Option<f32> {
+        parse_id(id_str).and_then(|id| self.peek_activation(id))
+    }
+
+    /// `{"outgoing": [{"id", "kind", "weight"}], "incoming": [...], "in_degree", "out_degree"}`.
+    #[wasm_bindgen(js_name = get_neighbors_json)]
+    pub fn get_neighbors_json_js(&self, id_str: &str) -> String {
+        let Some(id) = parse_id(id_str) else { return "{}".to_string() };
+        let edges = |list: Vec<(NodeId, EdgeKind, f32)>| -> Vec<serde_json::Value> {
+            list.into_iter()
+                .map(|(other, kind, weight)| serde_json::json!({ "id": other, "kind": kind, "weight": weight }))
+                .collect()
+        };
+        let (outgoing, incoming) = (self.neighbors(id), self.incoming(id));
+        serde_json::json!({
+            "out_degree": outgoing.len(),
+            "in_degree": incoming.len(),
+            "outgoing": edges(outgoing),
+            "incoming": edges(incoming),
+        }).to_string()
+    }
+
+    /// Projected activation for the next `ticks_ahead` ticks, as a JSON array.
+    #[wasm_bindgen(js_name = forecast)]
+    pub fn forecast_js(&self, id_str: &str, ticks_ahead: u64) -> String {
+        let values = parse_id(id_str).map(|id| self.forecast(id, ticks_ahead)).unwrap_or_default();
+        serde_json::to_string(&values).unwrap_or("[]".to_string())
+    }
+
+    #[wasm_bindgen(js_name = will_survive)]
+    pub fn will_survive_js(&self, id_str: &str, ticks: u64, threshold: f32) -> bool {
+        parse_id(id_str).is_some_and(|id| self.will_survive(id, ticks, threshold))
+    }
+
+    /// `[[id, projected activation], ...]`, most faded first.
+    #[wasm_bindgen(js_name = at_risk)]
+    pub fn at_risk_js(&self, ticks: u64, threshold: f32) -> String {
+        serde_json::to_string(&self.at_risk(ticks, threshold)).unwrap_or("[]".to_string())
+    }
+
+    #[wasm_bindgen(js_name = importance)]
+    pub fn importance_js(&self, id_str: &str) -> Option<f32> {
+        parse_id(id_str).and_then(|id| self.importance(id))
+    }
+
+    /// `[[id, score], ...]`, highest first.
+    #[wasm_bindgen(js_name = top_important)]
+    pub fn top_important_js(&self, k: usize) -> String {
+        serde_json::to_string(&self.top_important(k)).unwrap_or("[]".to_string())
+    }
+
+    /// `{id: score}`, scores sum to 1.
+    #[wasm_bindgen(js_name = compute_pagerank)]
+    pub fn compute_pagerank_js(&self, damping: f32, iterations: usize) -> String {
+        serde_json::to_string(&self.compute_pagerank(damping, iterations)).unwrap_or("{}".to_string())
+    }
+
+    /// `[[id, score], ...]`, highest first.
+    #[wasm_bindgen(js_name = betweenness_top_k)]
+    pub fn betweenness_top_k_js(&self, k: usize) -> String {
+        serde_json::to_string(&self.betweenness_top_k(k)).unwrap_or("[]".to_string())
+    }
+
+    /// `[[id, due tick], ...]`, most overdue first.
+    #[wasm_bindgen(js_name = due_for_rehearsal)]
+    pub fn due_for_rehearsal_js(&self, now: u64) -> String {
+        serde_json::to_string(&self.due_for_rehearsal(now)).unwrap_or("[]".to_string())
+    }
+
+    /// Returns the next due tick, or undefined if the node is missing or never decays.
+    #[wasm_bindgen(js_name = rehearse)]
+    pub fn rehearse_js(&mut self, id_str: &str) -> Option<u64> {
+        parse_id(id_str).and_then(|id| self.rehearse(id))
+    }
+
+    /// Node JSON plus `"pinned"`, `"community"` (from the last
+    /// `detect_communities`, or null) and, for States, the current emotion `"label"`.
+    #[wasm_bindgen(js_name = get_node_info)]
+    pub fn get_node_info_js(&self, id_str: &str) -> String {
+        let Some((id, node)) = parse_id(id_str).and_then(|id| self.nodes.get(&id).map(|n| (id, n))) else {
+            return "{}".to_string();
+        };
+        let mut value = serde_json::to_value(node).unwrap_or_default();
+        if let Some(object) = value.as_object_mut() {
+            object.insert("pinned".to_string(), serde_json::json!(node.meta().is_pinned()));
+            object.insert("community".to_string(), serde_json::json!(self.community_of(id)));
+            if let Node::State(_, data) = node {
+                object.insert("label".to_string(), serde_json::json!(self.state_label(data)));
+            }
+        }
+        value.to_string()
+    }
+
+    /// `{"<id>": community, ...}`
+    #[wasm_bindgen(js_name = detect_communities)]
+    pub fn detect_communities_js(&mut self) -> String {
+        serde_json::to_string(&self.detect_communities()).unwrap_or("{}".to_string())
+    }
+
+    #[wasm_bindgen(js_name = community_members)]
+    pub fn community_members_js(&self, community: usize) -> String {
+        ids_json(&self.community_members(community))
+    }
+
+    // --- SIMULAÇÃO & TEMPO ---
+
+    /// `config_json`: partial `ConsolidationConfig` (`"{}"` for defaults). Returns the report as JSON.
+    #[wasm_bindgen(js_name = consolidate)]
+    pub fn consolidate_js(&mut self, config_json: &str) -> Result<String, JsError> {
+        let config: ConsolidationConfig = serde_json::from_str(config_json).map_err(|e| JsError::new(&e.to_string()))?;
+        let report = self.consolidate(&config);
+        Ok(serde_json::to_string(&report).unwrap_or("{}".to_string()))
+    }
+
+    /// Episode clusters ready for abstraction: `[{"episodes": [ids], "summaries": [..]}, ...]`.
+    /// Summarize them (e.g. with an async LLM call), then call `add_abstraction`.
+    #[wasm_bindgen(js_name = episode_clusters)]
+    pub fn episode_clusters_js(&self, config_json: &str) -> Result<String, JsError> {
+        let config: ConsolidationConfig = serde_json::from_str(config_json).map_err(|e| JsError::new(&e.to_string()))?;
+        let clusters: Vec<serde_json::Value> = self.episode_clusters(&config).into_iter()
+            .map(|episodes| {
+                let summaries: Vec<&str> = episodes.iter()
+                    .filter_map(|id| match self.nodes.get(id) {
+                        Some(Node::Episode(_, data)) => Some(data.summary.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                serde_json::json!({ "episodes": episodes, "summaries": summaries })
+            })
+            .collect();
+        Ok(serde_json::to_string(&clusters).unwrap_or("[]".to_string()))
+    }
+
+    /// Creates a concept with `Evoked` edges to the episodes in `episode_ids_json`; returns its id.
+    #[wasm_bindgen(js_name = add_abstraction)]
+    pub fn add_abstraction_js(&mut self, name: String, definition: String, episode_ids_json: &str, weight: f32) -> String {
+        let episodes: Vec<NodeId> = serde_json::from_str(episode_ids_json).unwrap_or_default();
+        self.add_abstraction(name, definition, &episodes, weight).to_string()
+    }
+
+    #[wasm_bindgen(js_name = tick)]
+    pub fn tick_js(&mut self) {
+        self.tick();
+    }
+
+    /// Advances `ticks` at once, running due scheduled events; returns how many ran.
+    #[wasm_bindgen(js_name = fast_forward)]
+    pub fn fast_forward_js(&mut self, ticks: u64) -> usize {
+        self.fast_forward(ticks)
+    }
+
+    /// `action_json`: `{"Stimulate": {"id": .., "force": ..}}`, `"Dream"` or `{"Prune": {"threshold": ..}}`.
+    #[wasm_bindgen(js_name = schedule_in)]
+    pub fn schedule_in_js(&mut self, delay: u64, action_json: &str) -> Result<(), JsError> {
+        let action: ScheduledAction = serde_json::from_str(action_json).map_err(|e| JsError::new(&e.to_string()))?;
+        self.schedule_in(delay, action);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = stimulate)]
+    pub fn stimulate_js(&mut self, id_str: &str, force: f32) -> bool {
+        parse_id(id_str).is_some_and(|id| self.stimulate(id, force))
+    }
+
+    /// `{"touched": n, "truncated": bool}`, or `"{}"` for an unknown id.
+    #[wasm_bindgen(js_name = stimulate_report)]
+    pub fn stimulate_report_js(&mut self, id_str: &str, force: f32) -> String {
+        parse_id(id_str)
+            .and_then(|id| self.stimulate_with_report(id, force))
+            .and_then(|report| serde_json::to_string(&report).ok())
+            .unwrap_or("{}".to_string())
+    }
+
+    #[wasm_bindgen(js_name = set_decay_override)]
+    pub fn set_decay_override_js(&mut self, id_str: &str, rate: Option<f32>, never: bool) -> bool {
+        let Some(id) = parse_id(id_str) else { return false };
+        let decay = if never { Some(DecayOverride::Never) } else { rate.map(DecayOverride::Rate) };
+        self.set_decay_override(id, decay)
+    }
+
+    /// Returns -1 if `id_str` isn't a concept or episode.
+    #[wasm_bindgen(js_name = confidence)]
+    pub fn confidence_js(&self, id_str: &str) -> f32 {
+        parse_id(id_str).and_then(|id| self.confidence(id)).unwrap_or(-1.0)
+    }
+
+    #[wasm_bindgen(js_name = set_confidence)]
+    pub fn set_confidence_js(&mut self, id_str: &str, value: f32) -> bool {
+        parse_id(id_str).is_some_and(|id| self.set_confidence(id, value))
+    }
+
+    /// Returns the new score, or -1 if `id_str` isn't a concept or episode.
+    #[wasm_bindgen(js_name = raise_confidence)]
+    pub fn raise_confidence_js(&mut self, id_str: &str) -> f32 {
+        parse_id(id_str).and_then(|id| self.raise_confidence(id)).unwrap_or(-1.0)
+    }
+
+    /// Returns the new score, or -1 if `id_str` isn't a concept or episode.
+    #[wasm_bindgen(js_name = lower_confidence)]
+    pub fn lower_confidence_js(&mut self, id_str: &str) -> f32 {
+        parse_id(id_str).and_then(|id| self.lower_confidence(id)).unwrap_or(-1.0)
+    }
+
+    #[wasm_bindgen(js_name = mark_exclusive)]
+    pub fn mark_exclusive_js(&mut self, a: &str, b: &str) -> bool {
+        self.mark_exclusive(a, b)
+    }
+
+    /// Returns the conflicting pairs as a JSON array of `[id, id]`.
+    #[wasm_bindgen(js_name = find_contradictions)]
+    pub fn find_contradictions_js(&mut self) -> String {
+        let pairs: Vec<[String; 2]> = self.find_contradictions().into_iter()
+            .map(|(a, b)| [a.to_string(), b.to_string()])
+            .collect();
+        serde_json::to_string(&pairs).unwrap_or_default()
+    }
+
+    /// Returns false if `id_str` isn't an episode.
+    #[wasm_bindgen(js_name = attach)]
+    pub fn attach_js(&mut self, id_str: &str, uri: String, mime_type: String, hash: Option<String>) -> bool {
+        let attachment = Attachment { uri, mime_type, hash };
+        parse_id(id_str).is_some_and(|id| self.attach(id, attachment))
+    }
+
+    #[wasm_bindgen(js_name = detach)]
+    pub fn detach_js(&mut self, id_str: &str, uri: &str) -> bool {
+        parse_id(id_str).is_some_and(|id| self.detach(id, uri))
+    }
+
+    /// `[{"uri", "mime_type", "hash"}, ...]`
+    #[wasm_bindgen(js_name = attachments)]
+    pub fn attachments_js(&self, id_str: &str) -> String {
+        let attachments = parse_id(id_str).map(|id| self.attachments(id)).unwrap_or_default();
+        serde_json::to_string(attachments).unwrap_or("[]".to_string())
+    }
+
+    #[wasm_bindgen(js_name = episodes_with_attachments)]
+    pub fn episodes_with_attachments_js(&self, mime_prefix: &str) -> String {
+        ids_json(&self.episodes_with_attachments(mime_prefix))
+    }
+
+    #[wasm_bindgen(js_name = set_source)]
+    pub fn set_source_js(&mut self, id_str: &str, origin: SourceOrigin, reference: String) -> bool {
+        parse_id(id_str).is_some_and(|id| self.set_source(id, origin, reference))
+    }
+
+    /// `{"origin", "reference", "created_at"}`, or undefined if the node has no source.
+    #[wasm_bindgen(js_name = get_source)]
+    pub fn get_source_js(&self, id_str: &str) -> Option<String> {
+        let source = parse_id(id_str).and_then(|id| self.source(id))?;
+        serde_json::to_string(source).ok()
+    }
+
+    #[wasm_bindgen(js_name = nodes_from)]
+    pub fn nodes_from_js(&self, origin: SourceOrigin) -> String {
+        ids_json(&self.nodes_from(origin))
+    }
+
+    #[wasm_bindgen(js_name = set_tag)]
+    pub fn set_tag_js(&mut self, id_str: &str, key: String, value: String) -> bool {
+        parse_id(id_str).is_some_and(|id| self.set_tag(id, key, value))
+    }
+
+    #[wasm_bindgen(js_name = get_tag)]
+    pub fn get_tag_js(&self, id_str: &str, key: &str) -> Option<String> {
+        parse_id(id_str).and_then(|id| self.get_tag(id, key)).map(str::to_string)
+    }
+
+    #[wasm_bindgen(js_name = remove_tag)]
+    pub fn remove_tag_js(&mut self, id_str: &str, key: &str) -> Option<String> {
+        parse_id(id_str).and_then(|id| self.remove_tag(id, key))
+    }
+
+    /// Ids tagged `key=value`, most active first, as a JSON array.
+    #[wasm_bindgen(js_name = search_by_tag)]
+    pub fn search_by_tag_js(&self, key: &str, value: &str) -> String {
+        ids_json(&self.search_by_tag(key, value))
+    }
+
+    #[wasm_bindgen(js_name = pin)]
+    pub fn pin_js(&mut self, id_str: &str) -> bool {
+        parse_id(id_str).is_some_and(|id| self.pin(id))
+    }
+
+    #[wasm_bindgen(js_name = unpin)]
+    pub fn unpin_js(&mut self, id_str: &str) -> bool {
+        parse_id(id_str).is_some_and(|id| self.unpin(id))
+    }
+
+    #[wasm_bindgen(js_name = is_pinned)]
+    pub fn is_pinned_js(&self, id_str: &str) -> bool {
+        parse_id(id_str).is_some_and(|id| self.is_pinned(id))
+    }
+
+    #[wasm_bindgen(js_name = set_always_in_context)]
+    pub fn set_always_in_context_js(&mut self, id_str: &str, pinned: bool) -> bool {
+        parse_id(id_str).is_some_and(|id| self.set_always_in_context(id, pinned))
+    }
+
+    #[wasm_bindgen(js_name = random_walk)]
+    pub fn random_walk_js(&self, start_id: &str, steps: usize, temperature: f32) -> String {
+        let path = match parse_id(start_id) {
+            Some(id) => self.random_walk(id, steps, temperature),
+            None => Vec::new(),
+        };
+        serde_json::to_string(&path).unwrap_or("[]".to_string())
+    }
+
+    /// Returns the `WakeReport` as JSON.
+    #[wasm_bindgen(js_name = wake_up)]
+    pub fn wake_up_js(&mut self) -> String {
+        serde_json::to_string(&self.wake_up()).unwrap_or("{}".to_string())
+    }
+
+    /// Real-time clock catch-up; returns the ticks advanced.
+    #[wasm_bindgen(js_name = catch_up_clock)]
+    pub fn catch_up_clock_js(&mut self) -> u64 {
+        self.catch_up_clock()
+    }
+
+    #[wasm_bindgen(js_name = dream)]
+    pub fn dream_js(&mut self) -> String {
+        self.dream()
+    }
+
+    #[wasm_bindgen(js_name = prune_low_stability)]
+    pub fn prune_low_stability_js(&mut self, threshold: f32) -> usize {
+        self.prune_low_stability(threshold)
+    }
+
+    /// Takes a `StabilityPolicy` as JSON, e.g. `{"Degree":{"base":1.0,"per_edge":0.5}}`.
+    /// Returns the number of recalibrated nodes, or -1 on a malformed policy.
+    #[wasm_bindgen(js_name = recalibrate_stability)]
+    pub fn recalibrate_stability_js(&mut self, policy_json: &str) -> i32 {
+        match serde_json::from_str(policy_json) {
+            Ok(policy) => self.recalibrate_stability(policy) as i32,
+            Err(_) => -1,
+        }
+    }
+
+    /// Rolling latency percentiles and the slow-op log, as JSON.
+    #[wasm_bindgen(js_name = perf_stats)]
+    pub fn perf_stats_js(&self) -> String {
+        serde_json::to_string(&self.perf_stats()).unwrap_or("{}".to_string())
+    }
+
+    #[wasm_bindgen(getter, js_name = current_tick)]
+    pub fn current_tick_js(&self) -> u64 {
+        self.current_tick
+    }
+
+    #[wasm_bindgen(getter, js_name = live_tick)]
+    pub fn live_tick_js(&self) -> u64 {
+        self.live_tick()
+    }
+
+    #[wasm_bindgen(getter, js_name = decay_rate)]
+    pub fn decay_rate_js(&self) -> f32 {
+        self.decay_rate
+    }
+
+    /// Throws outside `0 < rate <= 1`; pending decay is settled at the old rate.
+    #[wasm_bindgen(setter, js_name = decay_rate)]
+    pub fn set_decay_rate_js(&mut self, rate: f32) -> Result<(), JsError> {
+        self.set_decay_rate(rate).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    #[wasm_bindgen(getter, js_name = node_count)]
+    pub fn node_count_js(&self) -> usize {
+        self.node_count()
+    }
+
+    #[wasm_bindgen(getter, js_name = edge_count)]
+    pub fn edge_count_js(&self) -> usize {
+        self.edge_count()
+    }
+
+    /// Alias of `config()`: every tunable, `decay_rate` included, as JSON.
+    #[wasm_bindgen(js_name = get_config_json)]
+    pub fn get_config_json_js(&self) -> String {
+        self.config_js()
+    }
+
+    /// Current `LoomConfig` as JSON (including the graph-wide `decay_rate`).
+    #[wasm_bindgen(js_name = config)]
+    pub fn config_js(&self) -> String {
+        serde_json::to_string(&self.config()).unwrap_or("{}".to_string())
+    }
+
+    /// Accepts a full or partial `LoomConfig` as JSON, merged over the
+    /// current one. A `decay_rate` key changes the decay rate too; changing a
+    /// creation-time field (`capacity`, `archive_tier`, `journal_capacity`,
+    /// `dynamics.clock`) throws.
+    #[wasm_bindgen(js_name = set_config)]
+    pub fn set_config_js(&mut self, config_json: &str) -> Result<(), JsError> {
+        let patch: serde_json::Value = serde_json::from_str(config_json).map_err(|e| JsError::new(&e.to_string()))?;
+        let mut merged = serde_json::to_value(self.config()).unwrap_or_default();
+        merge_json(&mut merged, &patch);
+        let config: LoomConfig = serde_json::from_value(merged).map_err(|e| JsError::new(&e.to_string()))?;
+        self.set_config(config).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// `HealthReport` as JSON; each recommendation is `{"action": .., ...}`.
+    #[wasm_bindgen(js_name = health_report)]
+    pub fn health_report_js(&self) -> String {
+        serde_json::to_string(&self.health_report()).unwrap_or("{}".to_string())
+    }
+
+    /// Integrity issues as a JSON array (empty when the graph is consistent).
+    #[wasm_bindgen(js_name = validate)]
+    pub fn validate_js(&self) -> String {
+        serde_json::to_string(&self.validate()).unwrap_or("[]".to_string())
+    }
+
+    /// `{"fixed": [...], "remaining": [...]}`
+    #[wasm_bindgen(js_name = repair)]
+    pub fn repair_js(&mut self) -> String {
+        serde_json::to_string(&self.repair()).unwrap_or("{}".to_string())
+    }
+
+    #[wasm_bindgen(js_name = rebuild_index)]
+    pub fn rebuild_index_js(&mut self) {
+        self.rebuild_index();
+    }
+
+    /// Registers `callback(event, payloadJson)` for `node_added`, `edge_added`,
+    /// `boost`, `decay_below` and `pruned`. Returns an id for `remove_observer`.
+    #[wasm_bindgen(js_name = add_observer)]
+    pub fn add_observer_js(&mut self, callback: ObserverCallback) -> f64 {
+        self.add_observer(Arc::new(JsObserver(callback))).0 as f64
+    }
+
+    #[wasm_bindgen(js_name = remove_observer)]
+    pub fn remove_observer_js(&mut self, id: f64) -> bool {
+        self.remove_observer(ObserverId(id as u64))
+    }
+
+    /// `ids_json`: JSON array of ids (empty array = every tracked node).
+    #[wasm_bindgen(js_name = history_json)]
+    pub fn history_json_js(&self, ids_json: &str) -> String {
+        let ids: Vec<NodeId> = serde_json::from_str(ids_json).unwrap_or_default();
+        self.history_json(&ids)
+    }
+
+    #[wasm_bindgen(js_name = history_csv)]
+    pub fn history_csv_js(&self, ids_json: &str) -> String {
+        let ids: Vec<NodeId> = serde_json::from_str(ids_json).unwrap_or_default();
+        self.history_csv(&ids)
+    }
+
+    // --- EXPORT/IMPORT ---
+
+    #[wasm_bindgen(js_name = export_backup)]
+    pub fn export_backup_js(&self) -> String {
+        self.export_backup()
+    }
+
+    /// Throws on a corrupt or unsupported backup instead of starting empty.
+    #[wasm_bindgen]
+    pub fn import_backup(json: &str) -> Result<LoomGraph, JsError> {
+        LoomGraph::from_backup_json(json).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Deprecated: same as `import_backup`, which now throws too.
+    #[wasm_bindgen]
+    pub fn import_backup_checked(json: &str) -> Result<LoomGraph, JsError> {
+        LoomGraph::import_backup(json)
+    }
+
+    /// Backup with concept names, emails, URLs and configured patterns pseudonymized.
+    /// `options_json`: partial `DotOptions` (`"{}"` for defaults).
+    #[wasm_bindgen(js_name = export_dot)]
+    pub fn export_dot_js(&self, options_json: &str) -> Result<String, JsError> {
+        let options: DotOptions = serde_json::from_str(options_json).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(self.export_dot(&options))
+    }
+
+    /// `{"nodes": [{id, label, group, value}], "edges": [{from, to, weight, kind}]}`,
+    /// ready for `new vis.Network(container, JSON.parse(json), options)`.
+    #[wasm_bindgen(js_name = export_vis_json)]
+    pub fn export_vis_json_js(&self) -> String {
+        self.export_vis_json()
+    }
+
+    /// One `{"node", "edges"}` JSON record per line.
+    #[wasm_bindgen(js_name = export_nodes_jsonl)]
+    pub fn export_nodes_jsonl_js(&self) -> String {
+        let mut out = Vec::new();
+        let _ = self.export_nodes_jsonl(&mut out);
+        String::from_utf8(out).unwrap_or_default()
+    }
+
+    /// Returns `{"inserted", "edges_inserted", "id_map", "collisions"}`.
+    #[wasm_bindgen(js_name = import_nodes_jsonl)]
+    pub fn import_nodes_jsonl_js(&mut self, jsonl: &str) -> Result<String, JsError> {
+        let report = self.import_nodes_jsonl(jsonl.as_bytes()).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(serde_json::to_string(&report).unwrap_or("{}".to_string()))
+    }
+
+    /// `name,definition` rows; returns the new ids as a JSON array.
+    #[wasm_bindgen(js_name = import_concepts_csv)]
+    pub fn import_concepts_csv_js(&mut self, csv: &str) -> Result<String, JsError> {
+        let ids = self.import_concepts_csv(csv.as_bytes()).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(ids_json(&ids))
+    }
+
+    /// `timestamp,summary` rows; returns the new ids as a JSON array.
+    #[wasm_bindgen(js_name = import_episodes_csv)]
+    pub fn import_episodes_csv_js(&mut self, csv: &str) -> Result<String, JsError> {
+        let ids = self.import_episodes_csv(csv.as_bytes()).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(ids_json(&ids))
+    }
+
+    #[wasm_bindgen(js_name = export_mermaid)]
+    pub fn export_mermaid_js(&self, center_str: &str, radius: usize) -> Option<String> {
+        parse_id(center_str).and_then(|center| self.export_mermaid(center, radius))
+    }
+
+    #[wasm_bindgen(js_name = export_graphml)]
+    pub fn export_graphml_js(&self) -> String {
+        self.export_graphml()
+    }
+
+    #[wasm_bindgen(js_name = export_gexf)]
+    pub fn export_gexf_js(&self) -> String {
+        self.export_gexf()
+    }
+
+    #[wasm_bindgen(js_name = export_anonymized)]
+    pub fn export_anonymized_js(&self, salt: &str) -> Result<String, JsError> {
+        self.export_anonymized(salt).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    #[wasm_bindgen]
+    pub fn export_backup_binary(&self) -> Vec<u8> {
+        self.to_binary().unwrap_or_default()
+    }
+
+    #[wasm_bindgen]
+    pub fn import_backup_binary(bytes: &[u8]) -> Result<LoomGraph, JsError> {
+        LoomGraph::from_binary(bytes).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = export_backup_compressed)]
+    pub fn export_backup_compressed_js(&self) -> Vec<u8> {
+        self.export_backup_compressed().unwrap_or_default()
+    }
+
+    #[wasm_bindgen(js_name = import_backup_compressed)]
+    pub fn import_backup_compressed_js(bytes: &[u8]) -> Result<LoomGraph, JsError> {
+        LoomGraph::import_backup_compressed(bytes).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = graft)]
+    pub fn graft_js(&mut self, subgraph_json: &str, namespace: &str, regenerate_ids: bool) -> String {
+        let subgraph: LoomGraph = match serde_json::from_str(subgraph_json) {
+            Ok(g) => g,
+            Err(_) => return "{}".to_string(),
+        };
+        let policy = if regenerate_ids { IdPolicy::Regenerate } else { IdPolicy::Preserve };
+        let report = self.graft(subgraph, namespace, policy);
+        serde_json::to_string(&report).unwrap_or("{}".to_string())
+    }
+
+    /// `other_json`: a graph as produced by `export_backup`; returns the `MergeReport` as JSON.
+    #[wasm_bindgen(js_name = merge)]
+    pub fn merge_js(&mut self, other_json: &str, policy: MergePolicy) -> Result<String, JsError> {
+        let other: LoomGraph = serde_json::from_str(other_json).map_err(|e| JsError::new(&e.to_string()))?;
+        let report = self.merge(other, policy);
+        Ok(serde_json::to_string(&report).unwrap_or("{}".to_string()))
+    }
+
+    #[wasm_bindgen(js_name = clone_graph)]
+    pub fn clone_graph_js(&self) -> LoomGraph {
+        self.clone_graph()
+    }
+
+    /// `other_json`: a graph as produced by `export_backup`; returns the `GraphDiff` as JSON.
+    #[wasm_bindgen(js_name = diff)]
+    pub fn diff_js(&self, other_json: &str) -> Result<String, JsError> {
+        let other: LoomGraph = serde_json::from_str(other_json).map_err(|e| JsError::new(&e.to_string()))?;
+        serde_json::to_string(&self.diff(&other)).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Returns how many entries of the diff were applied.
+    #[wasm_bindgen(js_name = apply_diff)]
+    pub fn apply_diff_js(&mut self, diff_json: &str) -> Result<usize, JsError> {
+        let diff: GraphDiff = serde_json::from_str(diff_json).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(self.apply_diff(diff))
+    }
+
+    /// This replica's vector clock as JSON; send it to the peer that will call `changes_since`.
+    #[wasm_bindgen(js_name = sync_clock)]
+    pub fn sync_clock_js(&mut self) -> String {
+        serde_json::to_string(&self.sync_clock()).unwrap_or("{}".to_string())
+    }
+
+    /// `clock_json`: the peer's `sync_clock()`; returns the `ChangeSet` as JSON.
+    #[wasm_bindgen(js_name = changes_since)]
+    pub fn changes_since_js(&mut self, clock_json: &str) -> Result<String, JsError> {
+        let since: VectorClock = serde_json::from_str(clock_json).map_err(|e| JsError::new(&e.to_string()))?;
+        serde_json::to_string(&self.changes_since(&since)).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Applies a peer's `changes_since` output; returns the `SyncReport` as JSON.
+    #[wasm_bindgen(js_name = apply_changes)]
+    pub fn apply_changes_js(&mut self, changes_json: &str) -> Result<String, JsError> {
+        let changes: ChangeSet = serde_json::from_str(changes_json).map_err(|e| JsError::new(&e.to_string()))?;
+        let report = self.apply_changes(changes);
+        Ok(serde_json::to_string(&report).unwrap_or("{}".to_string()))
+    }
+
+    #[wasm_bindgen]
+    pub fn install_pack_json(&mut self, pack_json: &str) -> String {
+        let result = MemoryPack::from_json(pack_json)
+            .map_err(|e| e.to_string())
+            .and_then(|pack| self.install_pack(&pack).map_err(|e| e.to_string()));
+        match result {
+            Ok(report) => serde_json::to_string(&report).unwrap_or("{}".to_string()),
+            Err(e) => serde_json::json!({ "error": e }).to_string(),
+        }
+    }
+
+    // --- CONTEXTO ---
+
+    #[wasm_bindgen]
+    pub fn get_context(&self, min_activation: f32) -> String {
+        self.get_context_prompt(min_activation)
+    }
+
+    #[wasm_bindgen(js_name = get_context_as)]
+    pub fn get_context_as_js(&self, format: ContextFormat, min_activation: f32) -> String {
+        self.get_context_as(format, min_activation)
+    }
+
+    /// `[{"role": "system", "content": ...}, ...]`
+    #[wasm_bindgen(js_name = get_context_messages)]
+    pub fn get_context_messages_js(&self, min_activation: f32, grouping: MessageGrouping) -> String {
+        let messages = self.get_context_messages_with(min_activation, grouping);
+        serde_json::to_string(&messages).unwrap_or("[]".to_string())
+    }
+
+    #[wasm_bindgen(js_name = get_context_budgeted)]
+    pub fn get_context_budgeted_js(&self, min_activation: f32, max_tokens: usize) -> String {
+        self.get_context_budgeted_with(min_activation, max_tokens, &CharEstimator::default())
+    }
+
+    // --- TIME TRAVEL ---
+
+    #[wasm_bindgen(js_name = enable_event_log)]
+    pub fn enable_event_log_js(&mut self) {
+        self.enable_event_log();
+    }
+
+    #[wasm_bindgen(js_name = reconstruct_at)]
+    pub fn reconstruct_at_js(&self, tick: u64) -> Option<LoomGraph> {
+        self.reconstruct_at(tick)
+    }
+
+    #[wasm_bindgen(js_name = compare)]
+    pub fn compare_js(&self, tick_a: u64, tick_b: u64) -> String {
+        match self.compare(tick_a, tick_b) {
+            Some(cmp) => serde_json::to_string(&cmp).unwrap_or("{}".to_string()),
+            None => "{}".to_string(),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use loom_db::{EvictionPolicy, LoomGraph};
+
+#[test]
+fn batch_inserts_are_never_their_own_eviction_victims() {
+    let mut graph = LoomGraph::with_capacity(3, EvictionPolicy::LowestImportance).unwrap().with_seed(71);
+    let old: Vec<_> = ["Rust", "WASM", "Tokio"].iter()
+        .map(|name| graph.add_concept(name.to_string(), "Well known".into()))
+        .collect();
+    for _ in 0..5 {
+        for id in &old {
+            graph.stimulate(*id, 0.9);
+        }
+    }
+
+    let batch = graph.add_concepts_batch(vec![("Zig".into(), "New".into()), ("Gleam".into(), "New".into())]);
+
+    assert_eq!(graph.node_count(), 3);
+    assert!(batch.iter().all(|id| graph.get_node(*id).is_some()));
+}
+
+#[test]
+fn zero_capacity_is_rejected() {
+    assert!(matches!(
+        LoomGraph::with_capacity(0, EvictionPolicy::Lru),
+        Err(loom_db::ConfigError::OutOfRange { field: "capacity.max_nodes", .. })
+    ));
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Duration, Utc};
+use loom_db::{ClockMode, GoalDue, LoomConfig, LoomGraph, ManualClock, ScheduledAction, ScheduledEvent};
+use std::sync::Arc;
+
+fn real_time(seconds_per_tick: u64) -> (LoomGraph, Arc<ManualClock>) {
+    let config = LoomConfig::builder().seconds_per_tick(seconds_per_tick).clock(ClockMode::RealTime).build().unwrap();
+    let mut graph = LoomGraph::with_config(config).unwrap().with_seed(9);
+    let clock = Arc::new(ManualClock::new(DateTime::<Utc>::UNIX_EPOCH));
+    graph.set_time_source(clock.clone());
+    (graph, clock)
+}
+
+#[test]
+fn reads_project_to_the_live_tick() {
+    let (mut graph, clock) = real_time(60);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let fresh = graph.search_native("rust")[0].1;
+
+    clock.advance(Duration::minutes(30));
+
+    assert_eq!(graph.current_tick, 0);
+    assert_eq!(graph.live_tick(), 30);
+    let later = graph.search_native("rust")[0].1;
+    assert!(later < fresh);
+    assert_eq!(Some(later), graph.peek_activation(rust));
+    assert!(graph.forecast(rust, 1)[0] < later);
+    assert!(!graph.will_survive(rust, 0, fresh));
+}
+
+#[test]
+fn datetime_deadlines_use_the_tick_duration() {
+    let (mut graph, _) = real_time(3600);
+    let due = graph.now() + Duration::hours(5);
+    let goal = graph.add_goal("Ship the release".into(), GoalDue::At(due), 1.0);
+
+    let Some(loom_db::Node::Goal(_, data)) = graph.get_node(goal) else { panic!("not a goal") };
+    assert_eq!(graph.ticks_until_due(data), 5);
+}
+
+#[test]
+fn clock_mode_is_fixed_at_creation() {
+    let (mut graph, _) = real_time(60);
+    let mut config = graph.config();
+    config.dynamics.clock = ClockMode::Ticks;
+    assert_eq!(graph.set_config(config), Err(loom_db::ConfigError::Immutable("dynamics.clock")));
+
+    let mut config = graph.config();
+    config.journal_capacity = 4;
+    assert_eq!(graph.set_config(config), Err(loom_db::ConfigError::Immutable("journal_capacity")));
+
+    let mut config = graph.config();
+    config.pin_floor = 0.2;
+    assert!(graph.set_config(config).is_ok());
+}
+
+#[test]
+fn decay_override_rejects_rates_outside_the_unit_interval() {
+    let mut graph = LoomGraph::new(0.95).with_seed(10);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    for rate in [0.0, -0.5, 1.5, f32::NAN, f32::INFINITY] {
+        assert!(!graph.set_decay_override(rust, Some(loom_db::DecayOverride::Rate(rate))));
+    }
+    assert_eq!(graph.get_node(rust).unwrap().meta().decay_override, None);
+    assert!(graph.set_decay_override(rust, Some(loom_db::DecayOverride::Rate(1.0))));
+}
+
+#[test]
+fn wake_up_reports_what_came_due() {
+    let config = LoomConfig::builder().seconds_per_tick(60).build().unwrap();
+    let mut graph = LoomGraph::with_config(config).unwrap().with_seed(12);
+    let clock = Arc::new(ManualClock::new(DateTime::<Utc>::UNIX_EPOCH));
+    graph.set_time_source(clock.clone());
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let ship = graph.add_goal("Ship the release".into(), GoalDue::Tick(20), 1.0);
+    graph.add_goal("Write the changelog".into(), GoalDue::Tick(500), 1.0);
+    graph.schedule_at(10, ScheduledAction::Stimulate { id: rust, force: 0.5 });
+    graph.schedule_at(400, ScheduledAction::Dream);
+    graph.last_saved = Some(graph.now());
+
+    clock.advance(Duration::hours(1));
+    let report = graph.wake_up();
+
+    assert_eq!(report.ticks_elapsed, 60);
+    assert_eq!(report.due.goals, vec![ship]);
+    let fired = ScheduledEvent { at_tick: 10, action: ScheduledAction::Stimulate { id: rust, force: 0.5 } };
+    assert_eq!(report.due.scheduled, vec![fired]);
+}
@@ -0,0 +1,30 @@
+use loom_db::{CharEstimator, ContextFormat, LoomGraph, SortStrategy, TokenEstimator};
+
+#[test]
+fn context_selection_uses_projected_activation() {
+    let mut graph = LoomGraph::new(0.9).with_seed(91);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let wasm = graph.add_concept("WASM".into(), "Portable bytecode".into());
+    graph.fast_forward(30);
+    graph.stimulate(wasm, 0.8);
+
+    let selected: Vec<_> = graph.context_builder().min_activation(0.3).select().iter().map(|n| n.meta().id).collect();
+    assert_eq!(selected, vec![wasm]);
+    assert!(!graph.get_context_as(ContextFormat::Markdown, 0.3).contains("Rust"));
+
+    let ranked: Vec<_> = graph.context_builder().sort_by(SortStrategy::Activation).select().iter().map(|n| n.meta().id).collect();
+    assert_eq!(ranked, vec![wasm, rust]);
+}
+
+#[test]
+fn budgeted_context_never_exceeds_the_budget() {
+    let mut graph = LoomGraph::new(0.95).with_seed(92);
+    graph.add_concept("Rust".into(), "Systems language".into());
+    let estimator = CharEstimator::default();
+
+    assert_eq!(graph.get_context_budgeted(0.0, 3), "");
+    for budget in 0..80 {
+        let context = graph.get_context_budgeted(0.0, budget);
+        assert!(estimator.estimate(&context) <= budget, "{} tokens over a budget of {}", estimator.estimate(&context), budget);
+    }
+}
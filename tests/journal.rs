@@ -0,0 +1,33 @@
+use loom_db::{LoomGraph, WalOptions};
+
+fn snapshot(graph: &LoomGraph) -> String {
+    let mut nodes: Vec<String> = graph.nodes.values().map(|n| serde_json::to_string(n).unwrap()).collect();
+    nodes.sort();
+    format!("{} {:?} {}", graph.current_tick, nodes, graph.edge_count())
+}
+
+#[test]
+fn undo_and_redo_survive_wal_recovery() {
+    let dir = std::env::temp_dir().join(format!("loom-journal-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (wal, snap) = (dir.join("undo.wal"), dir.join("undo.snap"));
+    let (wal, snap) = (wal.to_str().unwrap(), snap.to_str().unwrap());
+
+    let mut graph = LoomGraph::new(0.95).with_seed(11);
+    graph.attach_wal(wal, WalOptions { snapshot_path: snap.to_string(), compact_every: None }).unwrap();
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let wasm = graph.add_concept("WASM".into(), "Portable bytecode".into());
+    graph.connect(rust, wasm, 0.7);
+    graph.tick();
+    graph.stimulate(rust, 0.5);
+
+    assert_eq!(graph.undo(3), 3); // boost, connect, add WASM
+    assert_eq!(graph.redo(1), 1); // add WASM again
+    assert!(graph.dirty > 0);
+
+    let recovered = LoomGraph::recover(snap, wal).unwrap();
+    assert_eq!(snapshot(&recovered), snapshot(&graph));
+    assert_eq!(recovered.edge_count(), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,25 @@
+use loom_db::{ArchivedNode, LoomGraph, MergePolicy};
+
+#[test]
+fn merge_does_not_revive_forgotten_or_archived_nodes() {
+    let mut laptop = LoomGraph::new(0.95).with_seed(61);
+    let rust = laptop.add_concept("Rust".into(), "Systems language".into());
+    laptop.sync_clock();
+
+    let mut phone = laptop.clone_graph();
+    let scratch = phone.add_episode("Scratch note".into());
+    phone.connect(scratch, rust, 0.5);
+    phone.forget(scratch);
+    phone.forget(rust);
+    let old = phone.add_concept("Perl".into(), "Old scripting".into());
+    let node = phone.nodes.remove(&old).unwrap();
+    phone.archived.insert(old, ArchivedNode { node, outgoing: Vec::new(), incoming: Vec::new(), archived_at_tick: 0 });
+
+    let report = laptop.merge(phone, MergePolicy::KeepMax);
+
+    assert_eq!(report.inserted, 0);
+    assert!(laptop.get_node(scratch).is_none());
+    assert!(laptop.is_forgotten(rust));
+    assert!(laptop.get_node(old).is_none());
+    assert_eq!(laptop.search_archive("perl").unwrap().len(), 1);
+}
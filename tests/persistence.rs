@@ -0,0 +1,39 @@
+use loom_db::LoomGraph;
+
+fn assert_stable(graph: &LoomGraph) {
+    let backup = graph.export_backup();
+    for _ in 0..4 {
+        assert_eq!(LoomGraph::from_backup_json(&backup).unwrap().export_backup(), backup);
+    }
+}
+
+#[test]
+fn tagged_nodes_back_up_byte_identically() {
+    let mut graph = LoomGraph::new(0.95).with_seed(81);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    for (key, value) in [("topic", "lang"), ("source", "chat"), ("owner", "me"), ("lang", "en"), ("team", "core")] {
+        graph.set_tag(rust, key.into(), value.into());
+    }
+    assert_stable(&graph);
+}
+
+#[test]
+fn forgotten_nodes_back_up_byte_identically() {
+    let mut graph = LoomGraph::new(0.95).with_seed(82);
+    for name in ["Rust", "WASM", "Tokio", "Serde", "Axum", "Rayon"] {
+        let id = graph.add_concept(name.into(), "Crate".into());
+        graph.forget(id);
+    }
+    assert_stable(&graph);
+}
+
+#[test]
+fn archived_nodes_back_up_byte_identically() {
+    let mut graph = LoomGraph::new(0.95).with_seed(83);
+    for name in ["Rust", "WASM", "Tokio", "Serde", "Axum", "Rayon"] {
+        let id = graph.add_concept(name.into(), "Crate".into());
+        let node = graph.nodes.remove(&id).unwrap();
+        graph.archived.insert(id, loom_db::ArchivedNode { node, outgoing: Vec::new(), incoming: Vec::new(), archived_at_tick: 0 });
+    }
+    assert_stable(&graph);
+}
@@ -0,0 +1,61 @@
+use loom_db::{LoomGraph, NodeId, ScheduledAction};
+
+fn seeded() -> (LoomGraph, NodeId, NodeId) {
+    let mut graph = LoomGraph::new(0.95).with_seed(7);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let wasm = graph.add_concept("WASM".into(), "Portable bytecode".into());
+    graph.connect(rust, wasm, 0.8);
+    graph.add_episode("Compiled the crate to WASM".into());
+    (graph, rust, wasm)
+}
+
+fn state(graph: &LoomGraph) -> Vec<(NodeId, f32, f32)> {
+    let mut nodes: Vec<(NodeId, f32, f32)> = graph.nodes.values()
+        .map(|node| (node.meta().id, graph.peek_activation(node.meta().id).unwrap(), node.meta().stability))
+        .collect();
+    nodes.sort_by_key(|(id, _, _)| *id);
+    nodes
+}
+
+#[test]
+fn fast_forward_matches_tick_loop() {
+    let (mut looped, rust, wasm) = seeded();
+    looped.schedule_in(1, ScheduledAction::Dream);
+    looped.schedule_in(3, ScheduledAction::Stimulate { id: rust, force: 0.5 });
+    looped.schedule_in(7, ScheduledAction::Stimulate { id: wasm, force: 0.2 });
+    looped.forget(wasm);
+    let mut jumped = looped.clone();
+
+    for _ in 0..10 {
+        looped.tick();
+    }
+    let ran = jumped.fast_forward(10);
+
+    assert_eq!(ran, 3);
+    assert_eq!(jumped.current_tick, looped.current_tick);
+    assert_eq!(state(&jumped), state(&looped));
+    assert_eq!(jumped.scheduled_events(), looped.scheduled_events());
+}
+
+#[test]
+fn scheduled_dream_never_moves_the_clock_back() {
+    let (mut graph, _, _) = seeded();
+    graph.schedule_in(1, ScheduledAction::Dream);
+    graph.fast_forward(10);
+    assert_eq!(graph.current_tick, 1 + 480 + 9);
+}
+
+#[test]
+fn wake_up_runs_due_events() {
+    let (mut graph, rust, _) = seeded();
+    graph.last_saved = Some(graph.now());
+    graph.schedule_in(5, ScheduledAction::Stimulate { id: rust, force: 0.5 });
+    let clock = loom_db::ManualClock::new(graph.now() + chrono::Duration::minutes(30));
+    graph.set_time_source(std::sync::Arc::new(clock));
+
+    let report = graph.wake_up();
+
+    assert_eq!(report.ticks_elapsed, 30);
+    assert_eq!(graph.current_tick, 30);
+    assert!(graph.scheduled_events().is_empty());
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "server")]
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use loom_db::{http_router, LoomGraph, SharedLoom, MAX_TICKS_PER_REQUEST};
+use tower::ServiceExt;
+
+async fn post_tick(brain: &SharedLoom, body: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::post("/tick").header("content-type", "application/json").body(Body::from(body.to_string())).unwrap();
+    let response = http_router(brain.clone()).oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn tick_fast_forwards_and_caps_the_jump() {
+    let brain = SharedLoom::new(LoomGraph::new(0.95).with_seed(41));
+
+    let (status, body) = post_tick(&brain, r#"{"ticks": 500}"#).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["tick"], 500);
+
+    let (status, body) = post_tick(&brain, &format!(r#"{{"ticks": {}}}"#, MAX_TICKS_PER_REQUEST + 1)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].is_string());
+    assert_eq!(brain.read().current_tick, 500);
+}
+
+#[tokio::test]
+async fn malformed_input_gets_a_json_error() {
+    let brain = SharedLoom::new(LoomGraph::new(0.95).with_seed(42));
+
+    let (status, body) = post_tick(&brain, r#"{"ticks": -3}"#).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["error"].is_string());
+
+    let request = Request::get("/search?limit=5").body(Body::empty()).unwrap();
+    let response = http_router(brain).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert!(body["error"].is_string());
+}
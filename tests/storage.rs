@@ -0,0 +1,68 @@
+use loom_db::{LoomGraph, MemoryBackend, StorageBackend};
+
+#[test]
+fn save_to_backend_drops_removed_nodes() {
+    let mut graph = LoomGraph::new(0.95).with_seed(3);
+    let js = graph.add_concept("JavaScript".into(), "Web language".into());
+    let dup = graph.add_concept("JS".into(), "Scripting".into());
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    graph.connect(dup, rust, 0.5);
+
+    let mut backend = MemoryBackend::new();
+    graph.save_to_backend(&mut backend).unwrap();
+    assert_eq!(backend.node_ids().unwrap().len(), 3);
+
+    assert!(graph.merge_nodes(js, dup));
+    graph.save_to_backend(&mut backend).unwrap();
+
+    let reloaded = LoomGraph::load_from_backend(&backend).unwrap();
+    assert_eq!(reloaded.node_count(), 2);
+    assert!(reloaded.get_node(dup).is_none());
+    assert_eq!(reloaded.neighbors(js).len(), 1);
+}
+
+#[test]
+fn undone_removal_is_kept() {
+    let mut graph = LoomGraph::new(0.95).with_seed(4);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let mut backend = MemoryBackend::new();
+    graph.save_to_backend(&mut backend).unwrap();
+
+    graph.set_namespace(rust, Some("scratch".into()));
+    assert_eq!(graph.drop_namespace("scratch"), 1);
+    assert_eq!(graph.undo(1), 1);
+    graph.save_to_backend(&mut backend).unwrap();
+
+    assert!(LoomGraph::load_from_backend(&backend).unwrap().get_node(rust).is_some());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_save_removes_merged_nodes() {
+    let mut graph = LoomGraph::new(0.95).with_seed(5);
+    let js = graph.add_concept("JavaScript".into(), "Web language".into());
+    let dup = graph.add_concept("JS".into(), "Scripting".into());
+    let mut backend = loom_db::SqliteBackend::open_in_memory().unwrap();
+    graph.save_to_backend(&mut backend).unwrap();
+
+    graph.merge_nodes(js, dup);
+    graph.save_to_backend(&mut backend).unwrap();
+
+    assert_eq!(backend.node_ids().unwrap(), vec![js]);
+}
+
+#[test]
+fn saves_stamp_the_graph_clock() {
+    let mut graph = LoomGraph::new(0.95).with_seed(6);
+    graph.add_concept("Rust".into(), "Systems language".into());
+    let epoch = Some(chrono::DateTime::<chrono::Utc>::UNIX_EPOCH);
+
+    graph.save_to_backend(&mut MemoryBackend::new()).unwrap();
+    assert_eq!(graph.last_saved, epoch);
+
+    graph.last_saved = None;
+    let path = std::env::temp_dir().join(format!("loom-stamp-{}.bin", std::process::id()));
+    graph.save_binary(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(graph.last_saved, epoch);
+}
@@ -0,0 +1,82 @@
+use loom_db::LoomGraph;
+
+fn contents(graph: &LoomGraph) -> Vec<(String, Vec<String>)> {
+    let mut nodes: Vec<(String, Vec<String>)> = graph.nodes.values()
+        .map(|node| {
+            let id = node.meta().id;
+            let mut edges: Vec<String> = graph.neighbors(id).iter().map(|(target, kind, weight)| format!("{}:{}:{}", target, kind, weight)).collect();
+            edges.sort();
+            // `seq` é a ordem de inserção local de cada réplica
+            let mut node = node.clone();
+            node.meta_mut().seq = 0;
+            (serde_json::to_string(&node).unwrap(), edges)
+        })
+        .collect();
+    nodes.sort();
+    nodes
+}
+
+fn exchange(from: &mut LoomGraph, to: &mut LoomGraph) {
+    let clock = to.sync_clock();
+    let changes = from.changes_since(&clock);
+    to.apply_changes(changes);
+}
+
+#[test]
+fn replicas_converge_after_exchanging_both_ways() {
+    let mut origin = LoomGraph::new(0.95).with_seed(31);
+    let rust = origin.add_concept("Rust".into(), "Systems language".into());
+    let wasm = origin.add_concept("WASM".into(), "Portable bytecode".into());
+    origin.connect(rust, wasm, 0.4);
+    origin.sync_clock();
+
+    let mut laptop = origin.clone_graph();
+    let mut phone = origin.clone_graph();
+
+    laptop.set_tag(rust, "topic".into(), "lang".into());
+    laptop.set_confidence(rust, 0.8);
+    let note = laptop.add_episode("Read the WASM spec".into());
+    laptop.connect(note, wasm, 0.6);
+
+    phone.add_alias(wasm, "WebAssembly");
+    phone.add_episode("Benchmarked the crate".into());
+
+    exchange(&mut laptop, &mut phone);
+    exchange(&mut phone, &mut laptop);
+    exchange(&mut laptop, &mut phone);
+
+    assert_eq!(contents(&laptop), contents(&phone));
+    assert_eq!(laptop.node_count(), 4);
+    assert_eq!(phone.search_native("webassembly").len(), 1);
+}
+
+#[test]
+fn backup_round_trip_keeps_the_sync_clock() {
+    let mut graph = LoomGraph::new(0.95).with_seed(32);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let wasm = graph.add_concept("WASM".into(), "Portable bytecode".into());
+    let tokio = graph.add_concept("Tokio".into(), "Async runtime".into());
+    graph.connect(rust, wasm, 0.4);
+    graph.connect(rust, tokio, 0.6);
+    for (key, value) in [("topic", "lang"), ("source", "chat"), ("owner", "me"), ("lang", "en")] {
+        graph.set_tag(rust, key.into(), value.into());
+    }
+    let clock = graph.sync_clock();
+
+    let mut restored = LoomGraph::from_backup_json(&graph.export_backup()).unwrap();
+    assert_eq!(restored.sync_clock(), clock);
+}
+
+#[test]
+fn sync_state_serializes_in_canonical_order() {
+    let mut graph = LoomGraph::new(0.95).with_seed(33);
+    for name in ["Rust", "WASM", "Tokio", "Serde", "Axum", "Rayon"] {
+        graph.add_concept(name.into(), "Crate".into());
+    }
+    graph.sync_clock();
+    let backup = graph.export_backup();
+
+    for _ in 0..4 {
+        assert_eq!(LoomGraph::from_backup_json(&backup).unwrap().export_backup(), backup);
+    }
+}
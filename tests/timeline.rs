@@ -0,0 +1,36 @@
+use loom_db::{LoomConfig, LoomGraph};
+
+fn snapshot(graph: &LoomGraph) -> String {
+    let mut nodes: Vec<String> = graph.nodes.values().map(|n| serde_json::to_string(n).unwrap()).collect();
+    nodes.sort();
+    format!("{} {:?} {}", graph.current_tick, nodes, graph.edge_count())
+}
+
+#[test]
+fn event_log_is_capped_and_folds_into_its_base() {
+    let config = LoomConfig::builder().with(|c| c.event_log_capacity = 4).build().unwrap();
+    let mut graph = LoomGraph::with_config(config).unwrap().with_seed(51);
+    graph.enable_event_log();
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let wasm = graph.add_concept("WASM".into(), "Portable bytecode".into());
+    graph.connect(rust, wasm, 0.6);
+    for _ in 0..5 {
+        graph.tick();
+        graph.stimulate(rust, 0.2);
+    }
+
+    assert_eq!(graph.event_log.events().len(), 4);
+    assert!(graph.reconstruct_at(0).is_none());
+    assert_eq!(snapshot(&graph.reconstruct_at(graph.current_tick).unwrap()), snapshot(&graph));
+}
+
+#[test]
+fn event_log_is_not_saved_with_the_graph() {
+    let mut graph = LoomGraph::new(0.95).with_seed(52);
+    graph.enable_event_log();
+    graph.add_concept("Rust".into(), "Systems language".into());
+
+    let restored = LoomGraph::from_binary(&graph.to_binary().unwrap()).unwrap();
+    assert!(!restored.event_log.is_enabled());
+    assert!(restored.reconstruct_at(0).is_none());
+}
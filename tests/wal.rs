@@ -0,0 +1,139 @@
+use loom_db::{LoomGraph, WalOptions};
+use std::path::PathBuf;
+
+struct TempWal {
+    dir: PathBuf,
+}
+
+impl TempWal {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("loom-wal-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self { dir }
+    }
+
+    fn wal(&self) -> String {
+        self.dir.join("graph.wal").to_str().unwrap().to_string()
+    }
+
+    fn snapshot(&self) -> String {
+        self.dir.join("graph.snap").to_str().unwrap().to_string()
+    }
+
+    fn attach(&self, graph: &mut LoomGraph) {
+        graph.attach_wal(&self.wal(), WalOptions { snapshot_path: self.snapshot(), compact_every: None }).unwrap();
+    }
+
+    fn recover(&self) -> LoomGraph {
+        LoomGraph::recover(&self.snapshot(), &self.wal()).unwrap()
+    }
+}
+
+impl Drop for TempWal {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn snapshot(graph: &LoomGraph) -> String {
+    let mut nodes: Vec<String> = graph.nodes.values().map(|n| serde_json::to_string(n).unwrap()).collect();
+    nodes.sort();
+    format!("{} {:?} {}", graph.current_tick, nodes, graph.edge_count())
+}
+
+#[test]
+fn boost_batch_replays_without_ripple() {
+    let wal = TempWal::new("batch");
+    let mut graph = LoomGraph::new(0.95).with_seed(21);
+    wal.attach(&mut graph);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let wasm = graph.add_concept("WASM".into(), "Portable bytecode".into());
+    let tokio = graph.add_concept("Tokio".into(), "Async runtime".into());
+    graph.connect(rust, tokio, 0.9);
+    for _ in 0..20 {
+        graph.tick();
+    }
+    graph.boost_batch(&[rust, wasm], 0.4);
+
+    assert_eq!(snapshot(&wal.recover()), snapshot(&graph));
+}
+
+#[test]
+fn recover_replays_metadata_and_config_edits() {
+    let wal = TempWal::new("edits");
+    let mut graph = LoomGraph::new(0.95).with_seed(22);
+    wal.attach(&mut graph);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let js = graph.add_concept("JS".into(), "Scripting".into());
+    let trip = graph.add_episode("Flew to Lisbon".into());
+    graph.connect(trip, rust, 0.5);
+    graph.tick();
+    graph.pin(rust);
+    graph.set_decay_override(js, Some(loom_db::DecayOverride::Never));
+    graph.set_tag(trip, "source".into(), "chat".into());
+    graph.set_namespace(js, Some("work".into()));
+    graph.add_alias(js, "JavaScript");
+    graph.set_confidence(rust, 0.9);
+    graph.forget(trip);
+    let mut config = graph.config();
+    config.pin_floor = 0.3;
+    graph.set_config(config).unwrap();
+    graph.set_decay_rate(0.9).unwrap();
+    for _ in 0..10 {
+        graph.tick();
+    }
+
+    let recovered = wal.recover();
+    assert_eq!(snapshot(&recovered), snapshot(&graph));
+    assert_eq!(recovered.config(), graph.config());
+    assert!(recovered.is_forgotten(trip));
+    assert_eq!(recovered.find_concept_by_name("javascript"), Some(js));
+}
+
+#[test]
+fn scheduled_events_fire_once_across_recovery() {
+    let wal = TempWal::new("schedule");
+    let mut graph = LoomGraph::new(0.95).with_seed(23);
+    wal.attach(&mut graph);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    graph.schedule_at(5, loom_db::ScheduledAction::Stimulate { id: rust, force: 0.5 });
+    graph.compact_wal().unwrap();
+    graph.fast_forward(10);
+    graph.schedule_at(20, loom_db::ScheduledAction::Prune { threshold: 0.1 });
+
+    let recovered = wal.recover();
+    assert_eq!(snapshot(&recovered), snapshot(&graph));
+    assert_eq!(recovered.scheduled_events(), graph.scheduled_events());
+}
+
+#[test]
+fn recover_keeps_the_creation_config_without_compaction() {
+    let wal = TempWal::new("config");
+    let config = loom_db::LoomConfig::builder().decay_rate(0.8).seconds_per_tick(3600).with(|c| c.pin_floor = 0.2).build().unwrap();
+    let mut graph = LoomGraph::with_config(config).unwrap().with_seed(24);
+    wal.attach(&mut graph);
+    graph.add_concept("Rust".into(), "Systems language".into());
+    graph.tick();
+
+    let recovered = wal.recover();
+    assert_eq!(recovered.decay_rate, graph.decay_rate);
+    assert_eq!(recovered.config(), graph.config());
+    assert_eq!(snapshot(&recovered), snapshot(&graph));
+}
+
+#[test]
+fn access_statistics_replay_with_their_original_time() {
+    let wal = TempWal::new("access");
+    let mut graph = LoomGraph::new(0.95).with_seed(25);
+    wal.attach(&mut graph);
+    let rust = graph.add_concept("Rust".into(), "Systems language".into());
+    let wasm = graph.add_concept("WASM".into(), "Portable bytecode".into());
+    graph.recall("rust");
+    graph.stimulate(wasm, 0.3);
+    graph.recall("wasm");
+
+    let recovered = wal.recover();
+    assert_eq!(snapshot(&recovered), snapshot(&graph));
+    assert_eq!(recovered.most_accessed(2), vec![(wasm, 2), (rust, 1)]);
+    assert_eq!(recovered.least_recently_used(2), graph.least_recently_used(2));
+}